@@ -1,15 +1,18 @@
 use core::time::Duration;
-use storm::cgmath::prelude::*;
 use storm::cgmath::*;
 use storm::color::RGBA8;
 use storm::event::*;
 use storm::graphics::{
-    clear, set_window_display_mode, shaders::sprite::*, window_logical_size, ClearMode, DisplayMode,
-    TextureSection, Vsync, WindowSettings,
+    clear, set_vsync, set_window_display_mode,
+    shaders::particle::{Curve, EmitterSettings, Gradient, ParticleEmitter, ParticleShader},
+    viewport_logical_size, vsync, BlendMode, ClearMode, ContextSettings, DebugMode, DisplayMode, RenderState,
+    ScalingMode, Vsync, WindowSettings,
 };
 use storm::math::Transform;
 use storm::*;
 
+const CAPACITY: usize = 200_000;
+
 /// Run with: cargo run --example particles --release
 fn main() {
     start(
@@ -20,37 +23,68 @@ fn main() {
                 height: 1024,
                 resizable: true,
             },
+            scaling: ScalingMode::Window,
             vsync: Vsync::Disabled,
+            debug: DebugMode::Disabled,
+            context: ContextSettings::default(),
         },
         run,
     );
 }
 
+fn settings() -> EmitterSettings {
+    EmitterSettings {
+        rate: 40_000.0,
+        lifetime: (2.0, 4.0),
+        direction: 0.25,
+        spread: 0.15,
+        speed: (300.0, 600.0),
+        gravity: Vector2::new(0.0, -400.0),
+        drag: 0.2,
+        size: Curve::new(&[(0.0, 1.0), (0.1, 4.0), (1.0, 2.0)]),
+        color: Gradient::new(&[
+            (0.0, RGBA8::new(255, 255, 200, 255)),
+            (0.3, RGBA8::new(255, 140, 40, 200)),
+            (1.0, RGBA8::new(120, 20, 200, 0)),
+        ]),
+        ..EmitterSettings::default()
+    }
+}
+
+fn create_emitter(shader: &ParticleShader, transform: &mut Transform) -> ParticleEmitter {
+    let mut emitter = ParticleEmitter::new(shader, settings(), CAPACITY, transform.matrix());
+    emitter.set_render_state(RenderState::blended(BlendMode::Additive));
+    emitter
+}
+
 fn run() -> impl FnMut(Event) {
     wait_periodic(Some(Duration::from_secs_f32(1.0 / 144.0)));
     let mut is_dragging = false;
 
-    let mut transform = Transform::new(window_logical_size());
-    let sprite_shader = SpriteShader::new();
-    let mut pass = SpriteShaderPass::new(transform.matrix());
-    transform.set().rotation = 0.125;
-
-    let mut sprites = Vec::new();
-    let mut particles = Vec::new();
-    const RANGE: i32 = 100;
-    for x in -RANGE..RANGE {
-        for y in -RANGE..RANGE {
-            let (sprite, particle) = Particle::new(Vector3::new(x as f32 * 5.0, y as f32 * 5.0, 0.0));
-            sprites.push(sprite);
-            particles.push(particle);
-        }
-    }
-    pass.buffer.set(&sprites);
+    let mut transform = Transform::new(viewport_logical_size());
+    let gpu_shader = ParticleShader::new();
+    let cpu_shader = ParticleShader::with_cpu_simulation();
+    let mut use_gpu = true;
+    let mut emitter = create_emitter(&gpu_shader, &mut transform);
 
     move |event| match event {
         Event::CloseRequested => request_stop(),
         Event::KeyPressed(key) => match key {
             KeyboardButton::Escape => request_stop(),
+            KeyboardButton::Space => emitter.burst(20_000),
+            KeyboardButton::E => emitter.emitting = !emitter.emitting,
+            KeyboardButton::C => {
+                // Switches between simulating on the GPU and on the CPU.
+                use_gpu = !use_gpu;
+                let shader = if use_gpu {
+                    &gpu_shader
+                } else {
+                    &cpu_shader
+                };
+                let pos = emitter.pos;
+                emitter = create_emitter(shader, &mut transform);
+                emitter.pos = pos;
+            }
             KeyboardButton::U => set_window_display_mode(DisplayMode::Windowed {
                 width: 1500,
                 height: 1000,
@@ -62,24 +96,32 @@ fn run() -> impl FnMut(Event) {
                 resizable: true,
             }),
             KeyboardButton::O => set_window_display_mode(DisplayMode::WindowedFullscreen),
-            KeyboardButton::P => set_window_display_mode(DisplayMode::Fullscreen),
+            KeyboardButton::P => set_window_display_mode(DisplayMode::Fullscreen {
+                monitor: None,
+                video_mode: None,
+            }),
+            KeyboardButton::V => set_vsync(match vsync() {
+                Vsync::Disabled => Vsync::Enabled,
+                Vsync::Enabled | Vsync::Adaptive => Vsync::Disabled,
+            }),
             _ => {}
         },
         Event::CursorPressed {
             button,
             ..
         } => match button {
-            CursorButton::Left => is_dragging = true,
+            CursorButton::Right => is_dragging = true,
             _ => {}
         },
         Event::CursorReleased {
             button,
             ..
         } => match button {
-            CursorButton::Left => is_dragging = false,
+            CursorButton::Right => is_dragging = false,
             _ => {}
         },
         Event::CursorMoved {
+            normalized_pos,
             delta,
             ..
         } => {
@@ -87,6 +129,7 @@ fn run() -> impl FnMut(Event) {
                 let scale = transform.get().scale;
                 transform.set().translation += delta / scale;
             }
+            emitter.pos = transform.screen_to_world(normalized_pos);
         }
         Event::WindowResized {
             logical_size,
@@ -99,52 +142,17 @@ fn run() -> impl FnMut(Event) {
             ScrollDirection::Down => transform.set().scale /= 1.1,
             _ => {}
         },
-        Event::Update(_delta) => {
-            for index in 0..sprites.len() {
-                Particle::tick(&mut sprites[index], &mut particles[index], 1.0 / 144.0);
-            }
-            pass.buffer.set(&sprites);
+        Event::Update(delta) => {
+            let shader = if use_gpu {
+                &gpu_shader
+            } else {
+                &cpu_shader
+            };
+            emitter.update(shader, delta);
             clear(ClearMode::color_depth(RGBA8::BLACK));
-            pass.set_ortho(transform.generate());
-            pass.draw(&sprite_shader);
+            emitter.set_ortho(transform.generate());
+            emitter.draw(shader);
         }
         _ => {}
     }
 }
-
-pub struct Particle {
-    pos: Vector2<f32>,
-    velocity: Vector2<f32>,
-    acceleration: Vector2<f32>,
-}
-
-impl Particle {
-    const G: f32 = 10.674;
-    const MASS: f32 = 500.0;
-
-    pub fn new(pos: Vector3<f32>) -> (Sprite, Particle) {
-        let sprite = Sprite::new(pos, Vector2::new(2.0, 2.0), TextureSection::full(), RGBA8::WHITE, 0.0);
-        let velocity = if pos.y < 0.0 {
-            Vector2::new(20.0, 0.0)
-        } else {
-            Vector2::new(-20.0, 0.0)
-        };
-        let particle = Particle {
-            pos: pos.truncate(),
-            velocity: velocity,
-            acceleration: Vector2::zero(),
-        };
-        (sprite, particle)
-    }
-
-    pub fn tick(sprite: &mut Sprite, particle: &mut Particle, delta: f32) {
-        let length_squared = particle.pos.x * particle.pos.x + particle.pos.y * particle.pos.y;
-        let length = f32::sqrt(length_squared);
-        let norm = particle.pos / length;
-        particle.acceleration = -(norm * (Self::G * Self::MASS)) / length_squared.max(1000.0);
-
-        particle.velocity += particle.acceleration;
-        particle.pos += particle.velocity * delta;
-        sprite.pos = particle.pos.extend(0.0);
-    }
-}
@@ -8,7 +8,8 @@ use storm::fontdue::{layout::LayoutSettings, Font};
 use storm::graphics::{
     clear,
     shaders::{sprite::*, text::*},
-    window_logical_size, ClearMode, DisplayMode, Vsync, WindowSettings,
+    viewport_logical_size, ClearMode, ContextSettings, DebugMode, DisplayMode, ScalingMode, Vsync,
+    WindowSettings,
 };
 use storm::math::{Transform, AABB2D};
 use storm::*;
@@ -27,7 +28,13 @@ fn main() {
                 height: 1024,
                 resizable: true,
             },
+            scaling: ScalingMode::Letterbox {
+                width: 1280.0,
+                height: 1024.0,
+            },
             vsync: Vsync::Disabled,
+            debug: DebugMode::Disabled,
+            context: ContextSettings::default(),
         },
         run,
     );
@@ -37,7 +44,7 @@ fn run() -> impl FnMut(Event) {
     wait_periodic(Some(Duration::from_secs_f32(1.0 / 144.0)));
 
     let boop = Sound::from_flac(SOUND).unwrap();
-    let mut transform = Transform::new(window_logical_size());
+    let mut transform = Transform::new(viewport_logical_size());
     let text_shader = TextShader::new();
     let sprite_shader = SpriteShader::new();
 
@@ -65,7 +72,7 @@ fn run() -> impl FnMut(Event) {
     let mut background = SpriteShaderPass::new(transform.matrix());
     background.buffer.set(&[Sprite {
         pos: Vector3::new(-500.0, -400.0, -0.1),
-        size: Vector2::new(1000, 800),
+        size: Vector2::new(1000.0, 800.0),
         color: RGBA8::new(15, 15, 15, 255),
         ..Default::default()
     }]);
@@ -77,13 +84,13 @@ fn run() -> impl FnMut(Event) {
     let mut paddle_sprites = [
         Sprite {
             pos: Vector3::new(-500.0, -60.0, 0.0),
-            size: Vector2::new(30, 120),
+            size: Vector2::new(30.0, 120.0),
             color: RGBA8::WHITE,
             ..Default::default()
         },
         Sprite {
             pos: Vector3::new(500.0 - 30.0, -60.0, 0.0),
-            size: Vector2::new(30, 120),
+            size: Vector2::new(30.0, 120.0),
             color: RGBA8::WHITE,
             ..Default::default()
         },
@@ -94,7 +101,7 @@ fn run() -> impl FnMut(Event) {
     let mut ball_speed = Vector3::new(-300.0, 0.0, 0.0);
     let mut ball_sprites = [Sprite {
         pos: Vector3::new(-12.0, -12.0, 0.0),
-        size: Vector2::new(25, 25),
+        size: Vector2::new(25.0, 25.0),
         color: RGBA8::WHITE,
         ..Default::default()
     }];
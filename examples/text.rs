@@ -1,9 +1,11 @@
 use core::time::Duration;
+use storm::cgmath::Vector2;
 use storm::color::RGBA8;
 use storm::event::*;
 use storm::fontdue::{layout::LayoutSettings, Font};
 use storm::graphics::{
-    clear, shaders::text::*, window_logical_size, ClearMode, DisplayMode, Vsync, WindowSettings,
+    clear, shaders::text::*, viewport_logical_size, ClearMode, ContextSettings, DebugMode, DisplayMode,
+    ScalingMode, Vsync, WindowSettings,
 };
 use storm::math::Transform;
 use storm::*;
@@ -21,7 +23,10 @@ fn main() {
                 height: 1024,
                 resizable: true,
             },
+            scaling: ScalingMode::Window,
             vsync: Vsync::Disabled,
+            debug: DebugMode::Disabled,
+            context: ContextSettings::default(),
         },
         run,
     );
@@ -30,35 +35,29 @@ fn main() {
 fn run() -> impl FnMut(Event) {
     wait_periodic(Some(Duration::from_secs_f32(1.0 / 144.0)));
     let mut is_dragging = false;
-    let mut transform = Transform::new(window_logical_size());
+    let mut transform = Transform::new(viewport_logical_size());
     let text_shader = TextShader::new();
+    let sdf_shader = SdfTextShader::new();
 
-    // Create a Layers to draw on.
+    // Create a Layers to draw on. The bitmap layer blurs when zoomed in, while the distance field
+    // layer stays sharp and draws an outline and a shadow.
     let mut text_layer = TextShaderPass::new(transform.matrix());
+    let mut sdf_layer = SdfTextShaderPass::new(transform.matrix());
+    sdf_layer.set_effects(TextEffects {
+        outline_width: 0.04,
+        outline_color: RGBA8::new(200, 60, 20, 255),
+        shadow_offset: Vector2::new(0.05, -0.05),
+        shadow_softness: 0.03,
+        shadow_color: RGBA8::new(0, 0, 0, 180),
+        ..TextEffects::default()
+    });
 
     // Setup the layout for our text.
     let fonts = [Font::from_bytes(FONT, Default::default()).unwrap()];
-    let layout_settings = LayoutSettings {
-        x: 100.0,
-        y: 500.0,
-        max_width: Some(500.0),
-        ..Default::default()
-    };
 
     // Append some text with our layout settings.
-    const SIZE: f32 = 100.0;
     let mut message = String::from("Nice\nPost");
-    text_layer.append(
-        &fonts,
-        &layout_settings,
-        &[Text {
-            text: &message,
-            font_index: 0,
-            px: SIZE,
-            color: RGBA8::WHITE,
-            depth: 0.0,
-        }],
-    );
+    append(&mut text_layer, &mut sdf_layer, &fonts, &message);
 
     move |event| match event {
         Event::ReceivedCharacter(char) => {
@@ -67,18 +66,7 @@ fn run() -> impl FnMut(Event) {
                 return;
             }
             message.push(char);
-            text_layer.clear_text();
-            text_layer.append(
-                &fonts,
-                &layout_settings,
-                &[Text {
-                    text: &message,
-                    font_index: 0,
-                    px: SIZE,
-                    color: RGBA8::WHITE,
-                    depth: 0.0,
-                }],
-            );
+            append(&mut text_layer, &mut sdf_layer, &fonts, &message);
         }
         Event::CloseRequested => request_stop(),
         Event::KeyPressed(key) => match key {
@@ -88,18 +76,7 @@ fn run() -> impl FnMut(Event) {
             }
             KeyboardButton::Back => {
                 message.pop();
-                text_layer.clear_text();
-                text_layer.append(
-                    &fonts,
-                    &layout_settings,
-                    &[Text {
-                        text: &message,
-                        font_index: 0,
-                        px: SIZE,
-                        color: RGBA8::WHITE,
-                        depth: 0.0,
-                    }],
-                );
+                append(&mut text_layer, &mut sdf_layer, &fonts, &message);
             }
             _ => {}
         },
@@ -139,9 +116,34 @@ fn run() -> impl FnMut(Event) {
         }
         Event::Update(_delta) => {
             clear(ClearMode::color_depth(RGBA8::BLACK));
-            text_layer.set_ortho(transform.generate());
+            let ortho = transform.generate();
+            text_layer.set_ortho(ortho);
             text_layer.draw(&text_shader);
+            sdf_layer.set_ortho(ortho);
+            sdf_layer.draw(&sdf_shader);
         }
         _ => {}
     }
 }
+
+/// Replaces the text in both layers with the message.
+fn append(text_layer: &mut TextShaderPass, sdf_layer: &mut SdfTextShaderPass, fonts: &[Font], message: &str) {
+    const SIZE: f32 = 100.0;
+    let layout_settings = |x| LayoutSettings {
+        x,
+        y: 500.0,
+        max_width: Some(500.0),
+        ..Default::default()
+    };
+    let text = [Text {
+        text: message,
+        font_index: 0,
+        px: SIZE,
+        color: RGBA8::WHITE,
+        depth: 0.0,
+    }];
+    text_layer.clear_text();
+    text_layer.append(fonts, &layout_settings(100.0), &text);
+    sdf_layer.clear_text();
+    sdf_layer.append(fonts, &layout_settings(700.0), &text);
+}
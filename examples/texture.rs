@@ -5,7 +5,8 @@ use storm::audio::*;
 use storm::color::RGBA8;
 use storm::event::*;
 use storm::graphics::{
-    clear, shaders::sprite::*, window_logical_size, ClearMode, DisplayMode, Texture, Vsync, WindowSettings,
+    clear, shaders::sprite::*, viewport_logical_size, ClearMode, ContextSettings, DebugMode, DisplayMode,
+    ScalingMode, Texture, Vsync, WindowSettings,
 };
 use storm::math::Transform;
 use storm::*;
@@ -23,7 +24,10 @@ fn main() {
                 height: 1024,
                 resizable: true,
             },
+            scaling: ScalingMode::Window,
             vsync: Vsync::Disabled,
+            debug: DebugMode::Disabled,
+            context: ContextSettings::default(),
         },
         run,
     );
@@ -32,7 +36,7 @@ fn main() {
 fn run() -> impl FnMut(Event) {
     wait_periodic(Some(Duration::from_secs_f32(1.0 / 144.0)));
 
-    let mut transform = Transform::new(window_logical_size());
+    let mut transform = Transform::new(viewport_logical_size());
     let sprite_shader = SpriteShader::new();
     let mut pass = SpriteShaderPass::new(transform.matrix());
     pass.atlas = Texture::from_png(TEXTURE_A);
@@ -44,13 +48,13 @@ fn run() -> impl FnMut(Event) {
         Sprite::default(),
         Sprite {
             pos: Vector3::new(-200.0, -62.0, 0.0),
-            size: Vector2::new(25, 25),
+            size: Vector2::new(25.0, 25.0),
             color: RGBA8::WHITE,
             ..Sprite::default()
         },
         Sprite {
             pos: Vector3::new(-200.0, -50.0, 0.0),
-            size: Vector2::new(400, 3),
+            size: Vector2::new(400.0, 3.0),
             color: RGBA8::BLACK,
             ..Sprite::default()
         },
@@ -74,9 +78,9 @@ fn run() -> impl FnMut(Event) {
             ..
         } => {
             if pos.x >= back_sprites[1].pos.x
-                && pos.x <= back_sprites[1].pos.x + back_sprites[1].size.x as f32
+                && pos.x <= back_sprites[1].pos.x + back_sprites[1].size.x
                 && pos.y >= back_sprites[1].pos.y
-                && pos.y <= back_sprites[1].pos.y + back_sprites[1].size.y as f32
+                && pos.y <= back_sprites[1].pos.y + back_sprites[1].size.y
             {
                 clicking = true;
             }
@@ -27,6 +27,19 @@ pub fn request_read(relative_path: &str) {
     ctx().assets().push_read(relative_path);
 }
 
+/// Requests a read of an asset, then requests it again each time the asset changes. This produces
+/// an AssetRead event with the result of each read once it has completed. This is intended for
+/// iterating on assets during development, such as shaders.
+///
+/// ## Platform-specific
+///
+/// - **Non-web:** The path is relative to the current working directory. Changes are only watched
+///   for in debug builds. In release builds this is equivalent to [`request_read`].
+/// - **Web:** Changes are not watched for. This is equivalent to [`request_read`].
+pub fn request_watch(relative_path: &str) {
+    ctx().assets().push_watch(relative_path);
+}
+
 pub(crate) trait AssetStateContract {
     /// Creates a new asset state.
     fn init() -> Self;
@@ -34,6 +47,9 @@ pub(crate) trait AssetStateContract {
     /// Pushes a read request to the queue. Relative to the current working directory.
     fn push_read(&mut self, relative_path: &str);
 
+    /// Pushes a watch request to the queue. Relative to the current working directory.
+    fn push_watch(&mut self, relative_path: &str);
+
     /// Pops the next available read off the queue, returning None if there are no finished reads
     /// available.
     fn try_pop_read(&mut self) -> Option<Asset>;
@@ -5,13 +5,27 @@ use alloc::{
     vec::Vec,
 };
 use core::time::Duration;
-use std::fs::File;
+use std::fs::{self, File};
+use std::time::{Instant, SystemTime};
 use std::{io, io::Read};
 use std::{thread, thread::JoinHandle};
 
+/// How often watched assets are checked for changes.
+const WATCH_INTERVAL: Duration = Duration::from_millis(250);
+
+enum Request {
+    Read(String),
+    Watch(String),
+}
+
+struct Watched {
+    relative_path: String,
+    modified: Option<SystemTime>,
+}
+
 pub(crate) struct AssetState {
     handle: JoinHandle<()>,
-    read_request_sender: Producer<String>,
+    read_request_sender: Producer<Request>,
     read_result_receiver: Consumer<Asset>,
 }
 
@@ -20,11 +34,34 @@ impl AssetStateContract for AssetState {
         let (read_request_sender, read_request_receiver) = spsc_make(256);
         let (read_result_sender, read_result_receiver) = spsc_make(256);
 
-        let handle = thread::spawn(move || loop {
-            while let Some(relative_path) = read_request_receiver.try_pop() {
-                read_result_sender.push(read(relative_path));
+        let handle = thread::spawn(move || {
+            let mut watched: Vec<Watched> = Vec::new();
+            let mut last_poll = Instant::now();
+            loop {
+                while let Some(request) = read_request_receiver.try_pop() {
+                    match request {
+                        Request::Read(relative_path) => read_result_sender.push(read(relative_path)),
+                        Request::Watch(relative_path) => {
+                            watched.push(Watched {
+                                modified: modified(&relative_path),
+                                relative_path: relative_path.clone(),
+                            });
+                            read_result_sender.push(read(relative_path));
+                        }
+                    }
+                }
+                if !watched.is_empty() && last_poll.elapsed() >= WATCH_INTERVAL {
+                    last_poll = Instant::now();
+                    for watch in &mut watched {
+                        let modified = modified(&watch.relative_path);
+                        if modified != watch.modified {
+                            watch.modified = modified;
+                            read_result_sender.push(read(watch.relative_path.clone()));
+                        }
+                    }
+                }
+                thread::sleep(Duration::from_millis(1));
             }
-            thread::sleep(Duration::from_millis(1));
         });
 
         AssetState {
@@ -35,7 +72,15 @@ impl AssetStateContract for AssetState {
     }
 
     fn push_read(&mut self, relative_path: &str) {
-        self.read_request_sender.push(relative_path.to_string())
+        self.read_request_sender.push(Request::Read(relative_path.to_string()))
+    }
+
+    fn push_watch(&mut self, relative_path: &str) {
+        if cfg!(debug_assertions) {
+            self.read_request_sender.push(Request::Watch(relative_path.to_string()))
+        } else {
+            self.push_read(relative_path)
+        }
     }
 
     fn try_pop_read(&mut self) -> Option<Asset> {
@@ -56,6 +101,11 @@ fn read(relative_path: String) -> Asset {
     Asset::new_ok(relative_path, contents)
 }
 
+/// Gets the last modification time of the file, or None if it's unavailable.
+fn modified(relative_path: &str) -> Option<SystemTime> {
+    fs::metadata(relative_path).and_then(|metadata| metadata.modified()).ok()
+}
+
 impl From<io::ErrorKind> for LoaderError {
     fn from(error: io::ErrorKind) -> Self {
         match error {
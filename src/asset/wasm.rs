@@ -54,6 +54,10 @@ impl AssetStateContract for AssetState {
         }
     }
 
+    fn push_watch(&mut self, relative_path: &str) {
+        self.push_read(relative_path);
+    }
+
     fn try_pop_read(&mut self) -> Option<Asset> {
         if self.results.len() == 0 {
             let array: Array = self.pull.call0(&JsValue::UNDEFINED).unwrap().dyn_into().unwrap();
@@ -0,0 +1,50 @@
+use super::{ColorComponentType, ColorDescriptor, ColorLayoutFormat};
+
+/// Simple 16 bit depth type, for depth textures. The value is normalized into the range [0, 1]
+/// when sampled.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Depth16 {
+    pub depth: u16,
+}
+
+impl Depth16 {
+    pub const fn new(depth: u16) -> Depth16 {
+        Depth16 {
+            depth,
+        }
+    }
+}
+
+impl ColorDescriptor for Depth16 {
+    fn component_type() -> ColorComponentType {
+        ColorComponentType::U16
+    }
+    fn layout() -> ColorLayoutFormat {
+        ColorLayoutFormat::Depth
+    }
+}
+
+/// Simple 32 bit floating point depth type, for depth textures.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Depth32F {
+    pub depth: f32,
+}
+
+impl Depth32F {
+    pub const fn new(depth: f32) -> Depth32F {
+        Depth32F {
+            depth,
+        }
+    }
+}
+
+impl ColorDescriptor for Depth32F {
+    fn component_type() -> ColorComponentType {
+        ColorComponentType::F32
+    }
+    fn layout() -> ColorLayoutFormat {
+        ColorLayoutFormat::Depth
+    }
+}
@@ -1,11 +1,21 @@
+mod depth;
+mod r16;
+mod r32f;
 mod r8;
 mod rg8;
 mod rgb8;
+mod rgba16f;
+mod rgba32f;
 mod rgba8;
 
+pub use depth::{Depth16, Depth32F};
+pub use r16::R16;
+pub use r32f::R32F;
 pub use r8::R8;
 pub use rg8::RG8;
 pub use rgb8::RGB8;
+pub use rgba16f::RGBA16F;
+pub use rgba32f::RGBA32F;
 pub use rgba8::RGBA8;
 
 use crate::graphics::{PixelFormat, PixelInternalFormat, PixelType};
@@ -44,11 +54,14 @@ pub trait ColorDescriptor: Sized + Copy {
     fn layout() -> ColorLayoutFormat;
 }
 
-/// Represents the type of each color component.
+/// Represents the type of each color component. U8 and U16 components are normalized into the
+/// range [0, 1] when sampled. F16 components are IEEE 754 half precision floats stored as u16s.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u32)]
 pub enum ColorComponentType {
     U8 = PixelType::UnsignedByte as u32,
+    U16 = PixelType::UnsignedShort as u32,
+    F16 = PixelType::HalfFloat as u32,
     F32 = PixelType::Float as u32,
 }
 
@@ -67,16 +80,35 @@ pub enum ColorLayoutFormat {
     RGB = PixelFormat::RGB as u32,
     RGBA = PixelFormat::RGBA as u32,
     BGRA = PixelFormat::BGRA as u32,
+    /// A single depth component, for depth textures. Depth layouts require U16 or F32 components.
+    Depth = PixelFormat::DepthComponent as u32,
 }
 
 impl ColorLayoutFormat {
-    pub(crate) fn gpu_format(&self) -> PixelInternalFormat {
-        match self {
-            ColorLayoutFormat::R => PixelInternalFormat::R8,
-            ColorLayoutFormat::RG => PixelInternalFormat::RG8,
-            ColorLayoutFormat::RGB => PixelInternalFormat::RGB8,
-            ColorLayoutFormat::RGBA => PixelInternalFormat::RGBA8,
-            ColorLayoutFormat::BGRA => PixelInternalFormat::RGBA8,
+    pub(crate) fn gpu_format(&self, component_type: ColorComponentType) -> PixelInternalFormat {
+        use ColorComponentType::*;
+        match (self, component_type) {
+            (ColorLayoutFormat::R, U8) => PixelInternalFormat::R8,
+            (ColorLayoutFormat::R, U16) => PixelInternalFormat::R16,
+            (ColorLayoutFormat::R, F16) => PixelInternalFormat::R16f,
+            (ColorLayoutFormat::R, F32) => PixelInternalFormat::R32f,
+            (ColorLayoutFormat::RG, U8) => PixelInternalFormat::RG8,
+            (ColorLayoutFormat::RG, U16) => PixelInternalFormat::RG16,
+            (ColorLayoutFormat::RG, F16) => PixelInternalFormat::RG16f,
+            (ColorLayoutFormat::RG, F32) => PixelInternalFormat::RG32f,
+            (ColorLayoutFormat::RGB, U8) => PixelInternalFormat::RGB8,
+            (ColorLayoutFormat::RGB, U16) => PixelInternalFormat::RGB16,
+            (ColorLayoutFormat::RGB, F16) => PixelInternalFormat::RGB16f,
+            (ColorLayoutFormat::RGB, F32) => PixelInternalFormat::RGB32f,
+            (ColorLayoutFormat::RGBA | ColorLayoutFormat::BGRA, U8) => PixelInternalFormat::RGBA8,
+            (ColorLayoutFormat::RGBA | ColorLayoutFormat::BGRA, U16) => PixelInternalFormat::RGBA16,
+            (ColorLayoutFormat::RGBA | ColorLayoutFormat::BGRA, F16) => PixelInternalFormat::RGBA16f,
+            (ColorLayoutFormat::RGBA | ColorLayoutFormat::BGRA, F32) => PixelInternalFormat::RGBA32f,
+            (ColorLayoutFormat::Depth, U16) => PixelInternalFormat::DepthComponent16,
+            (ColorLayoutFormat::Depth, F32) => PixelInternalFormat::DepthComponent32f,
+            (ColorLayoutFormat::Depth, component_type) => {
+                panic!("Depth layouts don't support {:?} components.", component_type)
+            }
         }
     }
 
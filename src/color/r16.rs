@@ -0,0 +1,43 @@
+use super::{ColorComponentType, ColorDescriptor, ColorLayoutFormat};
+
+/// Simple R16 color type to represent single channel data with more precision than R8, such as
+/// heightmaps. The value is normalized into the range [0, 1] when sampled.
+///
+/// ## Platform-specific
+///
+/// - **Web and OpenGL ES:** Requires the `EXT_texture_norm16` extension. Creating a texture
+///   without it panics.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct R16 {
+    pub r: u16,
+}
+
+impl R16 {
+    pub const fn new(red: u16) -> R16 {
+        R16 {
+            r: red,
+        }
+    }
+
+    pub fn from_f32(red: f32) -> R16 {
+        R16 {
+            r: (red * 65535.0) as u16,
+        }
+    }
+}
+
+impl Into<f32> for R16 {
+    fn into(self) -> f32 {
+        (self.r as f32) / 65535.0
+    }
+}
+
+impl ColorDescriptor for R16 {
+    fn component_type() -> ColorComponentType {
+        ColorComponentType::U16
+    }
+    fn layout() -> ColorLayoutFormat {
+        ColorLayoutFormat::R
+    }
+}
@@ -0,0 +1,32 @@
+use super::{ColorComponentType, ColorDescriptor, ColorLayoutFormat};
+
+/// Simple R32F color type to represent single channel floating point data, such as heightmaps and
+/// signed distance fields. The value is not normalized when sampled.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct R32F {
+    pub r: f32,
+}
+
+impl R32F {
+    pub const fn new(red: f32) -> R32F {
+        R32F {
+            r: red,
+        }
+    }
+}
+
+impl Into<f32> for R32F {
+    fn into(self) -> f32 {
+        self.r
+    }
+}
+
+impl ColorDescriptor for R32F {
+    fn component_type() -> ColorComponentType {
+        ColorComponentType::F32
+    }
+    fn layout() -> ColorLayoutFormat {
+        ColorLayoutFormat::R
+    }
+}
@@ -0,0 +1,123 @@
+use super::{ColorComponentType, ColorDescriptor, ColorLayoutFormat};
+
+/// Simple RGBA16F color type to represent high dynamic range colors with half the memory of
+/// RGBA32F. Each component is stored as the bits of an IEEE 754 half precision float. The values
+/// are not normalized when sampled.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RGBA16F {
+    pub r: u16,
+    pub g: u16,
+    pub b: u16,
+    pub a: u16,
+}
+
+impl RGBA16F {
+    /// Creates a new color from the bits of half precision floats.
+    pub const fn new(red: u16, green: u16, blue: u16, alpha: u16) -> RGBA16F {
+        RGBA16F {
+            r: red,
+            g: green,
+            b: blue,
+            a: alpha,
+        }
+    }
+
+    /// Creates a new color, converting each component to half precision. Values outside of the
+    /// half precision range become infinity.
+    pub fn from_f32(red: f32, green: f32, blue: f32, alpha: f32) -> RGBA16F {
+        RGBA16F {
+            r: f32_to_f16(red),
+            g: f32_to_f16(green),
+            b: f32_to_f16(blue),
+            a: f32_to_f16(alpha),
+        }
+    }
+}
+
+impl Into<(f32, f32, f32, f32)> for RGBA16F {
+    fn into(self) -> (f32, f32, f32, f32) {
+        (f16_to_f32(self.r), f16_to_f32(self.g), f16_to_f32(self.b), f16_to_f32(self.a))
+    }
+}
+
+impl ColorDescriptor for RGBA16F {
+    fn component_type() -> ColorComponentType {
+        ColorComponentType::F16
+    }
+    fn layout() -> ColorLayoutFormat {
+        ColorLayoutFormat::RGBA
+    }
+}
+
+/// Converts a single precision float into the bits of a half precision float, rounding toward
+/// zero.
+fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xFF) as i32;
+    let mantissa = bits & 0x007F_FFFF;
+    if exponent == 0xFF {
+        // Infinity and NaN, keeping NaN quiet.
+        let nan = if mantissa != 0 {
+            0x0200
+        } else {
+            0
+        };
+        return sign | 0x7C00 | nan;
+    }
+    let exponent = exponent - 127 + 15;
+    if exponent >= 0x1F {
+        sign | 0x7C00
+    } else if exponent <= 0 {
+        if exponent < -10 {
+            sign
+        } else {
+            // Subnormal, shift the implicit leading bit into the mantissa.
+            let mantissa = mantissa | 0x0080_0000;
+            sign | (mantissa >> (14 - exponent)) as u16
+        }
+    } else {
+        sign | ((exponent as u16) << 10) | (mantissa >> 13) as u16
+    }
+}
+
+/// Converts the bits of a half precision float into a single precision float.
+fn f16_to_f32(value: u16) -> f32 {
+    let sign = ((value & 0x8000) as u32) << 16;
+    let exponent = ((value >> 10) & 0x1F) as u32;
+    let mantissa = (value & 0x03FF) as u32;
+    let bits = match exponent {
+        0 if mantissa == 0 => sign,
+        0 => {
+            // Subnormal, normalize the mantissa.
+            let shift = mantissa.leading_zeros() - 21;
+            let mantissa = (mantissa << shift) & 0x03FF;
+            let exponent = 127 - 15 + 1 - shift;
+            sign | (exponent << 23) | (mantissa << 13)
+        }
+        0x1F => sign | 0x7F80_0000 | (mantissa << 13),
+        _ => sign | ((exponent + 127 - 15) << 23) | (mantissa << 13),
+    };
+    f32::from_bits(bits)
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn half_conversion() {
+        for &value in &[0.0f32, -0.0, 1.0, -2.5, 0.5, 65504.0, 0.000061035156, 0.000000059604645] {
+            assert_eq!(f16_to_f32(f32_to_f16(value)), value);
+        }
+        assert_eq!(f32_to_f16(1.0), 0x3C00);
+        assert_eq!(f32_to_f16(100000.0), 0x7C00);
+        assert_eq!(f16_to_f32(0x7C00), f32::INFINITY);
+        assert!(f16_to_f32(f32_to_f16(f32::NAN)).is_nan());
+    }
+}
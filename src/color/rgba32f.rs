@@ -0,0 +1,38 @@
+use super::{ColorComponentType, ColorDescriptor, ColorLayoutFormat};
+
+/// Simple RGBA32F color type to represent high dynamic range colors and data. The values are not
+/// normalized when sampled.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RGBA32F {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl RGBA32F {
+    pub const fn new(red: f32, green: f32, blue: f32, alpha: f32) -> RGBA32F {
+        RGBA32F {
+            r: red,
+            g: green,
+            b: blue,
+            a: alpha,
+        }
+    }
+}
+
+impl Into<(f32, f32, f32, f32)> for RGBA32F {
+    fn into(self) -> (f32, f32, f32, f32) {
+        (self.r, self.g, self.b, self.a)
+    }
+}
+
+impl ColorDescriptor for RGBA32F {
+    fn component_type() -> ColorComponentType {
+        ColorComponentType::F32
+    }
+    fn layout() -> ColorLayoutFormat {
+        ColorLayoutFormat::RGBA
+    }
+}
@@ -23,6 +23,15 @@ impl EventConverter {
         }
     }
 
+    fn resize<T: 'static + FnMut(Event)>(&mut self, event_handler: &mut T) {
+        let graphics = ctx().graphics();
+        graphics.resize_viewport(self.physical_size, self.logical_size);
+        event_handler(Event::WindowResized {
+            physical_size: self.physical_size,
+            logical_size: graphics.viewport().logical_size,
+        });
+    }
+
     pub fn push<T: 'static + FnMut(Event)>(&mut self, event: WindowEvent, event_handler: &mut T) {
         match event {
             // Window
@@ -31,11 +40,7 @@ impl EventConverter {
                 self.physical_size = Vector2::new(physical_size.width as f32, physical_size.height as f32);
                 self.logical_size = self.physical_size / self.scale_factor;
 
-                ctx().graphics().resize_viewport(self.physical_size, self.logical_size);
-                event_handler(Event::WindowResized {
-                    physical_size: self.physical_size,
-                    logical_size: self.logical_size,
-                });
+                self.resize(event_handler);
             }
             WindowEvent::ScaleFactorChanged {
                 scale_factor,
@@ -45,11 +50,7 @@ impl EventConverter {
                 self.physical_size = Vector2::new(new_inner_size.width as f32, new_inner_size.height as f32);
                 self.logical_size = self.physical_size / self.scale_factor;
 
-                ctx().graphics().resize_viewport(self.physical_size, self.logical_size);
-                event_handler(Event::WindowResized {
-                    physical_size: self.physical_size,
-                    logical_size: self.logical_size,
-                });
+                self.resize(event_handler);
             }
 
             // Keyboard
@@ -77,13 +78,15 @@ impl EventConverter {
                 position,
                 ..
             } => {
+                let viewport = ctx().graphics().viewport();
                 let cursor_pos = Vector2::new(position.x as f32, self.physical_size.y - position.y as f32);
-                let normalized_pos =
-                    (cursor_pos.div_element_wise(self.physical_size) * 2.0) - Vector2::new(1.0, 1.0);
-                let delta = (cursor_pos - self.cursor_pos) / self.scale_factor;
+                let pos = viewport.logical_pos(cursor_pos);
+                let normalized_pos = viewport.normalized_pos(cursor_pos);
+                let delta = pos - viewport.logical_pos(self.cursor_pos);
                 self.cursor_pos = cursor_pos;
                 event_handler(Event::CursorMoved {
                     physical_pos: self.cursor_pos,
+                    pos,
                     normalized_pos,
                     delta,
                 });
@@ -115,13 +118,13 @@ impl EventConverter {
                 winit::event::ElementState::Pressed => {
                     event_handler(Event::CursorPressed {
                         button,
-                        pos: self.cursor_pos,
+                        pos: ctx().graphics().viewport().logical_pos(self.cursor_pos),
                     });
                 }
                 winit::event::ElementState::Released => {
                     event_handler(Event::CursorReleased {
                         button,
-                        pos: self.cursor_pos,
+                        pos: ctx().graphics().viewport().logical_pos(self.cursor_pos),
                     });
                 }
             },
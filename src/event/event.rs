@@ -22,14 +22,16 @@ pub enum Event {
     CursorPressed {
         /// Button pressed.
         button: CursorButton,
-        /// Cursor position at time of press.
+        /// Cursor position at time of press. This is in the viewport's logical coordinates, with
+        /// (0,0) being the bottom left of the viewport. See [`Event::CursorMoved`].
         pos: Vector2<f32>,
     },
     /// Cursor release event. Contains the button released and the position it was released at.
     CursorReleased {
         /// Button released.
         button: CursorButton,
-        /// Cursor position at time of release.
+        /// Cursor position at time of release. This is in the viewport's logical coordinates, with
+        /// (0,0) being the bottom left of the viewport. See [`Event::CursorMoved`].
         pos: Vector2<f32>,
     },
     /// Cursor wheel scroll event.
@@ -40,11 +42,16 @@ pub enum Event {
         /// Current cursor position. This is based on the physical size of the window, with (0,0)
         /// being the bottom left.
         physical_pos: Vector2<f32>,
+        /// Current cursor position in the viewport's logical coordinates, with (0,0) being the
+        /// bottom left of the viewport. When the [`crate::graphics::ScalingMode`] has a virtual
+        /// resolution, this is in virtual pixels. This is outside of the viewport's logical size
+        /// while the cursor is over the bars around a letterboxed viewport.
+        pos: Vector2<f32>,
         /// Current cursor position. This is normalized where the x and y values are between -1 and
-        /// 1, with the bottom left of the screen being (-1, -1), and the top right being (1, 1).
+        /// 1, with the bottom left of the viewport being (-1, -1), and the top right being (1, 1).
         /// This may be useful for converting screen space coordinates into world space.
         normalized_pos: Vector2<f32>,
-        /// Change from last position. This is based on the logical size of the window.
+        /// Change from last position. This is in the viewport's logical coordinates.
         delta: Vector2<f32>,
     },
     /// Cursor left the bounds of the window event.
@@ -53,7 +60,10 @@ pub enum Event {
     CursorEntered,
     /// Window resized event. Contains the new dimensions of the window.
     WindowResized {
+        /// The physical size of the window.
         physical_size: Vector2<f32>,
+        /// The logical size of the viewport. This is the window's logical size, unless the
+        /// [`crate::graphics::ScalingMode`] has a virtual resolution.
         logical_size: Vector2<f32>,
     },
     /// This event is useful as a place to put your code that should be run after all state-changing
@@ -0,0 +1,60 @@
+use crate::graphics::TextureSection;
+use alloc::vec::Vec;
+
+/// How an animation continues once it reaches its last frame.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PlaybackMode {
+    /// Starts over from the first frame.
+    Loop,
+    /// Plays backwards to the first frame, then forwards again.
+    PingPong,
+    /// Stops on the last frame.
+    Once,
+}
+
+/// A single frame of an animation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AnimationFrame {
+    /// The section of the texture shown during the frame.
+    pub texture: TextureSection,
+    /// How long the frame is shown for, in seconds.
+    pub duration: f32,
+    /// A user defined event, produced by [`crate::graphics::animation::AnimationPlayer::update`]
+    /// when playback enters the frame. This is useful for things like footstep sounds.
+    pub event: Option<u32>,
+}
+
+impl AnimationFrame {
+    /// Creates a new frame without an event.
+    pub fn new(texture: TextureSection, duration: f32) -> AnimationFrame {
+        AnimationFrame {
+            texture,
+            duration,
+            event: None,
+        }
+    }
+}
+
+/// An ordered set of frames and how to play them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnimationClip {
+    /// The frames of the animation, in order.
+    pub frames: Vec<AnimationFrame>,
+    /// How the animation continues once it reaches its last frame.
+    pub mode: PlaybackMode,
+}
+
+impl AnimationClip {
+    /// Creates a new clip.
+    pub fn new(frames: Vec<AnimationFrame>, mode: PlaybackMode) -> AnimationClip {
+        AnimationClip {
+            frames,
+            mode,
+        }
+    }
+
+    /// The time it takes to play every frame once, in seconds.
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|frame| frame.duration).sum()
+    }
+}
@@ -0,0 +1,7 @@
+mod clip;
+mod player;
+mod sheet;
+
+pub use self::clip::{AnimationClip, AnimationFrame, PlaybackMode};
+pub use self::player::{AnimationEvent, AnimationPlayer};
+pub use self::sheet::{SpriteSheet, SpriteSheetFrame};
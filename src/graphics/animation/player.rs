@@ -0,0 +1,214 @@
+use crate::graphics::{
+    animation::{AnimationClip, PlaybackMode},
+    shaders::sprite::Sprite,
+    TextureSection,
+};
+use alloc::vec::Vec;
+
+/// Frames shorter than this are treated as this long, so a large update can't advance forever.
+const MIN_FRAME_DURATION: f32 = 0.001;
+
+/// Something that happened during an [`AnimationPlayer::update`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AnimationEvent {
+    /// Playback entered a frame with a user defined event.
+    Frame(u32),
+    /// Playback returned to the first frame of a looping or ping-pong clip.
+    Looped,
+    /// Playback reached the end of a clip that plays once.
+    Finished,
+}
+
+/// Tracks playback of an [`AnimationClip`]. The player doesn't own the clip, so one clip can be
+/// shared by many players. Pass the same clip to each call, and use [`AnimationPlayer::restart`]
+/// when switching clips.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AnimationPlayer {
+    frame: usize,
+    elapsed: f32,
+    speed: f32,
+    reversing: bool,
+    finished: bool,
+}
+
+impl Default for AnimationPlayer {
+    fn default() -> AnimationPlayer {
+        AnimationPlayer::new()
+    }
+}
+
+impl AnimationPlayer {
+    /// Creates a new player at the start of a clip, playing at normal speed.
+    pub fn new() -> AnimationPlayer {
+        AnimationPlayer {
+            frame: 0,
+            elapsed: 0.0,
+            speed: 1.0,
+            reversing: false,
+            finished: false,
+        }
+    }
+
+    /// Returns playback to the first frame.
+    pub fn restart(&mut self) {
+        self.frame = 0;
+        self.elapsed = 0.0;
+        self.reversing = false;
+        self.finished = false;
+    }
+
+    /// The playback speed multiplier. The default is 1.0.
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Sets the playback speed multiplier. For example, 2.0 plays twice as fast, and 0.0 pauses.
+    /// Negative speeds are treated as 0.0.
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.max(0.0);
+    }
+
+    /// The index of the current frame.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Returns true if a clip that plays once has reached its end.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// The texture of the current frame, or None if the clip has no frames.
+    pub fn texture(&self, clip: &AnimationClip) -> Option<TextureSection> {
+        clip.frames.get(self.frame).map(|frame| frame.texture)
+    }
+
+    /// Sets the sprite's texture to the current frame. The sprite is unchanged if the clip has no
+    /// frames.
+    pub fn apply(&self, clip: &AnimationClip, sprite: &mut Sprite) {
+        if let Some(texture) = self.texture(clip) {
+            sprite.texture = texture;
+        }
+    }
+
+    /// Advances playback by the given time in seconds, usually the delta from
+    /// [`crate::event::Event::Update`]. Returns the events that happened, in order.
+    pub fn update(&mut self, clip: &AnimationClip, delta: f32) -> Vec<AnimationEvent> {
+        let mut events = Vec::new();
+        let count = clip.frames.len();
+        if count == 0 || self.finished {
+            return events;
+        }
+        // The clip may have changed since the last update.
+        if self.frame >= count {
+            self.restart();
+        }
+        self.elapsed += delta * self.speed;
+        loop {
+            let duration = clip.frames[self.frame].duration.max(MIN_FRAME_DURATION);
+            if self.elapsed < duration {
+                break;
+            }
+            self.elapsed -= duration;
+            match clip.mode {
+                PlaybackMode::Loop => {
+                    self.frame += 1;
+                    if self.frame == count {
+                        self.frame = 0;
+                        events.push(AnimationEvent::Looped);
+                    }
+                }
+                PlaybackMode::PingPong => {
+                    if count == 1 {
+                        events.push(AnimationEvent::Looped);
+                    } else if self.reversing {
+                        self.frame -= 1;
+                        if self.frame == 0 {
+                            self.reversing = false;
+                            events.push(AnimationEvent::Looped);
+                        }
+                    } else {
+                        self.frame += 1;
+                        if self.frame == count - 1 {
+                            self.reversing = true;
+                        }
+                    }
+                }
+                PlaybackMode::Once => {
+                    if self.frame + 1 == count {
+                        self.elapsed = 0.0;
+                        self.finished = true;
+                        events.push(AnimationEvent::Finished);
+                        break;
+                    }
+                    self.frame += 1;
+                }
+            }
+            if let Some(event) = clip.frames[self.frame].event {
+                events.push(AnimationEvent::Frame(event));
+            }
+        }
+        events
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::animation::AnimationFrame;
+    use alloc::vec;
+
+    fn clip(count: usize, mode: PlaybackMode) -> AnimationClip {
+        AnimationClip::new(vec![AnimationFrame::new(TextureSection::full(), 0.1); count], mode)
+    }
+
+    fn frames(player: &mut AnimationPlayer, clip: &AnimationClip, steps: usize) -> Vec<usize> {
+        (0..steps)
+            .map(|_| {
+                player.update(clip, 0.1001);
+                player.frame()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn loop_wraps() {
+        let clip = clip(3, PlaybackMode::Loop);
+        let mut player = AnimationPlayer::new();
+        assert_eq!(frames(&mut player, &clip, 4), [1, 2, 0, 1]);
+        assert!(!player.is_finished());
+    }
+
+    #[test]
+    fn ping_pong_reverses() {
+        let clip = clip(3, PlaybackMode::PingPong);
+        let mut player = AnimationPlayer::new();
+        assert_eq!(frames(&mut player, &clip, 6), [1, 2, 1, 0, 1, 2]);
+    }
+
+    #[test]
+    fn once_finishes() {
+        let clip = clip(2, PlaybackMode::Once);
+        let mut player = AnimationPlayer::new();
+        assert_eq!(player.update(&clip, 0.15), []);
+        assert_eq!(player.update(&clip, 0.1), [AnimationEvent::Finished]);
+        assert_eq!(player.frame(), 1);
+        assert!(player.is_finished());
+        assert_eq!(player.update(&clip, 1.0), []);
+    }
+
+    #[test]
+    fn events_in_order() {
+        let mut clip = clip(3, PlaybackMode::Loop);
+        clip.frames[0].event = Some(7);
+        clip.frames[2].event = Some(9);
+        let mut player = AnimationPlayer::new();
+        player.set_speed(2.0);
+        let events = player.update(&clip, 0.16);
+        assert_eq!(events, [AnimationEvent::Frame(9), AnimationEvent::Looped, AnimationEvent::Frame(7)]);
+    }
+}
@@ -0,0 +1,336 @@
+use crate::graphics::{
+    animation::{AnimationClip, AnimationFrame, PlaybackMode},
+    SpriteSheetError, TextureSection,
+};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use cgmath::Vector2;
+use core::{fmt, marker::PhantomData};
+use hashbrown::HashMap;
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+
+/// The duration of frames that don't specify one, in seconds.
+const DEFAULT_FRAME_DURATION: f32 = 0.1;
+
+/// A named region of a sprite sheet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpriteSheetFrame {
+    /// The name of the frame, usually the name of the source image.
+    pub name: String,
+    /// The section of the sheet's texture the frame covers.
+    pub texture: TextureSection,
+    /// The size of the frame in pixels.
+    pub size: Vector2<f32>,
+    /// How long the frame is shown for when animated, in seconds.
+    pub duration: f32,
+}
+
+/// Frames and animation clips loaded from sprite sheet JSON. The sheet's image is loaded
+/// separately, for example with [`crate::image::Image::from_png`].
+///
+/// The JSON is usually read with [`crate::asset::request_read`], which produces an
+/// [`crate::event::Event::AssetRead`] with the bytes to pass to [`SpriteSheet::from_json`].
+#[derive(Clone, Debug, Default)]
+pub struct SpriteSheet {
+    frames: Vec<SpriteSheetFrame>,
+    clips: HashMap<String, AnimationClip>,
+}
+
+impl SpriteSheet {
+    /// Parses a sprite sheet exported by Aseprite or TexturePacker, in either the hash or array
+    /// format.
+    ///
+    /// Clips are created from Aseprite frame tags, and from the `animations` lists written by
+    /// TexturePacker's Phaser and PixiJS exporters. Clips from tags follow the tag's direction,
+    /// and play once if the tag repeats once. Other clips loop.
+    pub fn from_json(bytes: &[u8]) -> Result<SpriteSheet, SpriteSheetError> {
+        let json: SheetJson =
+            serde_json::from_slice(bytes).map_err(|error| SpriteSheetError::Parse(error.to_string()))?;
+        let size = json.meta.size;
+
+        let mut frames = Vec::with_capacity(json.frames.0.len());
+        for (name, frame) in json.frames.0 {
+            if frame.rotated {
+                return Err(SpriteSheetError::RotatedFrame(name));
+            }
+            let rect = frame.frame;
+            frames.push(SpriteSheetFrame {
+                texture: TextureSection::from_texture_size(
+                    size.w,
+                    size.h,
+                    rect.x,
+                    rect.x + rect.w,
+                    rect.y,
+                    rect.y + rect.h,
+                ),
+                size: Vector2::new(rect.w as f32, rect.h as f32),
+                duration: frame.duration.map_or(DEFAULT_FRAME_DURATION, |duration| duration / 1000.0),
+                name,
+            });
+        }
+
+        let mut clips = HashMap::new();
+        for tag in json.meta.frame_tags {
+            if tag.from > tag.to || tag.to >= frames.len() {
+                return Err(SpriteSheetError::FrameOutOfRange {
+                    tag: tag.name,
+                    frame: tag.to.max(tag.from),
+                });
+            }
+            let mut clip_frames: Vec<AnimationFrame> =
+                frames[tag.from..=tag.to].iter().map(animation_frame).collect();
+            let reverse = tag.direction == "reverse" || tag.direction == "pingpong_reverse";
+            if reverse {
+                clip_frames.reverse();
+            }
+            let mode = if tag.repeat.as_deref() == Some("1") {
+                PlaybackMode::Once
+            } else if tag.direction.starts_with("pingpong") {
+                PlaybackMode::PingPong
+            } else {
+                PlaybackMode::Loop
+            };
+            clips.insert(tag.name, AnimationClip::new(clip_frames, mode));
+        }
+        for (name, frame_names) in json.animations.0 {
+            let mut clip_frames = Vec::with_capacity(frame_names.len());
+            for frame_name in frame_names {
+                match frames.iter().find(|frame| frame.name == frame_name) {
+                    Some(frame) => clip_frames.push(animation_frame(frame)),
+                    None => return Err(SpriteSheetError::UnknownFrame(frame_name)),
+                }
+            }
+            clips.insert(name, AnimationClip::new(clip_frames, PlaybackMode::Loop));
+        }
+
+        Ok(SpriteSheet {
+            frames,
+            clips,
+        })
+    }
+
+    /// All of the frames in the sheet, in the order they were listed.
+    pub fn frames(&self) -> &[SpriteSheetFrame] {
+        &self.frames
+    }
+
+    /// Gets a frame by name.
+    pub fn frame(&self, name: &str) -> Option<&SpriteSheetFrame> {
+        self.frames.iter().find(|frame| frame.name == name)
+    }
+
+    /// Gets a clip by name.
+    pub fn clip(&self, name: &str) -> Option<&AnimationClip> {
+        self.clips.get(name)
+    }
+
+    /// All of the clips in the sheet, with their names.
+    pub fn clips(&self) -> impl Iterator<Item = (&str, &AnimationClip)> {
+        self.clips.iter().map(|(name, clip)| (name.as_str(), clip))
+    }
+
+    /// Creates a clip from every frame whose name starts with the prefix, in the order they were
+    /// listed. This is useful for sheets without animation data, where frames are named like
+    /// `walk_01.png`, `walk_02.png`.
+    pub fn clip_from_prefix(&self, prefix: &str, mode: PlaybackMode) -> AnimationClip {
+        let frames =
+            self.frames.iter().filter(|frame| frame.name.starts_with(prefix)).map(animation_frame).collect();
+        AnimationClip::new(frames, mode)
+    }
+}
+
+fn animation_frame(frame: &SpriteSheetFrame) -> AnimationFrame {
+    AnimationFrame::new(frame.texture, frame.duration)
+}
+
+#[derive(Deserialize)]
+struct SheetJson {
+    frames: NamedList<FrameJson>,
+    meta: MetaJson,
+    #[serde(default)]
+    animations: NamedList<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct FrameJson {
+    #[serde(default)]
+    filename: String,
+    frame: RectJson,
+    #[serde(default)]
+    rotated: bool,
+    /// Milliseconds, only written by Aseprite.
+    duration: Option<f32>,
+}
+
+#[derive(Copy, Clone, Deserialize)]
+struct RectJson {
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+}
+
+#[derive(Copy, Clone, Deserialize)]
+struct SizeJson {
+    w: u32,
+    h: u32,
+}
+
+#[derive(Deserialize)]
+struct MetaJson {
+    size: SizeJson,
+    #[serde(default, rename = "frameTags")]
+    frame_tags: Vec<TagJson>,
+}
+
+#[derive(Deserialize)]
+struct TagJson {
+    name: String,
+    from: usize,
+    to: usize,
+    #[serde(default)]
+    direction: String,
+    repeat: Option<String>,
+}
+
+/// Named entries that may be written as an object or as an array, keeping the order they were
+/// written in. Frames in the array format carry their name in a `filename` field.
+struct NamedList<T>(Vec<(String, T)>);
+
+impl<T> Default for NamedList<T> {
+    fn default() -> NamedList<T> {
+        NamedList(Vec::new())
+    }
+}
+
+trait Named {
+    fn name(&self) -> String;
+}
+
+impl Named for FrameJson {
+    fn name(&self) -> String {
+        self.filename.clone()
+    }
+}
+
+impl Named for Vec<String> {
+    fn name(&self) -> String {
+        String::new()
+    }
+}
+
+impl<'de, T: Deserialize<'de> + Named> Deserialize<'de> for NamedList<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<NamedList<T>, D::Error> {
+        struct NamedListVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de> + Named> Visitor<'de> for NamedListVisitor<T> {
+            type Value = NamedList<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an object or an array")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<NamedList<T>, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(NamedList(entries))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<NamedList<T>, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(value) = seq.next_element::<T>()? {
+                    entries.push((value.name(), value));
+                }
+                Ok(NamedList(entries))
+            }
+        }
+
+        deserializer.deserialize_any(NamedListVisitor(PhantomData))
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ASEPRITE: &str = r#"{
+        "frames": {
+            "hero 0.aseprite": { "frame": { "x": 0, "y": 0, "w": 16, "h": 24 }, "rotated": false, "duration": 100 },
+            "hero 1.aseprite": { "frame": { "x": 16, "y": 0, "w": 16, "h": 24 }, "rotated": false, "duration": 150 },
+            "hero 2.aseprite": { "frame": { "x": 32, "y": 0, "w": 16, "h": 24 }, "rotated": false, "duration": 100 }
+        },
+        "meta": {
+            "size": { "w": 64, "h": 32 },
+            "frameTags": [
+                { "name": "walk", "from": 0, "to": 2, "direction": "pingpong" },
+                { "name": "die", "from": 1, "to": 2, "direction": "reverse", "repeat": "1" }
+            ]
+        }
+    }"#;
+
+    const TEXTURE_PACKER: &str = r#"{
+        "frames": [
+            { "filename": "walk_01.png", "frame": { "x": 0, "y": 0, "w": 8, "h": 8 }, "rotated": false },
+            { "filename": "walk_02.png", "frame": { "x": 8, "y": 0, "w": 8, "h": 8 }, "rotated": false },
+            { "filename": "idle.png", "frame": { "x": 0, "y": 8, "w": 8, "h": 8 }, "rotated": false }
+        ],
+        "animations": { "walk": ["walk_01.png", "walk_02.png"] },
+        "meta": { "size": { "w": 16, "h": 16 } }
+    }"#;
+
+    #[test]
+    fn aseprite_tags() {
+        let sheet = SpriteSheet::from_json(ASEPRITE.as_bytes()).unwrap();
+        let names: Vec<&str> = sheet.frames().iter().map(|frame| frame.name.as_str()).collect();
+        assert_eq!(names, ["hero 0.aseprite", "hero 1.aseprite", "hero 2.aseprite"]);
+        assert_eq!(sheet.frames()[1].duration, 0.15);
+        assert_eq!(sheet.frames()[1].size, Vector2::new(16.0, 24.0));
+        assert_eq!(sheet.frames()[1].texture, TextureSection::from_texture_size(64, 32, 16, 32, 0, 24));
+
+        let walk = sheet.clip("walk").unwrap();
+        assert_eq!(walk.mode, PlaybackMode::PingPong);
+        assert_eq!(walk.frames.len(), 3);
+        let die = sheet.clip("die").unwrap();
+        assert_eq!(die.mode, PlaybackMode::Once);
+        assert_eq!(die.frames[0].texture, sheet.frames()[2].texture);
+    }
+
+    #[test]
+    fn texture_packer_animations() {
+        let sheet = SpriteSheet::from_json(TEXTURE_PACKER.as_bytes()).unwrap();
+        assert_eq!(sheet.frames()[2].name, "idle.png");
+        assert_eq!(sheet.frames()[2].duration, DEFAULT_FRAME_DURATION);
+        assert_eq!(sheet.clip("walk").unwrap().frames.len(), 2);
+        assert_eq!(sheet.clip_from_prefix("walk_", PlaybackMode::Loop), *sheet.clip("walk").unwrap());
+    }
+
+    #[test]
+    fn invalid_sheets() {
+        let tag =
+            ASEPRITE.replace(r#""to": 2, "direction": "pingpong""#, r#""to": 3, "direction": "pingpong""#);
+        assert_eq!(
+            SpriteSheet::from_json(tag.as_bytes()).unwrap_err(),
+            SpriteSheetError::FrameOutOfRange {
+                tag: "walk".to_string(),
+                frame: 3
+            }
+        );
+        let animation = TEXTURE_PACKER.replace(r#""walk_02.png"]"#, r#""walk_03.png"]"#);
+        assert_eq!(
+            SpriteSheet::from_json(animation.as_bytes()).unwrap_err(),
+            SpriteSheetError::UnknownFrame("walk_03.png".to_string())
+        );
+        assert!(matches!(SpriteSheet::from_json(b"{"), Err(SpriteSheetError::Parse(_))));
+    }
+}
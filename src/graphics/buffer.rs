@@ -1,18 +1,51 @@
 use crate::ctx;
 use crate::graphics::{configure_vertex, resource, BufferBindingTarget, BufferUsage, VertexDescriptor};
 use core::marker::PhantomData;
+use core::ops::Range;
+
+/// Hints to the driver how often the contents of a buffer will change, so it can choose where to
+/// store them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BufferUsageHint {
+    /// The contents are set once and drawn many times. Every set reallocates the buffer.
+    Static,
+    /// The contents are modified repeatedly and drawn many times. The buffer's storage is reused
+    /// between sets when it has enough capacity, and partial updates are cheap.
+    Dynamic,
+    /// The contents are replaced every frame and drawn a few times. The buffer's storage is
+    /// orphaned on every set, so the driver can hand out fresh storage instead of waiting for
+    /// previous draws that are still reading from it.
+    Stream,
+}
+
+impl BufferUsageHint {
+    fn usage(&self) -> BufferUsage {
+        match self {
+            BufferUsageHint::Static => BufferUsage::StaticDraw,
+            BufferUsageHint::Dynamic => BufferUsage::DynamicDraw,
+            BufferUsageHint::Stream => BufferUsage::StreamDraw,
+        }
+    }
+}
 
 /// Buffers a set of elements on the device.
 pub struct Buffer<T: VertexDescriptor + Copy> {
     vbo: resource::Buffer,
     vao: resource::VertexArray,
     vertices: usize,
+    capacity: usize,
+    usage: BufferUsageHint,
     phantom: PhantomData<T>,
 }
 
 impl<T: VertexDescriptor + Copy> Buffer<T> {
-    /// Creates a new buffer.
+    /// Creates a new buffer. The contents are expected to change rarely.
     pub fn new() -> Buffer<T> {
+        Self::with_usage(BufferUsageHint::Static)
+    }
+
+    /// Creates a new buffer with the given usage hint.
+    pub fn with_usage(usage: BufferUsageHint) -> Buffer<T> {
         let gl = ctx().graphics().gl();
 
         let vao = gl.create_vertex_array();
@@ -26,6 +59,8 @@ impl<T: VertexDescriptor + Copy> Buffer<T> {
             vbo,
             vao,
             vertices: 0,
+            capacity: 0,
+            usage,
             phantom: PhantomData,
         }
     }
@@ -35,7 +70,17 @@ impl<T: VertexDescriptor + Copy> Buffer<T> {
         self.vertices
     }
 
-    /// Clears all elements from the buffer.
+    /// Gets the number of elements the buffer's device storage can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Gets the usage hint the buffer was created with.
+    pub fn usage(&self) -> BufferUsageHint {
+        self.usage
+    }
+
+    /// Clears all elements from the buffer. The device storage is kept.
     pub fn clear(&mut self) {
         self.vertices = 0;
     }
@@ -46,10 +91,61 @@ impl<T: VertexDescriptor + Copy> Buffer<T> {
         if self.vertices > 0 {
             let gl = ctx().graphics().gl();
             gl.bind_buffer(BufferBindingTarget::ArrayBuffer, Some(self.vbo));
-            gl.buffer_data(BufferBindingTarget::ArrayBuffer, items, BufferUsage::StaticDraw);
+            match self.usage {
+                BufferUsageHint::Static => {
+                    gl.buffer_data(BufferBindingTarget::ArrayBuffer, items, self.usage.usage());
+                    self.capacity = self.vertices;
+                }
+                BufferUsageHint::Dynamic => {
+                    if self.vertices > self.capacity {
+                        self.capacity = self.vertices.next_power_of_two();
+                        gl.buffer_data_empty(
+                            BufferBindingTarget::ArrayBuffer,
+                            (self.capacity * core::mem::size_of::<T>()) as i32,
+                            self.usage.usage(),
+                        );
+                    }
+                    gl.buffer_sub_data(BufferBindingTarget::ArrayBuffer, 0, items);
+                }
+                BufferUsageHint::Stream => {
+                    // Orphans the previous storage, even if it has enough capacity.
+                    self.capacity = self.capacity.max(self.vertices.next_power_of_two());
+                    gl.buffer_data_empty(
+                        BufferBindingTarget::ArrayBuffer,
+                        (self.capacity * core::mem::size_of::<T>()) as i32,
+                        self.usage.usage(),
+                    );
+                    gl.buffer_sub_data(BufferBindingTarget::ArrayBuffer, 0, items);
+                }
+            }
         }
     }
 
+    /// Overwrites a range of elements already in the buffer, leaving the rest untouched. This only
+    /// uploads the given elements, which is much cheaper than [`Buffer::set`] when few elements
+    /// changed.
+    /// # Arguments
+    ///
+    /// * `range` - The range of elements to overwrite. This must be within the buffer's length.
+    /// * `items` - The new elements. The length must match the length of the range.
+    pub fn update(&mut self, range: Range<usize>, items: &[T]) {
+        assert!(range.start <= range.end && range.end <= self.vertices, "Range is outside of the buffer.");
+        assert!(range.len() == items.len(), "Range length must match the number of items.");
+        if !items.is_empty() {
+            let gl = ctx().graphics().gl();
+            gl.bind_buffer(BufferBindingTarget::ArrayBuffer, Some(self.vbo));
+            gl.buffer_sub_data(
+                BufferBindingTarget::ArrayBuffer,
+                (range.start * core::mem::size_of::<T>()) as i32,
+                items,
+            );
+        }
+    }
+
+    pub(crate) fn vbo(&self) -> resource::Buffer {
+        self.vbo
+    }
+
     pub(crate) fn bind(&self) {
         let gl = ctx().graphics().gl();
         gl.bind_vertex_array(Some(self.vao));
@@ -0,0 +1,89 @@
+use crate::ctx;
+
+/// Block compressed texture formats. Support depends on the device, check
+/// [`CompressedFormat::is_supported`] before uploading.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CompressedFormat {
+    /// S3TC DXT1 (BC1) with no alpha. 8 bytes per 4x4 block.
+    Dxt1Rgb,
+    /// S3TC DXT1 (BC1) with 1 bit alpha. 8 bytes per 4x4 block.
+    Dxt1Rgba,
+    /// S3TC DXT3 (BC2) with explicit alpha. 16 bytes per 4x4 block.
+    Dxt3Rgba,
+    /// S3TC DXT5 (BC3) with interpolated alpha. 16 bytes per 4x4 block.
+    Dxt5Rgba,
+    /// ETC2 with no alpha. 8 bytes per 4x4 block.
+    Etc2Rgb,
+    /// ETC2 with EAC alpha. 16 bytes per 4x4 block.
+    Etc2Rgba,
+    /// ASTC LDR with 4x4 blocks. 16 bytes per block.
+    Astc4x4,
+    /// ASTC LDR with 8x8 blocks. 16 bytes per block.
+    Astc8x8,
+}
+
+impl CompressedFormat {
+    /// Checks if the format is supported by the context. S3TC and ASTC formats require an
+    /// extension. ETC2 formats are supported by OpenGL ES 3.0 and OpenGL 4.3 contexts, and
+    /// otherwise require an extension.
+    pub fn is_supported(&self) -> bool {
+        let gl = ctx().graphics().gl();
+        match self {
+            CompressedFormat::Dxt1Rgb
+            | CompressedFormat::Dxt1Rgba
+            | CompressedFormat::Dxt3Rgba
+            | CompressedFormat::Dxt5Rgba => {
+                gl.supports_extension("GL_EXT_texture_compression_s3tc")
+                    || gl.supports_extension("WEBGL_compressed_texture_s3tc")
+            }
+            CompressedFormat::Etc2Rgb | CompressedFormat::Etc2Rgba => {
+                let (major, minor, embedded) = gl.version();
+                (!embedded && (major, minor) >= (4, 3))
+                    || (embedded && !cfg!(target_arch = "wasm32") && major >= 3)
+                    || gl.supports_extension("GL_ARB_ES3_compatibility")
+                    || gl.supports_extension("WEBGL_compressed_texture_etc")
+            }
+            CompressedFormat::Astc4x4 | CompressedFormat::Astc8x8 => {
+                gl.supports_extension("GL_KHR_texture_compression_astc_ldr")
+                    || gl.supports_extension("WEBGL_compressed_texture_astc")
+            }
+        }
+    }
+
+    /// The width and height of a block in texels.
+    pub fn block_size(&self) -> u32 {
+        match self {
+            CompressedFormat::Astc8x8 => 8,
+            _ => 4,
+        }
+    }
+
+    /// The number of bytes in a block.
+    pub fn block_bytes(&self) -> usize {
+        match self {
+            CompressedFormat::Dxt1Rgb | CompressedFormat::Dxt1Rgba | CompressedFormat::Etc2Rgb => 8,
+            _ => 16,
+        }
+    }
+
+    /// The number of bytes required for a texture of the given size in this format.
+    pub fn data_len(&self, width: u32, height: u32) -> usize {
+        let block_size = self.block_size();
+        let blocks_x = width.div_ceil(block_size);
+        let blocks_y = height.div_ceil(block_size);
+        blocks_x as usize * blocks_y as usize * self.block_bytes()
+    }
+
+    pub(crate) fn internal_format(&self) -> u32 {
+        match self {
+            CompressedFormat::Dxt1Rgb => glow::COMPRESSED_RGB_S3TC_DXT1_EXT,
+            CompressedFormat::Dxt1Rgba => glow::COMPRESSED_RGBA_S3TC_DXT1_EXT,
+            CompressedFormat::Dxt3Rgba => glow::COMPRESSED_RGBA_S3TC_DXT3_EXT,
+            CompressedFormat::Dxt5Rgba => glow::COMPRESSED_RGBA_S3TC_DXT5_EXT,
+            CompressedFormat::Etc2Rgb => glow::COMPRESSED_RGB8_ETC2,
+            CompressedFormat::Etc2Rgba => glow::COMPRESSED_RGBA8_ETC2_EAC,
+            CompressedFormat::Astc4x4 => glow::COMPRESSED_RGBA_ASTC_4x4_KHR,
+            CompressedFormat::Astc8x8 => glow::COMPRESSED_RGBA_ASTC_8x8_KHR,
+        }
+    }
+}
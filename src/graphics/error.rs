@@ -0,0 +1,250 @@
+use crate::graphics::{CompressedFormat, ShaderType};
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+/// The stage of a shader program that failed to compile.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ShaderStage {
+    /// The vertex shader.
+    Vertex,
+    /// The fragment shader.
+    Fragment,
+}
+
+impl ShaderStage {
+    pub(crate) fn shader_type(&self) -> ShaderType {
+        match self {
+            ShaderStage::Vertex => ShaderType::Vertex,
+            ShaderStage::Fragment => ShaderType::Fragment,
+        }
+    }
+}
+
+impl fmt::Display for ShaderStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShaderStage::Vertex => f.write_str("vertex"),
+            ShaderStage::Fragment => f.write_str("fragment"),
+        }
+    }
+}
+
+/// A line of shader source referenced by a compiler error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShaderSourceLine {
+    /// The line number, starting at 1, relative to the source given to the engine. This excludes
+    /// the version directive the engine prepends.
+    pub number: u32,
+    /// The contents of the line.
+    pub text: String,
+}
+
+/// An error that prevents a shader from being created.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ShaderError {
+    /// A shader stage failed to compile.
+    Compile {
+        /// The stage that failed to compile.
+        stage: ShaderStage,
+        /// The info log reported by the driver.
+        log: String,
+        /// The source lines the info log refers to, if they could be determined.
+        lines: Vec<ShaderSourceLine>,
+    },
+    /// The compiled stages failed to link into a program.
+    Link {
+        /// The info log reported by the driver.
+        log: String,
+    },
+    /// The vertex shader has no uniform block with the given name.
+    MissingUniformBlock(String),
+    /// The fragment shader has no texture uniform with the given name.
+    MissingTexture(String),
+}
+
+impl ShaderError {
+    /// Creates a compile error, resolving the source lines referenced by the log.
+    pub(crate) fn compile(stage: ShaderStage, log: String, source: &str) -> ShaderError {
+        let lines = referenced_lines(&log, source);
+        ShaderError::Compile {
+            stage,
+            log,
+            lines,
+        }
+    }
+}
+
+impl fmt::Display for ShaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShaderError::Compile {
+                stage,
+                log,
+                lines,
+            } => {
+                writeln!(f, "Failed to compile {} shader.", stage)?;
+                writeln!(f, "{}", log.trim_end())?;
+                for line in lines {
+                    writeln!(f, "{:5} | {}", line.number, line.text)?;
+                }
+                Ok(())
+            }
+            ShaderError::Link {
+                log,
+            } => {
+                writeln!(f, "Failed to link shader program.")?;
+                write!(f, "{}", log.trim_end())
+            }
+            ShaderError::MissingUniformBlock(name) => {
+                write!(f, "Failed to find uniform block named '{}' in vertex shader.", name)
+            }
+            ShaderError::MissingTexture(name) => {
+                write!(f, "Failed to find texture named '{}' in fragment shader.", name)
+            }
+        }
+    }
+}
+
+/// An error that prevents a texture from being created.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TextureError {
+    /// The compressed format isn't supported by the context.
+    UnsupportedFormat(CompressedFormat),
+    /// The length of the data doesn't match the size of the texture.
+    InvalidDataLength {
+        /// The length in bytes required by the size and format of the texture.
+        expected: usize,
+        /// The length in bytes of the given data.
+        actual: usize,
+    },
+}
+
+impl fmt::Display for TextureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextureError::UnsupportedFormat(format) => {
+                write!(f, "The compressed format {:?} is unsupported by this device.", format)
+            }
+            TextureError::InvalidDataLength {
+                expected,
+                actual,
+            } => write!(f, "Expected {} bytes of texture data, but {} were given.", expected, actual),
+        }
+    }
+}
+
+/// An error that prevents a sprite sheet from being loaded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SpriteSheetError {
+    /// The JSON is malformed or missing required fields.
+    Parse(String),
+    /// A frame is rotated in the sheet, which isn't supported.
+    RotatedFrame(String),
+    /// A frame tag references a frame that doesn't exist.
+    FrameOutOfRange {
+        /// The name of the tag.
+        tag: String,
+        /// The index of the missing frame.
+        frame: usize,
+    },
+    /// An animation references a frame name that doesn't exist.
+    UnknownFrame(String),
+}
+
+impl fmt::Display for SpriteSheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpriteSheetError::Parse(error) => write!(f, "Failed to parse sprite sheet: {}", error),
+            SpriteSheetError::RotatedFrame(name) => {
+                write!(f, "Frame '{}' is rotated, which is unsupported.", name)
+            }
+            SpriteSheetError::FrameOutOfRange {
+                tag,
+                frame,
+            } => write!(f, "Tag '{}' references frame {}, which doesn't exist.", tag, frame),
+            SpriteSheetError::UnknownFrame(name) => {
+                write!(f, "An animation references frame '{}', which doesn't exist.", name)
+            }
+        }
+    }
+}
+
+/// Finds the source lines an info log refers to. Drivers format locations differently, but the
+/// common forms are `0:12(5): error` (Mesa), `0(12) : error` (Nvidia), and `ERROR: 0:12:`
+/// (ANGLE). Line numbers are offset by one to account for the prepended version directive.
+fn referenced_lines(log: &str, source: &str) -> Vec<ShaderSourceLine> {
+    let source: Vec<&str> = source.lines().collect();
+    let mut numbers: Vec<u32> = log.lines().filter_map(parse_line_number).collect();
+    numbers.sort_unstable();
+    numbers.dedup();
+    numbers
+        .into_iter()
+        .filter_map(|number| {
+            let number = number.checked_sub(1)?;
+            let text = source.get(number.checked_sub(1)? as usize)?;
+            Some(ShaderSourceLine {
+                number,
+                text: String::from(*text),
+            })
+        })
+        .collect()
+}
+
+/// Parses the line number from a single info log line. Looks for a source string index followed by
+/// either `:` or `(`, then the line number.
+fn parse_line_number(line: &str) -> Option<u32> {
+    let bytes = line.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index].is_ascii_digit() && (index == 0 || !bytes[index - 1].is_ascii_alphanumeric()) {
+            let start = index;
+            while index < bytes.len() && bytes[index].is_ascii_digit() {
+                index += 1;
+            }
+            if index > start && index < bytes.len() && (bytes[index] == b':' || bytes[index] == b'(') {
+                let number_start = index + 1;
+                let mut number_end = number_start;
+                while number_end < bytes.len() && bytes[number_end].is_ascii_digit() {
+                    number_end += 1;
+                }
+                if number_end > number_start {
+                    return line[number_start..number_end].parse().ok();
+                }
+            }
+        } else {
+            index += 1;
+        }
+    }
+    None
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_number_formats() {
+        assert_eq!(parse_line_number("0:12(5): error: `foo' undeclared"), Some(12));
+        assert_eq!(parse_line_number("0(12) : error C1008: undefined variable \"foo\""), Some(12));
+        assert_eq!(parse_line_number("ERROR: 0:12: 'foo' : undeclared identifier"), Some(12));
+        assert_eq!(parse_line_number("ERROR: 1 compilation errors.  No code generated."), None);
+    }
+
+    #[test]
+    fn referenced_lines_skip_version() {
+        let source = "void main() {\n    foo = 1.0;\n}";
+        let log = "0:3(5): error: `foo' undeclared\n0:3(5): error: value of type float cannot be assigned";
+        let lines = referenced_lines(log, source);
+        assert_eq!(
+            lines,
+            [ShaderSourceLine {
+                number: 2,
+                text: String::from("    foo = 1.0;"),
+            }]
+        );
+    }
+}
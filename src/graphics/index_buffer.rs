@@ -0,0 +1,104 @@
+use crate::ctx;
+use crate::graphics::{resource, BufferBindingTarget, BufferUsage, IndiceType};
+use core::marker::PhantomData;
+
+/// A trait to describe the integer types that can be used as indices into a vertex buffer.
+pub trait IndexDescriptor: Sized + Copy {
+    /// Gets the type of the index.
+    fn index_type() -> IndexType;
+}
+
+/// Represents the type of each index in an index buffer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum IndexType {
+    U8 = IndiceType::UnsignedByte as u32,
+    U16 = IndiceType::UnsignedShort as u32,
+    U32 = IndiceType::UnsignedInt as u32,
+}
+
+impl IndexType {
+    pub(crate) fn indice_type(&self) -> IndiceType {
+        unsafe { core::mem::transmute(*self) }
+    }
+}
+
+impl IndexDescriptor for u8 {
+    fn index_type() -> IndexType {
+        IndexType::U8
+    }
+}
+
+impl IndexDescriptor for u16 {
+    fn index_type() -> IndexType {
+        IndexType::U16
+    }
+}
+
+impl IndexDescriptor for u32 {
+    fn index_type() -> IndexType {
+        IndexType::U32
+    }
+}
+
+/// Buffers a set of indices on the device. Indices reference elements in a vertex buffer, allowing
+/// elements to be reused across primitives when drawing.
+pub struct IndexBuffer<T: IndexDescriptor> {
+    ebo: resource::Buffer,
+    indices: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<T: IndexDescriptor> IndexBuffer<T> {
+    /// Creates a new index buffer.
+    pub fn new() -> IndexBuffer<T> {
+        let gl = ctx().graphics().gl();
+        let ebo = gl.create_buffer();
+        IndexBuffer {
+            ebo,
+            indices: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Gets the number of indices in the buffer.
+    pub fn len(&self) -> usize {
+        self.indices
+    }
+
+    /// Returns true if the buffer has no indices.
+    pub fn is_empty(&self) -> bool {
+        self.indices == 0
+    }
+
+    /// Clears all indices from the buffer.
+    pub fn clear(&mut self) {
+        self.indices = 0;
+    }
+
+    /// Sets the indices in the buffer.
+    pub fn set(&mut self, items: &[T]) {
+        self.indices = items.len();
+        if self.indices > 0 {
+            let gl = ctx().graphics().gl();
+            // The element array binding is part of the vertex array state, so unbind the vertex array
+            // to avoid attaching this buffer to whichever one was last used.
+            gl.bind_vertex_array(None);
+            gl.bind_buffer(BufferBindingTarget::ElementArrayBuffer, Some(self.ebo));
+            gl.buffer_data(BufferBindingTarget::ElementArrayBuffer, items, BufferUsage::StaticDraw);
+        }
+    }
+
+    /// Binds the index buffer to the currently bound vertex array.
+    pub(crate) fn bind(&self) {
+        let gl = ctx().graphics().gl();
+        gl.bind_buffer(BufferBindingTarget::ElementArrayBuffer, Some(self.ebo));
+    }
+}
+
+impl<T: IndexDescriptor> Drop for IndexBuffer<T> {
+    fn drop(&mut self) {
+        let gl = ctx().graphics().gl();
+        gl.delete_buffer(self.ebo);
+    }
+}
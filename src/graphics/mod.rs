@@ -1,32 +1,59 @@
+/// Sprite animation playback and sprite sheet loading.
+pub mod animation;
 /// Bundled sample shaders for basic sprite and text rendering.
 pub mod shaders;
 
 mod buffer;
+mod compressed_format;
+mod error;
+mod index_buffer;
 mod opengl;
+mod render_state;
+mod render_target;
 mod shader;
+mod shader_watcher;
 mod state;
 mod texture;
 mod texture_section;
 mod uniform;
 mod vertex_descriptor;
+mod viewport;
 mod window;
 
-pub use self::buffer::Buffer;
+pub use self::buffer::{Buffer, BufferUsageHint};
+pub use self::compressed_format::CompressedFormat;
+pub use self::error::{ShaderError, ShaderSourceLine, ShaderStage, SpriteSheetError, TextureError};
+pub use self::index_buffer::{IndexBuffer, IndexDescriptor, IndexType};
 pub use self::opengl::{ClearMode, DrawMode};
+pub use self::render_state::{BlendMode, CullMode, RenderState};
+pub use self::render_target::RenderTarget;
 pub use self::shader::{Shader, ShaderDescriptor};
+pub use self::shader_watcher::ShaderWatcher;
 pub use self::state::{
-    clear, default_texture, max_texture_size, set_window_display_mode, set_window_title,
-    viewport_logical_size, viewport_physical_size, window_logical_size, window_physical_size,
+    clear, default_texture, max_texture_layers, max_texture_size, monitors, render_state, scaling_mode,
+    set_cursor_grab, set_cursor_icon, set_cursor_visible, set_render_state, set_render_target,
+    set_scaling_mode, set_vsync, set_window_always_on_top, set_window_display_mode, set_window_icon,
+    set_window_max_size, set_window_min_size, set_window_position, set_window_title, viewport_logical_size,
+    viewport_physical_pos, viewport_physical_size, vsync, window_display_mode, window_logical_size,
+    window_physical_size, window_position,
 };
-pub use self::texture::Texture;
+pub use self::texture::{Texture, TextureFilter};
 pub use self::texture_section::TextureSection;
 pub use self::uniform::Uniform;
-pub use self::vertex_descriptor::{VertexAttribute, VertexDescriptor, VertexInputType, VertexOutputType};
-pub use self::window::{DisplayMode, Vsync, WindowSettings};
+pub use self::vertex_descriptor::{
+    VertexAttribute, VertexDescriptor, VertexInputType, VertexOutputType, VertexStepRate,
+};
+pub use self::viewport::ScalingMode;
+pub use self::window::{
+    ContextProfile, ContextSettings, CursorGrab, DebugMode, DisplayMode, Monitor, VideoMode, Vsync,
+    WindowSettings,
+};
 /// Macro for working with data adhering to GLSL’s std140 layout specification.
 pub use crevice::std140::AsStd140;
+pub use winit::window::CursorIcon;
 
 pub(crate) use self::opengl::*;
 pub(crate) use self::state::OpenGLState;
 pub(crate) use self::vertex_descriptor::configure_vertex;
+pub(crate) use self::viewport::Viewport;
 pub(crate) use self::window::{OpenGLWindow, OpenGLWindowContract};
@@ -1,4 +1,5 @@
 use crate::color::RGBA8;
+use crate::graphics::{ShaderError, ShaderStage};
 use alloc::string::{String, ToString};
 use glow::{HasContext, PixelUnpackData};
 use log::error;
@@ -168,6 +169,7 @@ pub enum TextureLoadTarget {
     TextureCubeMapPositiveZ = glow::TEXTURE_CUBE_MAP_POSITIVE_Z,
     TextureCubeMapNegativeZ = glow::TEXTURE_CUBE_MAP_NEGATIVE_Z,
     ProxyTextureCubeMap = glow::PROXY_TEXTURE_CUBE_MAP,
+    Texture2DArray = glow::TEXTURE_2D_ARRAY,
 }
 
 #[repr(u32)]
@@ -225,6 +227,9 @@ pub enum PixelFormat {
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum PixelInternalFormat {
     DepthComponent = glow::DEPTH_COMPONENT,
+    DepthComponent16 = glow::DEPTH_COMPONENT16,
+    DepthComponent24 = glow::DEPTH_COMPONENT24,
+    DepthComponent32f = glow::DEPTH_COMPONENT32F,
     DepthStencil = glow::DEPTH_STENCIL,
     Red = glow::RED,
     RG = glow::RG,
@@ -254,6 +259,7 @@ pub enum PixelInternalFormat {
     RGB10A2 = glow::RGB10_A2,
     RGB10A2ui = glow::RGB10_A2UI,
     RGBA12 = glow::RGBA12,
+    RGB16 = glow::RGB16,
     RGBA16 = glow::RGBA16,
     SRGB8 = glow::SRGB8,
     SRGB8Alpha8 = glow::SRGB8_ALPHA8,
@@ -303,6 +309,7 @@ pub enum PixelType {
     UnsignedInt = glow::UNSIGNED_INT,
     Int = glow::INT,
     Float = glow::FLOAT,
+    HalfFloat = glow::HALF_FLOAT,
     UnsignedByte3_3_2 = glow::UNSIGNED_BYTE_3_3_2,
     UnsignedByte2_3_3_Rev = glow::UNSIGNED_BYTE_2_3_3_REV,
     UnsignedShort5_6_5 = glow::UNSIGNED_SHORT_5_6_5,
@@ -444,6 +451,21 @@ pub enum PixelStoreAlignment {
     UnpackAlignment = glow::UNPACK_ALIGNMENT,
 }
 
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FramebufferTarget {
+    Framebuffer = glow::FRAMEBUFFER,
+}
+
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FramebufferAttachment {
+    Color0 = glow::COLOR_ATTACHMENT0,
+    Depth = glow::DEPTH_ATTACHMENT,
+    Stencil = glow::STENCIL_ATTACHMENT,
+    DepthStencil = glow::DEPTH_STENCIL_ATTACHMENT,
+}
+
 pub mod resource {
     pub type Shader = glow::Shader;
     pub type Program = glow::Program;
@@ -466,6 +488,8 @@ pub struct OpenGL {
     vertex_array: Option<resource::VertexArray>,
     active_texture_unit: u32,
     bound_textures: [Option<resource::Texture>; 16],
+    framebuffer: Option<resource::Framebuffer>,
+    check_errors: bool,
 }
 
 impl OpenGL {
@@ -477,29 +501,47 @@ impl OpenGL {
             vertex_array: None,
             active_texture_unit: 0,
             bound_textures: [None; 16],
+            framebuffer: None,
+            check_errors: false,
         }
     }
 
     pub fn get_string(&self, parameter: StringTarget) -> String {
-        unsafe { self.gl.get_parameter_string(parameter as u32) }
+        let result = unsafe { self.gl.get_parameter_string(parameter as u32) };
+        self.check_error("get_string");
+        result
     }
 
-    pub fn get_error(&self, context: &str) {
-        let error = unsafe { self.gl.get_error() };
-        match error {
-            glow::INVALID_ENUM => error!("GL ERROR: INVALID_ENUM at {}", context),
-            glow::INVALID_VALUE => error!("GL ERROR: INVALID_VALUE at {}", context),
-            glow::INVALID_OPERATION => error!("GL ERROR: INVALID_OPERATION at {}", context),
-            glow::INVALID_FRAMEBUFFER_OPERATION => {
-                error!("GL ERROR: INVALID_FRAMEBUFFER_OPERATION at {}", context)
-            }
-            glow::OUT_OF_MEMORY => error!("GL ERROR: OUT_OF_MEMORY at {}", context),
-            glow::STACK_UNDERFLOW => error!("GL ERROR: STACK_UNDERFLOW at {}", context),
-            glow::STACK_OVERFLOW => error!("GL ERROR: STACK_OVERFLOW at {}", context),
-            _ => {}
+    /// Enables checking for errors after every call. Errors are logged with the name of the call
+    /// that produced them.
+    pub fn set_check_errors(&mut self, check_errors: bool) {
+        self.check_errors = check_errors;
+    }
+
+    #[inline(always)]
+    fn check_error(&self, call: &str) {
+        if self.check_errors {
+            self.log_errors(call);
         }
-        if error > 0 {
-            panic!("Panic because of the previous error.");
+    }
+
+    fn log_errors(&self, call: &str) {
+        // Multiple error flags may be set at once, but a lost context can report errors
+        // indefinitely, so the number of reads is bounded.
+        for _ in 0..8 {
+            let error = unsafe { self.gl.get_error() };
+            let name = match error {
+                glow::NO_ERROR => return,
+                glow::INVALID_ENUM => "INVALID_ENUM",
+                glow::INVALID_VALUE => "INVALID_VALUE",
+                glow::INVALID_OPERATION => "INVALID_OPERATION",
+                glow::INVALID_FRAMEBUFFER_OPERATION => "INVALID_FRAMEBUFFER_OPERATION",
+                glow::OUT_OF_MEMORY => "OUT_OF_MEMORY",
+                glow::STACK_UNDERFLOW => "STACK_UNDERFLOW",
+                glow::STACK_OVERFLOW => "STACK_OVERFLOW",
+                _ => "UNKNOWN",
+            };
+            error!("GL ERROR: {} (0x{:X}) at {}", name, error, call);
         }
     }
 
@@ -517,30 +559,60 @@ impl OpenGL {
         version
     }
 
-    pub fn shader_program(&self, vertex_shader: &str, fragment_shader: &str) -> resource::Program {
-        let vertex_shader = Self::attach_version(vertex_shader);
-        let fragment_shader = Self::attach_version(fragment_shader);
+    /// Compiles and links a program. Any feedback varyings are captured interleaved, in order, into
+    /// the buffer bound to transform feedback binding 0.
+    pub fn shader_program(
+        &self,
+        vertex_shader: &str,
+        fragment_shader: &str,
+        feedback_varyings: &[&str],
+    ) -> Result<resource::Program, ShaderError> {
+        let vertex = self.compile_shader(ShaderStage::Vertex, vertex_shader)?;
+        let fragment = match self.compile_shader(ShaderStage::Fragment, fragment_shader) {
+            Ok(fragment) => fragment,
+            Err(error) => {
+                unsafe { self.gl.delete_shader(vertex) };
+                return Err(error);
+            }
+        };
         unsafe {
-            let vertex = self.gl.create_shader(ShaderType::Vertex as u32).unwrap();
-            self.gl.shader_source(vertex, &vertex_shader);
-            self.gl.compile_shader(vertex);
-            self.check_shader(vertex).unwrap();
-
-            let fragment = self.gl.create_shader(ShaderType::Fragment as u32).unwrap();
-            self.gl.shader_source(fragment, &fragment_shader);
-            self.gl.compile_shader(fragment);
-            self.check_shader(fragment).unwrap();
-
             let program = self.gl.create_program().unwrap();
             self.gl.attach_shader(program, vertex);
             self.gl.attach_shader(program, fragment);
+            if !feedback_varyings.is_empty() {
+                self.gl.transform_feedback_varyings(program, feedback_varyings, glow::INTERLEAVED_ATTRIBS);
+            }
             self.gl.link_program(program);
-            self.check_program(program).unwrap();
+            let result = self.check_program(program);
 
             self.gl.delete_shader(vertex);
             self.gl.delete_shader(fragment);
 
-            program
+            match result {
+                Ok(()) => Ok(program),
+                Err(log) => {
+                    self.gl.delete_program(program);
+                    Err(ShaderError::Link {
+                        log,
+                    })
+                }
+            }
+        }
+    }
+
+    fn compile_shader(&self, stage: ShaderStage, source: &str) -> Result<resource::Shader, ShaderError> {
+        let versioned = Self::attach_version(source);
+        unsafe {
+            let shader = self.gl.create_shader(stage.shader_type() as u32).unwrap();
+            self.gl.shader_source(shader, &versioned);
+            self.gl.compile_shader(shader);
+            match self.check_shader(shader) {
+                Ok(()) => Ok(shader),
+                Err(log) => {
+                    self.gl.delete_shader(shader);
+                    Err(ShaderError::compile(stage, log, source))
+                }
+            }
         }
     }
 
@@ -568,6 +640,7 @@ impl OpenGL {
         if self.shader_program != program {
             self.shader_program = program;
             unsafe { self.gl.use_program(program) };
+            self.check_error("use_program");
         }
     }
 
@@ -576,10 +649,12 @@ impl OpenGL {
             self.shader_program = None;
         }
         unsafe { self.gl.delete_program(program) };
+        self.check_error("delete_program");
     }
 
     pub fn uniform_block_binding(&self, program: resource::Program, index: u32, binding: u32) {
         unsafe { self.gl.uniform_block_binding(program, index, binding) }
+        self.check_error("uniform_block_binding");
     }
 
     pub fn get_uniform_location(
@@ -587,11 +662,15 @@ impl OpenGL {
         program: resource::Program,
         name: &str,
     ) -> Option<resource::UniformLocation> {
-        unsafe { self.gl.get_uniform_location(program, name) }
+        let result = unsafe { self.gl.get_uniform_location(program, name) };
+        self.check_error("get_uniform_location");
+        result
     }
 
     pub fn get_uniform_block_index(&self, program: resource::Program, name: &str) -> Option<u32> {
-        unsafe { self.gl.get_uniform_block_index(program, name) }
+        let result = unsafe { self.gl.get_uniform_block_index(program, name) };
+        self.check_error("get_uniform_block_index");
+        result
     }
 
     pub fn uniform_matrix_2x2_f32(
@@ -603,6 +682,7 @@ impl OpenGL {
         unsafe {
             self.gl.uniform_matrix_2_f32_slice(location, transpose, v);
         }
+        self.check_error("uniform_matrix_2x2_f32");
     }
 
     pub fn uniform_matrix_3x3_f32(
@@ -614,6 +694,7 @@ impl OpenGL {
         unsafe {
             self.gl.uniform_matrix_3_f32_slice(location, transpose, v);
         }
+        self.check_error("uniform_matrix_3x3_f32");
     }
 
     pub fn uniform_matrix_4x4_f32(
@@ -625,22 +706,26 @@ impl OpenGL {
         unsafe {
             self.gl.uniform_matrix_4_f32_slice(location, transpose, v);
         }
+        self.check_error("uniform_matrix_4x4_f32");
     }
 
     pub fn uniform_1_i32(&self, location: Option<&resource::UniformLocation>, x: i32) {
         unsafe {
             self.gl.uniform_1_i32(location, x);
         }
+        self.check_error("uniform_1_i32");
     }
     pub fn uniform_2_i32(&self, location: Option<&resource::UniformLocation>, x: i32, y: i32) {
         unsafe {
             self.gl.uniform_2_i32(location, x, y);
         }
+        self.check_error("uniform_2_i32");
     }
     pub fn uniform_3_i32(&self, location: Option<&resource::UniformLocation>, x: i32, y: i32, z: i32) {
         unsafe {
             self.gl.uniform_3_i32(location, x, y, z);
         }
+        self.check_error("uniform_3_i32");
     }
     pub fn uniform_4_i32(
         &self,
@@ -653,21 +738,25 @@ impl OpenGL {
         unsafe {
             self.gl.uniform_4_i32(location, x, y, z, w);
         }
+        self.check_error("uniform_4_i32");
     }
     pub fn uniform_1_u32(&self, location: Option<&resource::UniformLocation>, x: u32) {
         unsafe {
             self.gl.uniform_1_u32(location, x);
         }
+        self.check_error("uniform_1_u32");
     }
     pub fn uniform_2_u32(&self, location: Option<&resource::UniformLocation>, x: u32, y: u32) {
         unsafe {
             self.gl.uniform_2_u32(location, x, y);
         }
+        self.check_error("uniform_2_u32");
     }
     pub fn uniform_3_u32(&self, location: Option<&resource::UniformLocation>, x: u32, y: u32, z: u32) {
         unsafe {
             self.gl.uniform_3_u32(location, x, y, z);
         }
+        self.check_error("uniform_3_u32");
     }
     pub fn uniform_4_u32(
         &self,
@@ -680,22 +769,26 @@ impl OpenGL {
         unsafe {
             self.gl.uniform_4_u32(location, x, y, z, w);
         }
+        self.check_error("uniform_4_u32");
     }
 
     pub fn uniform_1_f32(&self, location: Option<&resource::UniformLocation>, x: f32) {
         unsafe {
             self.gl.uniform_1_f32(location, x);
         }
+        self.check_error("uniform_1_f32");
     }
     pub fn uniform_2_f32(&self, location: Option<&resource::UniformLocation>, x: f32, y: f32) {
         unsafe {
             self.gl.uniform_2_f32(location, x, y);
         }
+        self.check_error("uniform_2_f32");
     }
     pub fn uniform_3_f32(&self, location: Option<&resource::UniformLocation>, x: f32, y: f32, z: f32) {
         unsafe {
             self.gl.uniform_3_f32(location, x, y, z);
         }
+        self.check_error("uniform_3_f32");
     }
     pub fn uniform_4_f32(
         &self,
@@ -708,16 +801,20 @@ impl OpenGL {
         unsafe {
             self.gl.uniform_4_f32(location, x, y, z, w);
         }
+        self.check_error("uniform_4_f32");
     }
 
     pub fn create_vertex_array(&self) -> resource::VertexArray {
-        unsafe { self.gl.create_vertex_array().unwrap() }
+        let result = unsafe { self.gl.create_vertex_array().unwrap() };
+        self.check_error("create_vertex_array");
+        result
     }
 
     pub fn bind_vertex_array(&mut self, vertex_array: Option<resource::VertexArray>) {
         if self.vertex_array != vertex_array {
             self.vertex_array = vertex_array;
             unsafe { self.gl.bind_vertex_array(vertex_array) };
+            self.check_error("bind_vertex_array");
         }
     }
 
@@ -726,14 +823,17 @@ impl OpenGL {
             self.vertex_array = None;
         }
         unsafe { self.gl.delete_vertex_array(vertex_array) };
+        self.check_error("delete_vertex_array");
     }
 
     pub fn enable_vertex_attrib_array(&self, index: u32) {
         unsafe { self.gl.enable_vertex_attrib_array(index) };
+        self.check_error("enable_vertex_attrib_array");
     }
 
     pub fn vertex_attrib_divisor(&self, index: u32, divisor: u32) {
         unsafe { self.gl.vertex_attrib_divisor(index, divisor) };
+        self.check_error("vertex_attrib_divisor");
     }
 
     pub fn vertex_attrib_pointer_f32(
@@ -748,6 +848,7 @@ impl OpenGL {
         unsafe {
             self.gl.vertex_attrib_pointer_f32(index, size, data_type as u32, normalized, stride, offset)
         };
+        self.check_error("vertex_attrib_pointer_f32");
     }
 
     pub fn vertex_attrib_pointer_i32(
@@ -759,14 +860,18 @@ impl OpenGL {
         offset: i32,
     ) {
         unsafe { self.gl.vertex_attrib_pointer_i32(index, size, data_type as u32, stride, offset) };
+        self.check_error("vertex_attrib_pointer_i32");
     }
 
     pub fn create_buffer(&self) -> resource::Buffer {
-        unsafe { self.gl.create_buffer().unwrap() }
+        let result = unsafe { self.gl.create_buffer().unwrap() };
+        self.check_error("create_buffer");
+        result
     }
 
     pub fn bind_buffer(&self, target: BufferBindingTarget, buffer: Option<resource::Buffer>) {
         unsafe { self.gl.bind_buffer(target as u32, buffer) };
+        self.check_error("bind_buffer");
     }
 
     pub fn bind_buffer_base(
@@ -776,20 +881,24 @@ impl OpenGL {
         buffer: Option<resource::Buffer>,
     ) {
         unsafe { self.gl.bind_buffer_base(target as u32, index, buffer) };
+        self.check_error("bind_buffer_base");
     }
 
     pub fn delete_buffer(&self, buffer: resource::Buffer) {
         unsafe { self.gl.delete_buffer(buffer) };
+        self.check_error("delete_buffer");
     }
 
     pub fn buffer_data_empty(&self, target: BufferBindingTarget, size: i32, usage: BufferUsage) {
         unsafe { self.gl.buffer_data_size(target as u32, size, usage as u32) };
+        self.check_error("buffer_data_empty");
     }
 
     pub fn buffer_data_u8_slice(&self, target: BufferBindingTarget, data: &[u8], usage: BufferUsage) {
         unsafe {
             self.gl.buffer_data_u8_slice(target as u32, data, usage as u32);
         };
+        self.check_error("buffer_data_u8_slice");
     }
 
     pub fn buffer_data<T: Sized>(&self, target: BufferBindingTarget, data: &[T], usage: BufferUsage) {
@@ -799,37 +908,74 @@ impl OpenGL {
             let slice = core::slice::from_raw_parts(ptr, len);
             self.gl.buffer_data_u8_slice(target as u32, slice, usage as u32);
         };
+        self.check_error("buffer_data");
     }
 
-    pub fn buffer_sub_data<T: Sized>(&self, target: BufferBindingTarget, data: &[T]) {
+    pub fn buffer_sub_data<T: Sized>(&self, target: BufferBindingTarget, offset: i32, data: &[T]) {
         unsafe {
             let len = core::mem::size_of::<T>() * data.len();
             let ptr = data.as_ptr() as *const u8;
             let slice = core::slice::from_raw_parts(ptr, len);
-            self.gl.buffer_sub_data_u8_slice(target as u32, 0, slice);
+            self.gl.buffer_sub_data_u8_slice(target as u32, offset, slice);
         };
+        self.check_error("buffer_sub_data");
     }
 
     pub fn draw_arrays_instanced(&self, mode: DrawMode, first: i32, count: i32, instance_count: i32) {
         unsafe { self.gl.draw_arrays_instanced(mode as u32, first, count, instance_count) };
+        self.check_error("draw_arrays_instanced");
     }
 
     pub fn draw_arrays(&self, mode: DrawMode, first: i32, count: i32) {
         unsafe { self.gl.draw_arrays(mode as u32, first, count) };
+        self.check_error("draw_arrays");
+    }
+
+    pub fn begin_transform_feedback(&self, mode: DrawMode) {
+        unsafe { self.gl.begin_transform_feedback(mode as u32) };
+        self.check_error("begin_transform_feedback");
+    }
+
+    pub fn end_transform_feedback(&self) {
+        unsafe { self.gl.end_transform_feedback() };
+        self.check_error("end_transform_feedback");
+    }
+
+    pub fn draw_elements(&self, mode: DrawMode, count: i32, element_type: IndiceType, offset: i32) {
+        unsafe { self.gl.draw_elements(mode as u32, count, element_type as u32, offset) };
+        self.check_error("draw_elements");
+    }
+
+    pub fn draw_elements_instanced(
+        &self,
+        mode: DrawMode,
+        count: i32,
+        element_type: IndiceType,
+        offset: i32,
+        instance_count: i32,
+    ) {
+        unsafe {
+            self.gl.draw_elements_instanced(mode as u32, count, element_type as u32, offset, instance_count)
+        };
+        self.check_error("draw_elements_instanced");
     }
 
     pub fn create_texture(&self) -> resource::Texture {
-        unsafe { self.gl.create_texture().unwrap() }
+        let result = unsafe { self.gl.create_texture().unwrap() };
+        self.check_error("create_texture");
+        result
     }
 
     pub fn delete_texture(&self, texture: resource::Texture) {
         unsafe { self.gl.delete_texture(texture) };
+        self.check_error("delete_texture");
     }
 
     pub fn active_texture(&mut self, unit: u32) {
         if self.active_texture_unit != unit {
             self.active_texture_unit = unit;
             unsafe { self.gl.active_texture(glow::TEXTURE0 + unit as u32) };
+            self.check_error("active_texture");
         }
     }
 
@@ -838,6 +984,7 @@ impl OpenGL {
         if self.bound_textures[index] != texture {
             self.bound_textures[index] = texture;
             unsafe { self.gl.bind_texture(target as u32, texture) };
+            self.check_error("bind_texture");
         }
     }
 
@@ -851,34 +998,129 @@ impl OpenGL {
         internal_format: PixelInternalFormat,
         format: PixelFormat,
         ty: PixelType,
+        pixels: Option<&[T]>,
+    ) {
+        unsafe {
+            let slice = pixels.map(|pixels| {
+                let len = core::mem::size_of::<T>() * pixels.len();
+                let ptr = pixels.as_ptr() as *const u8;
+                core::slice::from_raw_parts(ptr, len)
+            });
+            self.gl.tex_image_2d(
+                target as u32,
+                level,
+                internal_format as u32 as i32,
+                width,
+                height,
+                border,
+                format as u32,
+                ty as u32,
+                slice,
+            )
+        };
+        self.check_error("tex_image_2d");
+    }
+
+    pub fn compressed_tex_image_2d(
+        &self,
+        target: TextureLoadTarget,
+        level: i32,
+        internal_format: u32,
+        width: i32,
+        height: i32,
+        data: &[u8],
+    ) {
+        unsafe {
+            self.gl.compressed_tex_image_2d(
+                target as u32,
+                level,
+                internal_format as i32,
+                width,
+                height,
+                0,
+                data.len() as i32,
+                data,
+            )
+        };
+        self.check_error("compressed_tex_image_2d");
+    }
+
+    pub fn tex_sub_image_2d<T: Sized>(
+        &self,
+        target: TextureLoadTarget,
+        level: i32,
+        x_offset: i32,
+        y_offset: i32,
+        width: i32,
+        height: i32,
+        format: PixelFormat,
+        ty: PixelType,
         pixels: &[T],
     ) {
         unsafe {
             let len = core::mem::size_of::<T>() * pixels.len();
             let ptr = pixels.as_ptr() as *const u8;
             let slice = core::slice::from_raw_parts(ptr, len);
-            self.gl.tex_image_2d(
+            self.gl.tex_sub_image_2d(
+                target as u32,
+                level,
+                x_offset,
+                y_offset,
+                width,
+                height,
+                format as u32,
+                ty as u32,
+                PixelUnpackData::Slice(slice),
+            );
+        };
+        self.check_error("tex_sub_image_2d");
+    }
+
+    pub fn tex_image_3d<T: Sized>(
+        &self,
+        target: TextureLoadTarget,
+        level: i32,
+        width: i32,
+        height: i32,
+        depth: i32,
+        border: i32,
+        internal_format: PixelInternalFormat,
+        format: PixelFormat,
+        ty: PixelType,
+        pixels: Option<&[T]>,
+    ) {
+        unsafe {
+            let slice = pixels.map(|pixels| {
+                let len = core::mem::size_of::<T>() * pixels.len();
+                let ptr = pixels.as_ptr() as *const u8;
+                core::slice::from_raw_parts(ptr, len)
+            });
+            self.gl.tex_image_3d(
                 target as u32,
                 level,
                 internal_format as u32 as i32,
                 width,
                 height,
+                depth,
                 border,
                 format as u32,
                 ty as u32,
-                Some(slice),
+                slice,
             )
         };
+        self.check_error("tex_image_3d");
     }
 
-    pub fn tex_sub_image_2d<T: Sized>(
+    pub fn tex_sub_image_3d<T: Sized>(
         &self,
         target: TextureLoadTarget,
         level: i32,
         x_offset: i32,
         y_offset: i32,
+        z_offset: i32,
         width: i32,
         height: i32,
+        depth: i32,
         format: PixelFormat,
         ty: PixelType,
         pixels: &[T],
@@ -887,36 +1129,42 @@ impl OpenGL {
             let len = core::mem::size_of::<T>() * pixels.len();
             let ptr = pixels.as_ptr() as *const u8;
             let slice = core::slice::from_raw_parts(ptr, len);
-            self.gl.tex_sub_image_2d(
+            self.gl.tex_sub_image_3d(
                 target as u32,
                 level,
                 x_offset,
                 y_offset,
+                z_offset,
                 width,
                 height,
+                depth,
                 format as u32,
                 ty as u32,
                 PixelUnpackData::Slice(slice),
             );
         };
+        self.check_error("tex_sub_image_3d");
     }
 
     pub fn tex_parameter_wrap_s(&self, target: TextureParameterTarget, value: TextureWrapValue) {
         unsafe {
             self.gl.tex_parameter_i32(target as u32, TextureParameterName::TextureWrapS as u32, value as i32)
         };
+        self.check_error("tex_parameter_wrap_s");
     }
 
     pub fn tex_parameter_wrap_t(&self, target: TextureParameterTarget, value: TextureWrapValue) {
         unsafe {
             self.gl.tex_parameter_i32(target as u32, TextureParameterName::TextureWrapT as u32, value as i32)
         };
+        self.check_error("tex_parameter_wrap_t");
     }
 
     pub fn tex_parameter_wrap_r(&self, target: TextureParameterTarget, value: TextureWrapValue) {
         unsafe {
             self.gl.tex_parameter_i32(target as u32, TextureParameterName::TextureWrapR as u32, value as i32)
         };
+        self.check_error("tex_parameter_wrap_r");
     }
 
     pub fn tex_parameter_min_filter(&self, target: TextureParameterTarget, value: TextureMinFilterValue) {
@@ -927,6 +1175,7 @@ impl OpenGL {
                 value as i32,
             )
         };
+        self.check_error("tex_parameter_min_filter");
     }
 
     pub fn tex_parameter_mag_filter(&self, target: TextureParameterTarget, value: TextureMagFilterValue) {
@@ -937,14 +1186,68 @@ impl OpenGL {
                 value as i32,
             )
         };
+        self.check_error("tex_parameter_mag_filter");
+    }
+
+    pub fn create_framebuffer(&self) -> resource::Framebuffer {
+        let result = unsafe { self.gl.create_framebuffer().unwrap() };
+        self.check_error("create_framebuffer");
+        result
+    }
+
+    pub fn bind_framebuffer(
+        &mut self,
+        target: FramebufferTarget,
+        framebuffer: Option<resource::Framebuffer>,
+    ) {
+        if self.framebuffer != framebuffer {
+            self.framebuffer = framebuffer;
+            unsafe { self.gl.bind_framebuffer(target as u32, framebuffer) };
+            self.check_error("bind_framebuffer");
+        }
+    }
+
+    pub fn delete_framebuffer(&mut self, framebuffer: resource::Framebuffer) {
+        if self.framebuffer == Some(framebuffer) {
+            self.framebuffer = None;
+        }
+        unsafe { self.gl.delete_framebuffer(framebuffer) };
+        self.check_error("delete_framebuffer");
+    }
+
+    pub fn framebuffer_texture_2d(
+        &self,
+        target: FramebufferTarget,
+        attachment: FramebufferAttachment,
+        texture: Option<resource::Texture>,
+        level: i32,
+    ) {
+        unsafe {
+            self.gl.framebuffer_texture_2d(target as u32, attachment as u32, glow::TEXTURE_2D, texture, level)
+        };
+        self.check_error("framebuffer_texture_2d");
+    }
+
+    /// Returns true if the bound framebuffer is complete.
+    pub fn check_framebuffer_status(&self, target: FramebufferTarget) -> bool {
+        let result = unsafe { self.gl.check_framebuffer_status(target as u32) };
+        self.check_error("check_framebuffer_status");
+        result == glow::FRAMEBUFFER_COMPLETE
     }
 
     pub fn pixel_store(&self, param: PixelStoreAlignment, value: i32) {
         unsafe { self.gl.pixel_store_i32(param as u32, value) };
+        self.check_error("pixel_store");
     }
 
     pub fn enable(&self, capability: Capability) {
         unsafe { self.gl.enable(capability as u32) };
+        self.check_error("enable");
+    }
+
+    pub fn disable(&self, capability: Capability) {
+        unsafe { self.gl.disable(capability as u32) };
+        self.check_error("disable");
     }
 
     pub fn clear_color(&mut self, color: RGBA8) {
@@ -952,31 +1255,69 @@ impl OpenGL {
             self.clear_color = color;
             let (red, green, blue, alpha) = color.into();
             unsafe { self.gl.clear_color(red, green, blue, alpha) };
+            self.check_error("clear_color");
         }
     }
 
     pub fn depth_func(&self, test: DepthTest) {
         unsafe { self.gl.depth_func(test as u32) };
+        self.check_error("depth_func");
     }
 
     pub fn blend_func(&self, src: BlendFactor, dst: BlendFactor) {
         unsafe { self.gl.blend_func(src as u32, dst as u32) };
+        self.check_error("blend_func");
     }
 
     pub fn cull_face(&self, face: CullFace) {
         unsafe { self.gl.cull_face(face as u32) };
+        self.check_error("cull_face");
+    }
+
+    pub fn depth_mask(&self, write: bool) {
+        unsafe { self.gl.depth_mask(write) };
+        self.check_error("depth_mask");
+    }
+
+    pub fn scissor(&self, x: i32, y: i32, width: i32, height: i32) {
+        unsafe { self.gl.scissor(x, y, width, height) };
+        self.check_error("scissor");
     }
 
     pub fn viewport(&self, x: i32, y: i32, width: i32, height: i32) {
         unsafe { self.gl.viewport(x, y, width, height) };
+        self.check_error("viewport");
     }
 
     pub fn clear(&self, mask: u32) {
         unsafe { self.gl.clear(mask) };
+        self.check_error("clear");
     }
 
     pub fn get_max_texture_size(&self) -> i32 {
-        unsafe { self.gl.get_parameter_i32(glow::MAX_TEXTURE_SIZE) }
+        let result = unsafe { self.gl.get_parameter_i32(glow::MAX_TEXTURE_SIZE) };
+        self.check_error("get_max_texture_size");
+        result
+    }
+
+    pub fn get_max_texture_layers(&self) -> i32 {
+        let result = unsafe { self.gl.get_parameter_i32(glow::MAX_ARRAY_TEXTURE_LAYERS) };
+        self.check_error("get_max_texture_layers");
+        result
+    }
+
+    pub fn supports_debug(&self) -> bool {
+        self.gl.supports_debug()
+    }
+
+    pub fn supports_extension(&self, name: &str) -> bool {
+        self.gl.supported_extensions().contains(name)
+    }
+
+    /// Returns the (major, minor) version of the context, and if it's an embedded context.
+    pub fn version(&self) -> (u32, u32, bool) {
+        let version = self.gl.version();
+        (version.major, version.minor, version.is_embedded)
     }
 
     pub fn debug_message_callback<F>(&self, callback: F)
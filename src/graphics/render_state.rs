@@ -0,0 +1,87 @@
+use crate::graphics::{BlendFactor, CullFace};
+use crate::math::AABB2D;
+
+/// How the output of a draw is combined with what's already on the screen.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BlendMode {
+    /// Standard transparency. The source is layered over the destination by its alpha.
+    Alpha,
+    /// The source is scaled by its alpha and added to the destination. Useful for particles,
+    /// glows, and light.
+    Additive,
+    /// The destination is multiplied by the source color. Useful for shadows and tinting.
+    Multiply,
+    /// Transparency for colors that have already been multiplied by their alpha.
+    Premultiplied,
+}
+
+impl BlendMode {
+    pub(crate) fn factors(&self) -> (BlendFactor, BlendFactor) {
+        match self {
+            BlendMode::Alpha => (BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha),
+            BlendMode::Additive => (BlendFactor::SrcAlpha, BlendFactor::One),
+            BlendMode::Multiply => (BlendFactor::DstColor, BlendFactor::Zero),
+            BlendMode::Premultiplied => (BlendFactor::One, BlendFactor::OneMinusSrcAlpha),
+        }
+    }
+}
+
+/// Which faces of a primitive are discarded before drawing.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CullMode {
+    /// Discards back facing (clockwise) primitives.
+    Back,
+    /// Discards front facing (counter-clockwise) primitives.
+    Front,
+}
+
+impl CullMode {
+    pub(crate) fn face(&self) -> CullFace {
+        match self {
+            CullMode::Back => CullFace::Back,
+            CullMode::Front => CullFace::Front,
+        }
+    }
+}
+
+/// Fixed function state used by draws. Changing the state only issues the calls for the values that
+/// differ from the current state.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RenderState {
+    /// How the output is blended with the screen. None disables blending, drawing opaquely. The
+    /// default is alpha blending.
+    pub blend: Option<BlendMode>,
+    /// If fragments are tested against the depth buffer, discarding those behind what's already
+    /// drawn. The default is true.
+    pub depth_test: bool,
+    /// If fragments write to the depth buffer. The default is true.
+    pub depth_write: bool,
+    /// Which faces are culled. None disables culling. The default is back face culling.
+    pub cull: Option<CullMode>,
+    /// Restricts drawing to a rectangle. Units are physical pixels, with (0, 0) being the bottom left
+    /// of the window, matching the cursor's physical position. This also restricts what's cleared
+    /// by [`crate::graphics::clear`]. None disables the restriction, which is the default.
+    pub scissor: Option<AABB2D>,
+}
+
+impl Default for RenderState {
+    fn default() -> RenderState {
+        RenderState {
+            blend: Some(BlendMode::Alpha),
+            depth_test: true,
+            depth_write: true,
+            cull: Some(CullMode::Back),
+            scissor: None,
+        }
+    }
+}
+
+impl RenderState {
+    /// The default state with the given blend mode.
+    pub fn blended(blend: BlendMode) -> RenderState {
+        RenderState {
+            blend: Some(blend),
+            ..RenderState::default()
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::color::{ColorDescriptor, Depth32F};
+use crate::ctx;
+use crate::graphics::{resource, FramebufferAttachment, FramebufferTarget, Texture};
+use cgmath::*;
+
+/// An offscreen surface that can be drawn to instead of the window, with a color texture and a
+/// depth texture. Draw to it by passing it to [`crate::graphics::set_render_target`], then sample
+/// its [`RenderTarget::texture`] like any other texture.
+///
+/// Rows of the texture are stored bottom to top, so sampling the texture at (0, 0) reads the bottom
+/// left of what was drawn.
+pub struct RenderTarget {
+    framebuffer: resource::Framebuffer,
+    color: Texture,
+    depth: Texture,
+}
+
+impl RenderTarget {
+    /// Creates a render target with a color texture in the format of the given color type. Panics
+    /// if the format can't be drawn to on this device.
+    pub fn new<T: ColorDescriptor>(width: u32, height: u32) -> RenderTarget {
+        let color = Texture::empty::<T>(width, height);
+        let depth = Texture::empty::<Depth32F>(width, height);
+        let gl = ctx().graphics().gl();
+        let framebuffer = gl.create_framebuffer();
+        gl.bind_framebuffer(FramebufferTarget::Framebuffer, Some(framebuffer));
+        gl.framebuffer_texture_2d(
+            FramebufferTarget::Framebuffer,
+            FramebufferAttachment::Color0,
+            Some(color.id()),
+            0,
+        );
+        gl.framebuffer_texture_2d(
+            FramebufferTarget::Framebuffer,
+            FramebufferAttachment::Depth,
+            Some(depth.id()),
+            0,
+        );
+        let complete = gl.check_framebuffer_status(FramebufferTarget::Framebuffer);
+        ctx().graphics().restore_render_target();
+        if !complete {
+            ctx().graphics().gl().delete_framebuffer(framebuffer);
+            panic!("The render target's color format can't be drawn to on this device.");
+        }
+        RenderTarget {
+            framebuffer,
+            color,
+            depth,
+        }
+    }
+
+    /// The width of the render target.
+    pub fn width(&self) -> u32 {
+        self.color.width()
+    }
+
+    /// The height of the render target.
+    pub fn height(&self) -> u32 {
+        self.color.height()
+    }
+
+    /// The texture holding what was drawn to the render target.
+    pub fn texture(&self) -> &Texture {
+        &self.color
+    }
+
+    /// The texture holding the depth of what was drawn to the render target.
+    pub fn depth_texture(&self) -> &Texture {
+        &self.depth
+    }
+
+    pub(crate) fn framebuffer(&self) -> resource::Framebuffer {
+        self.framebuffer
+    }
+
+    pub(crate) fn size(&self) -> Vector2<u32> {
+        Vector2::new(self.width(), self.height())
+    }
+}
+
+impl Drop for RenderTarget {
+    fn drop(&mut self) {
+        let graphics = ctx().graphics();
+        if graphics.render_target().map(|(framebuffer, _)| framebuffer) == Some(self.framebuffer) {
+            graphics.set_render_target(None);
+        }
+        graphics.gl().delete_framebuffer(self.framebuffer);
+    }
+}
@@ -1,6 +1,9 @@
 use crate::ctx;
-use crate::graphics::{resource, AsStd140, Buffer, DrawMode, Texture, Uniform, VertexDescriptor};
-use alloc::format;
+use crate::graphics::{
+    resource, AsStd140, Buffer, BufferBindingTarget, BufferBlockBindingTarget, Capability, DrawMode,
+    IndexBuffer, IndexDescriptor, ShaderError, Texture, Uniform, VertexDescriptor,
+};
+use alloc::string::ToString;
 use core::marker::PhantomData;
 
 /// A trait to describe a shader's inputs and outputs so they can be represented without using the
@@ -12,9 +15,13 @@ pub trait ShaderDescriptor<const TEXTURES: usize> {
     const VERTEX_UNIFORM_NAME: &'static str;
     type VertexUniformType: AsStd140;
     type VertexDescriptor: VertexDescriptor + Copy;
+    /// Outputs of the vertex shader captured by [`Shader::feedback`], in the order they're laid out
+    /// in the vertex descriptor. Most shaders don't capture anything.
+    const FEEDBACK_VARYINGS: &'static [&'static str] = &[];
 }
 
-/// Represents the runtime metadata required to configure and draw with a shader.
+/// Represents the runtime metadata required to configure and draw with a shader. Draws use the
+/// render state set by [`crate::graphics::set_render_state`].
 pub struct Shader<T: ShaderDescriptor<TEXTURES>, const TEXTURES: usize> {
     program: resource::Program,
     vertex_uniform_location: u32,
@@ -24,27 +31,76 @@ pub struct Shader<T: ShaderDescriptor<TEXTURES>, const TEXTURES: usize> {
 
 impl<T: ShaderDescriptor<TEXTURES>, const TEXTURES: usize> Shader<T, TEXTURES> {
     /// Creates a new shader. Shaders hold no mutable state and should be reused as often as
-    /// possible.
+    /// possible. Panics if the shader fails to compile; see [`Shader::try_new`] for a fallible
+    /// version.
     pub fn new() -> Shader<T, TEXTURES> {
-        let gl = ctx().graphics().gl();
-
-        let program = gl.shader_program(T::VERTEX_SHADER, T::FRAGMENT_SHADER);
-        let vertex_uniform_location = gl.get_uniform_block_index(program, T::VERTEX_UNIFORM_NAME).expect(
-            &format!("Failed to find uniform block named '{}' in vertex shader.", T::VERTEX_UNIFORM_NAME),
-        );
-        gl.uniform_block_binding(program, vertex_uniform_location, 0);
+        match Self::try_new() {
+            Ok(shader) => shader,
+            Err(error) => panic!("{}", error),
+        }
+    }
 
-        let texture_locations = T::TEXTURE_NAMES.map(|name| {
-            gl.get_uniform_location(program, name)
-                .expect(&format!("Failed to find texture named '{}' in fragment shader.", name))
-        });
+    /// Creates a new shader, returning an error describing the failure if the shader fails to
+    /// compile, link, or is missing a uniform block or texture named by the descriptor.
+    pub fn try_new() -> Result<Shader<T, TEXTURES>, ShaderError> {
+        Self::from_source(T::VERTEX_SHADER, T::FRAGMENT_SHADER)
+    }
 
-        Shader {
+    /// Creates a new shader from source provided at runtime instead of the descriptor's source.
+    /// The descriptor's uniform and texture names are still used.
+    pub fn from_source(
+        vertex_shader: &str,
+        fragment_shader: &str,
+    ) -> Result<Shader<T, TEXTURES>, ShaderError> {
+        let (program, vertex_uniform_location, texture_locations) =
+            Self::compile(vertex_shader, fragment_shader)?;
+        Ok(Shader {
             program,
             vertex_uniform_location,
             texture_locations,
             phantom: PhantomData,
+        })
+    }
+
+    /// Recompiles the shader from the given source. The existing program is only replaced if the
+    /// new source compiles successfully, otherwise the existing program is kept and the error is
+    /// returned. This is useful with a [`crate::graphics::ShaderWatcher`], for editing shaders
+    /// while the game runs.
+    pub fn reload(&mut self, vertex_shader: &str, fragment_shader: &str) -> Result<(), ShaderError> {
+        let (program, vertex_uniform_location, texture_locations) =
+            Self::compile(vertex_shader, fragment_shader)?;
+        let gl = ctx().graphics().gl();
+        gl.delete_program(self.program);
+        self.program = program;
+        self.vertex_uniform_location = vertex_uniform_location;
+        self.texture_locations = texture_locations;
+        Ok(())
+    }
+
+    fn compile(
+        vertex_shader: &str,
+        fragment_shader: &str,
+    ) -> Result<(resource::Program, u32, [resource::UniformLocation; TEXTURES]), ShaderError> {
+        let gl = ctx().graphics().gl();
+
+        let program = gl.shader_program(vertex_shader, fragment_shader, T::FEEDBACK_VARYINGS)?;
+        let vertex_uniform_location = match gl.get_uniform_block_index(program, T::VERTEX_UNIFORM_NAME) {
+            Some(location) => location,
+            None => {
+                gl.delete_program(program);
+                return Err(ShaderError::MissingUniformBlock(T::VERTEX_UNIFORM_NAME.to_string()));
+            }
+        };
+        gl.uniform_block_binding(program, vertex_uniform_location, 0);
+
+        let texture_locations = T::TEXTURE_NAMES.map(|name| gl.get_uniform_location(program, name));
+        if let Some(index) = texture_locations.iter().position(|location| location.is_none()) {
+            gl.delete_program(program);
+            return Err(ShaderError::MissingTexture(T::TEXTURE_NAMES[index].to_string()));
         }
+        let texture_locations = texture_locations.map(|location| location.unwrap());
+
+        Ok((program, vertex_uniform_location, texture_locations))
     }
 
     fn bind(
@@ -63,6 +119,39 @@ impl<T: ShaderDescriptor<TEXTURES>, const TEXTURES: usize> Shader<T, TEXTURES> {
         buffer.bind();
     }
 
+    /// Runs the vertex shader once for every element in the input buffer without drawing anything,
+    /// writing the outputs named by [`ShaderDescriptor::FEEDBACK_VARYINGS`] into the output buffer
+    /// in the same order. The input is expected to step per instance. The output buffer must hold
+    /// at least as many elements as the input, and must not be the input.
+    /// # Arguments
+    ///
+    /// * `uniform` - The uniform to use for the shader invocation.
+    /// * `textures` - The set of textures to use in the vertex shader.
+    /// * `input` - The buffer of elements to read.
+    /// * `output` - The buffer the captured elements are written to.
+    pub fn feedback(
+        &self,
+        uniform: &Uniform<T::VertexUniformType>,
+        textures: [&Texture; TEXTURES],
+        input: &Buffer<T::VertexDescriptor>,
+        output: &mut Buffer<T::VertexDescriptor>,
+    ) {
+        assert!(output.len() >= input.len(), "Feedback output is smaller than the input.");
+        if input.len() > 0 {
+            self.bind(uniform, textures, input);
+            let gl = ctx().graphics().gl();
+            // WebGL rejects feedback into a buffer that's still bound as an array buffer.
+            gl.bind_buffer(BufferBindingTarget::ArrayBuffer, None);
+            gl.bind_buffer_base(BufferBlockBindingTarget::TransformFeedbackBuffer, 0, Some(output.vbo()));
+            gl.enable(Capability::RasterizerDiscard);
+            gl.begin_transform_feedback(DrawMode::Points);
+            gl.draw_arrays_instanced(DrawMode::Points, 0, 1, input.len() as i32);
+            gl.end_transform_feedback();
+            gl.disable(Capability::RasterizerDiscard);
+            gl.bind_buffer_base(BufferBlockBindingTarget::TransformFeedbackBuffer, 0, None);
+        }
+    }
+
     /// Performs an instanced draw to the screen.
     /// # Arguments
     ///
@@ -106,6 +195,63 @@ impl<T: ShaderDescriptor<TEXTURES>, const TEXTURES: usize> Shader<T, TEXTURES> {
             gl.draw_arrays(mode, 0, buffer.len() as i32);
         }
     }
+
+    /// Performs an indexed draw to the screen. Each index references an element in the buffer,
+    /// which is expected to step per vertex.
+    /// # Arguments
+    ///
+    /// * `mode` - Specifies what kind of primitives to render.
+    /// * `uniform` - The uniform to use for the shader invocation.
+    /// * `textures` - The set of textures to use in the fragment shader.
+    /// * `buffer` - The buffer of vertices to draw.
+    /// * `indices` - The indices into the buffer of vertices, describing the primitives to draw.
+    pub fn draw_elements<I: IndexDescriptor>(
+        &self,
+        mode: DrawMode,
+        uniform: &Uniform<T::VertexUniformType>,
+        textures: [&Texture; TEXTURES],
+        buffer: &Buffer<T::VertexDescriptor>,
+        indices: &IndexBuffer<I>,
+    ) {
+        if buffer.len() > 0 && !indices.is_empty() {
+            self.bind(uniform, textures, buffer);
+            indices.bind();
+            let gl = ctx().graphics().gl();
+            gl.draw_elements(mode, indices.len() as i32, I::index_type().indice_type(), 0);
+        }
+    }
+
+    /// Performs an indexed, instanced draw to the screen. The indices are drawn once for every
+    /// element in the buffer, which is expected to step per instance. The indices are available to
+    /// the vertex shader as gl_VertexID.
+    /// # Arguments
+    ///
+    /// * `mode` - Specifies what kind of primitives to render.
+    /// * `uniform` - The uniform to use for the shader invocation.
+    /// * `textures` - The set of textures to use in the fragment shader.
+    /// * `buffer` - The buffer of instances to draw.
+    /// * `indices` - The indices describing the primitives to draw for each instance.
+    pub fn draw_elements_instanced<I: IndexDescriptor>(
+        &self,
+        mode: DrawMode,
+        uniform: &Uniform<T::VertexUniformType>,
+        textures: [&Texture; TEXTURES],
+        buffer: &Buffer<T::VertexDescriptor>,
+        indices: &IndexBuffer<I>,
+    ) {
+        if buffer.len() > 0 && !indices.is_empty() {
+            self.bind(uniform, textures, buffer);
+            indices.bind();
+            let gl = ctx().graphics().gl();
+            gl.draw_elements_instanced(
+                mode,
+                indices.len() as i32,
+                I::index_type().indice_type(),
+                0,
+                buffer.len() as i32,
+            );
+        }
+    }
 }
 
 impl<T: ShaderDescriptor<TEXTURES>, const TEXTURES: usize> Drop for Shader<T, TEXTURES> {
@@ -0,0 +1,84 @@
+use crate::asset::{request_watch, Asset};
+use crate::graphics::ShaderError;
+use alloc::string::{String, ToString};
+use log::{error, info, warn};
+
+/// Watches a pair of shader source files through the asset module, handing the sources back for
+/// recompilation whenever either file changes. This is intended for development, letting shaders be
+/// iterated on without restarting.
+///
+/// # Example
+/// ```ignore
+/// let mut shader = SpriteShader::new();
+/// let mut watcher = ShaderWatcher::new("./shaders/vertex.glsl", "./shaders/fragment.glsl");
+///
+/// move |event| match event {
+///     Event::AssetRead(asset) => {
+///         watcher.update(&asset, |vertex, fragment| shader.reload(vertex, fragment));
+///     }
+///     _ => {}
+/// }
+/// ```
+pub struct ShaderWatcher {
+    vertex_path: String,
+    fragment_path: String,
+    vertex: Option<String>,
+    fragment: Option<String>,
+}
+
+impl ShaderWatcher {
+    /// Creates a new watcher, requesting the given paths be read and watched. See
+    /// [`crate::asset::request_watch`] for how paths are resolved and when changes are detected.
+    pub fn new(vertex_path: &str, fragment_path: &str) -> ShaderWatcher {
+        request_watch(vertex_path);
+        request_watch(fragment_path);
+        ShaderWatcher {
+            vertex_path: vertex_path.to_string(),
+            fragment_path: fragment_path.to_string(),
+            vertex: None,
+            fragment: None,
+        }
+    }
+
+    /// Feeds an asset read into the watcher. If the asset is one of the watched sources and both
+    /// sources are available, `reload` is invoked with the vertex and fragment source. Errors from
+    /// `reload` are logged, and the shader is expected to keep its existing program in that case.
+    /// Returns true if the asset belonged to this watcher.
+    pub fn update<F>(&mut self, asset: &Asset, reload: F) -> bool
+    where
+        F: FnOnce(&str, &str) -> Result<(), ShaderError>,
+    {
+        let target = if asset.relative_path == self.vertex_path {
+            &mut self.vertex
+        } else if asset.relative_path == self.fragment_path {
+            &mut self.fragment
+        } else {
+            return false;
+        };
+
+        match &asset.result {
+            Ok(contents) => match core::str::from_utf8(contents) {
+                Ok(source) => *target = Some(source.to_string()),
+                Err(_) => {
+                    warn!("Shader source {} is not valid UTF-8.", asset.relative_path);
+                    return true;
+                }
+            },
+            Err(error) => {
+                warn!("Failed to read shader source {}: {:?}", asset.relative_path, error);
+                return true;
+            }
+        }
+
+        if let (Some(vertex), Some(fragment)) = (&self.vertex, &self.fragment) {
+            match reload(vertex, fragment) {
+                Ok(()) => info!("Reloaded shader ({}, {}).", self.vertex_path, self.fragment_path),
+                Err(error) => error!(
+                    "Failed to reload shader ({}, {}), keeping the previous program.\n{}",
+                    self.vertex_path, self.fragment_path, error
+                ),
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,5 @@
+mod data;
+mod shader;
+
+pub use self::data::CompactSprite;
+pub use self::shader::{CompactSpriteShader, CompactSpriteShaderPass};
@@ -0,0 +1,76 @@
+use crate::graphics::{
+    default_texture, set_render_state,
+    shaders::{compact_sprite::CompactSprite, sprite::SpriteUniform},
+    Buffer, DrawMode, RenderState, Shader, ShaderDescriptor, ShaderError, Texture, Uniform,
+};
+use cgmath::Matrix4;
+
+impl ShaderDescriptor<1> for CompactSpriteShader {
+    const VERTEX_SHADER: &'static str = include_str!("vertex.glsl");
+    const FRAGMENT_SHADER: &'static str = include_str!("fragment.glsl");
+    const TEXTURE_NAMES: [&'static str; 1] = ["tex"];
+    const VERTEX_UNIFORM_NAME: &'static str = "vertex";
+    type VertexUniformType = SpriteUniform;
+    type VertexDescriptor = CompactSprite;
+}
+
+/// Draws [`CompactSprite`]s, which use less memory than sprites at the cost of whole pixel sizes
+/// and rotating around their center.
+pub struct CompactSpriteShader {
+    shader: Shader<CompactSpriteShader, 1>,
+}
+
+impl CompactSpriteShader {
+    pub fn new() -> CompactSpriteShader {
+        CompactSpriteShader {
+            shader: Shader::new(),
+        }
+    }
+
+    /// Recompiles the shader, see [`crate::graphics::Shader::reload`].
+    pub fn reload(&mut self, vertex_shader: &str, fragment_shader: &str) -> Result<(), ShaderError> {
+        self.shader.reload(vertex_shader, fragment_shader)
+    }
+
+    /// Draws to the screen.
+    pub fn draw(&self, uniform: &Uniform<SpriteUniform>, atlas: &Texture, buffer: &Buffer<CompactSprite>) {
+        self.shader.draw_instanced(DrawMode::TriangleStrip, uniform, [atlas], buffer, 4);
+    }
+}
+
+pub struct CompactSpriteShaderPass {
+    pub uniform: Uniform<SpriteUniform>,
+    pub atlas: Texture,
+    pub buffer: Buffer<CompactSprite>,
+    render_state: RenderState,
+}
+
+impl CompactSpriteShaderPass {
+    pub fn new(ortho: Matrix4<f32>) -> CompactSpriteShaderPass {
+        CompactSpriteShaderPass {
+            uniform: Uniform::new(SpriteUniform::new(ortho)),
+            atlas: default_texture(),
+            buffer: Buffer::new(),
+            render_state: RenderState::default(),
+        }
+    }
+
+    /// Sets the orthographic projection used to draw this pass. If none is passed, this function
+    /// does nothing.
+    pub fn set_ortho(&mut self, ortho: Option<Matrix4<f32>>) {
+        if let Some(ortho) = ortho {
+            self.uniform.set(SpriteUniform::new(ortho));
+        }
+    }
+
+    /// Sets the render state applied when drawing this pass.
+    pub fn set_render_state(&mut self, state: RenderState) {
+        self.render_state = state;
+    }
+
+    /// Draws the pass to the screen.
+    pub fn draw(&mut self, shader: &CompactSpriteShader) {
+        set_render_state(&self.render_state);
+        shader.draw(&self.uniform, &self.atlas, &self.buffer);
+    }
+}
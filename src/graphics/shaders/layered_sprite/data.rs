@@ -0,0 +1,97 @@
+use crate::color::RGBA8;
+use crate::graphics::{
+    shaders::sprite::Sprite, TextureSection, VertexAttribute, VertexDescriptor, VertexInputType,
+    VertexOutputType,
+};
+use crate::math::AABB2D;
+use cgmath::*;
+
+/// Configuration settings for a sprite drawn from a layer of a texture array.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LayeredSprite {
+    /// Position of the sprite. The X and Y coordinates represent the bottom left corner of the
+    /// sprite. The Z coordinate represents sprite depth. Units are measured in pixels.
+    pub pos: Vector3<f32>,
+    /// Units are measured in pixels. Sizes may be fractional or negative. A negative size extends
+    /// the sprite left or down from its position, mirroring it.
+    pub size: Vector2<f32>,
+    /// Texture to apply to the sprite. The default is a plain white texture.
+    pub texture: TextureSection,
+    /// Color multiplier to apply to the sprite. The default is white.
+    pub color: RGBA8,
+    /// Rotation of the sprite around its pivot. Units are 1/65536th of a turn.
+    pub rotation: u16,
+    /// Mirrors the texture horizontally when set. The default is false.
+    pub flip_x: bool,
+    /// Mirrors the texture vertically when set. The default is false.
+    pub flip_y: bool,
+    /// The point the sprite rotates around, relative to the sprite's size. See [`Sprite::pivot`].
+    /// The default is the center, (0.5, 0.5).
+    pub pivot: Vector2<f32>,
+    /// Layer of the texture array to apply to the sprite. The default is the first layer.
+    pub layer: u16,
+}
+
+impl VertexDescriptor for LayeredSprite {
+    const ATTRIBUTES: &'static [VertexAttribute] = &[
+        // Pos, Size, Texture, Color::RGBA8, Rotation, Flip, Pivot, Layer
+        VertexAttribute::new(3, VertexInputType::F32, VertexOutputType::F32),
+        VertexAttribute::new(2, VertexInputType::F32, VertexOutputType::F32),
+        VertexAttribute::new(4, VertexInputType::U16, VertexOutputType::NormalizedF32),
+        VertexAttribute::new(4, VertexInputType::U8, VertexOutputType::NormalizedF32),
+        VertexAttribute::new(1, VertexInputType::U16, VertexOutputType::NormalizedF32),
+        VertexAttribute::new(2, VertexInputType::U8, VertexOutputType::F32),
+        VertexAttribute::new(2, VertexInputType::F32, VertexOutputType::F32),
+        VertexAttribute::new(1, VertexInputType::U16, VertexOutputType::F32),
+    ];
+}
+
+impl Default for LayeredSprite {
+    fn default() -> LayeredSprite {
+        LayeredSprite::from_sprite(Sprite::default(), 0)
+    }
+}
+
+impl LayeredSprite {
+    /// Creates a new sprite that rotates around its center. This converts the rotation from a float
+    /// automatically. Size is measured in pixels. Rotation is measured in turns from [0, 1). Values
+    /// outside of the range are wrapped into the range. For example, 1.75 is wrapped into 0.75,
+    /// -0.4 is wrapped into 0.6.
+    pub fn new(
+        pos: Vector3<f32>,
+        size: Vector2<f32>,
+        texture: TextureSection,
+        color: RGBA8,
+        rotation: f32,
+        layer: u16,
+    ) -> LayeredSprite {
+        LayeredSprite::from_sprite(Sprite::new(pos, size, texture, color, rotation), layer)
+    }
+
+    /// Creates a layered sprite from a sprite and the layer to draw it from.
+    pub fn from_sprite(sprite: Sprite, layer: u16) -> LayeredSprite {
+        LayeredSprite {
+            pos: sprite.pos,
+            size: sprite.size,
+            texture: sprite.texture,
+            color: sprite.color,
+            rotation: sprite.rotation,
+            flip_x: sprite.flip_x,
+            flip_y: sprite.flip_y,
+            pivot: sprite.pivot,
+            layer,
+        }
+    }
+}
+
+impl From<LayeredSprite> for AABB2D {
+    fn from(sprite: LayeredSprite) -> Self {
+        let a = sprite.pos.truncate();
+        let b = a + sprite.size;
+        AABB2D::from_min_max(
+            Vector2::new(a.x.min(b.x), a.y.min(b.y)),
+            Vector2::new(a.x.max(b.x), a.y.max(b.y)),
+        )
+    }
+}
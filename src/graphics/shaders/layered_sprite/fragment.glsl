@@ -0,0 +1,15 @@
+precision mediump float;
+
+in vec2 v_uv;
+in vec4 v_color;
+flat in float v_layer;
+out vec4 a_color;
+
+uniform mediump sampler2DArray tex;
+
+void main() {
+    a_color = texture(tex, vec3(v_uv, v_layer)) * v_color;
+    if (a_color.a <= 0.0) {
+        discard;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,5 @@
+mod data;
+mod shader;
+
+pub use self::data::LayeredSprite;
+pub use self::shader::{LayeredSpriteShader, LayeredSpriteShaderPass};
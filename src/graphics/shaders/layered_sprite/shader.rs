@@ -0,0 +1,79 @@
+use crate::graphics::{
+    set_render_state,
+    shaders::{layered_sprite::LayeredSprite, sprite::SpriteUniform},
+    Buffer, DrawMode, RenderState, Shader, ShaderDescriptor, ShaderError, Texture, Uniform,
+};
+use cgmath::Matrix4;
+
+impl ShaderDescriptor<1> for LayeredSpriteShader {
+    const VERTEX_SHADER: &'static str = include_str!("vertex.glsl");
+    const FRAGMENT_SHADER: &'static str = include_str!("fragment.glsl");
+    const TEXTURE_NAMES: [&'static str; 1] = ["tex"];
+    const VERTEX_UNIFORM_NAME: &'static str = "vertex";
+    type VertexUniformType = SpriteUniform;
+    type VertexDescriptor = LayeredSprite;
+}
+
+/// Draws sprites from the layers of a texture array, letting sprites from many texture pages share
+/// a single draw.
+pub struct LayeredSpriteShader {
+    shader: Shader<LayeredSpriteShader, 1>,
+}
+
+impl LayeredSpriteShader {
+    pub fn new() -> LayeredSpriteShader {
+        LayeredSpriteShader {
+            shader: Shader::new(),
+        }
+    }
+
+    /// Recompiles the shader, see [`crate::graphics::Shader::reload`].
+    pub fn reload(&mut self, vertex_shader: &str, fragment_shader: &str) -> Result<(), ShaderError> {
+        self.shader.reload(vertex_shader, fragment_shader)
+    }
+
+    /// Draws to the screen. The atlas must be a texture array, see [`Texture::from_images`].
+    pub fn draw(&self, uniform: &Uniform<SpriteUniform>, atlas: &Texture, buffer: &Buffer<LayeredSprite>) {
+        assert!(atlas.layers().is_some(), "The atlas must be a texture array.");
+        self.shader.draw_instanced(DrawMode::TriangleStrip, uniform, [atlas], buffer, 4);
+    }
+}
+
+pub struct LayeredSpriteShaderPass {
+    pub uniform: Uniform<SpriteUniform>,
+    /// The texture array sprites are drawn from.
+    pub atlas: Texture,
+    pub buffer: Buffer<LayeredSprite>,
+    render_state: RenderState,
+}
+
+impl LayeredSpriteShaderPass {
+    /// Creates a new pass drawing from the given texture array.
+    pub fn new(ortho: Matrix4<f32>, atlas: Texture) -> LayeredSpriteShaderPass {
+        LayeredSpriteShaderPass {
+            uniform: Uniform::new(SpriteUniform::new(ortho)),
+            atlas,
+            buffer: Buffer::new(),
+            render_state: RenderState::default(),
+        }
+    }
+
+    /// Sets the orthographic projection used to draw this pass. If none is passed, this function
+    /// does nothing.
+    pub fn set_ortho(&mut self, ortho: Option<Matrix4<f32>>) {
+        if let Some(ortho) = ortho {
+            self.uniform.set(SpriteUniform::new(ortho));
+        }
+    }
+
+    /// Sets the render state applied when drawing this pass.
+    pub fn set_render_state(&mut self, state: RenderState) {
+        self.render_state = state;
+    }
+
+    /// Draws the pass to the screen.
+    pub fn draw(&mut self, shader: &LayeredSpriteShader) {
+        set_render_state(&self.render_state);
+        shader.draw(&self.uniform, &self.atlas, &self.buffer);
+    }
+}
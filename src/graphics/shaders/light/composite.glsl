@@ -0,0 +1,14 @@
+precision mediump float;
+
+in vec2 v_uv;
+out vec4 a_color;
+
+uniform sampler2D tex;
+
+layout(std140) uniform fragment {
+    float strength;
+};
+
+void main() {
+    a_color = vec4(mix(vec3(1.0), texture(tex, v_uv).rgb, strength), 1.0);
+}
\ No newline at end of file
@@ -0,0 +1,209 @@
+use crate::color::RGBA8;
+use crate::graphics::{VertexAttribute, VertexDescriptor, VertexInputType, VertexOutputType, VertexStepRate};
+use crate::math::{cos_rad, sin_rad, AABB2D};
+use alloc::vec::Vec;
+use cgmath::*;
+
+/// The area a light shines into.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LightShape {
+    /// Shines in every direction.
+    Point,
+    /// Shines in a cone.
+    Cone {
+        /// The direction the cone points, measured in turns counter-clockwise from the positive X
+        /// axis.
+        direction: f32,
+        /// The full width of the cone, measured in turns.
+        angle: f32,
+        /// The width of the fade at each edge of the cone, measured in turns. The fade is inside the
+        /// cone's angle.
+        softness: f32,
+    },
+}
+
+/// How a light is blocked by occluders.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ShadowMode {
+    /// The light passes through occluders.
+    Disabled,
+    /// Occluders cast shadows with sharp edges, as if the light were a single point.
+    Hard,
+    /// Occluders cast shadows with soft edges, as if the light were a disc of the given radius.
+    Soft {
+        source_radius: f32,
+    },
+}
+
+/// A light, measured in the same units as the sprites it lights.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Light {
+    /// The center of the light.
+    pub pos: Vector2<f32>,
+    /// The color of the light. Its alpha scales the intensity.
+    pub color: RGBA8,
+    /// A multiplier on the light's color.
+    pub intensity: f32,
+    /// The distance the light reaches.
+    pub radius: f32,
+    /// How quickly the light fades with distance. The light is scaled by
+    /// `(1 - distance / radius) ^ falloff`, so 1.0 fades linearly, and larger values fade faster.
+    pub falloff: f32,
+    /// The area the light shines into.
+    pub shape: LightShape,
+    /// How the light is blocked by occluders.
+    pub shadows: ShadowMode,
+    /// How far the light is above the sprites. This is only used with normal mapping, where lower
+    /// lights graze the surface and highlight more detail.
+    pub height: f32,
+}
+
+impl Light {
+    /// Creates a new point light with an intensity of 1.0, a falloff of 2.0, and hard shadows.
+    pub fn point(pos: Vector2<f32>, radius: f32, color: RGBA8) -> Light {
+        Light {
+            pos,
+            color,
+            intensity: 1.0,
+            radius,
+            falloff: 2.0,
+            shape: LightShape::Point,
+            shadows: ShadowMode::Hard,
+            height: radius * 0.25,
+        }
+    }
+
+    /// Creates a new cone light with an intensity of 1.0, a falloff of 2.0, and hard shadows. The
+    /// direction and angle are measured in turns. See [`LightShape::Cone`].
+    pub fn cone(pos: Vector2<f32>, radius: f32, color: RGBA8, direction: f32, angle: f32) -> Light {
+        Light {
+            shape: LightShape::Cone {
+                direction,
+                angle,
+                softness: angle * 0.1,
+            },
+            ..Light::point(pos, radius, color)
+        }
+    }
+
+    /// The area the light can reach.
+    pub fn bounds(&self) -> AABB2D {
+        let radius = Vector2::new(self.radius, self.radius);
+        AABB2D::from_min_max(self.pos - radius, self.pos + radius)
+    }
+}
+
+/// A shape that blocks light. Shadows are cast from the edges facing away from each light, so the
+/// occluder itself is lit.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Occluder {
+    /// An axis aligned rectangle.
+    Rect(AABB2D),
+    /// A polygon, given as its corners in order. Either winding is accepted. The polygon must not
+    /// intersect itself.
+    Polygon(Vec<Vector2<f32>>),
+}
+
+impl Occluder {
+    /// The smallest box containing the occluder.
+    pub fn bounds(&self) -> AABB2D {
+        match self {
+            Occluder::Rect(rect) => *rect,
+            Occluder::Polygon(points) => {
+                let mut min = Vector2::new(f32::INFINITY, f32::INFINITY);
+                let mut max = Vector2::new(f32::NEG_INFINITY, f32::NEG_INFINITY);
+                for point in points {
+                    min = Vector2::new(min.x.min(point.x), min.y.min(point.y));
+                    max = Vector2::new(max.x.max(point.x), max.y.max(point.y));
+                }
+                AABB2D::from_min_max(min, max)
+            }
+        }
+    }
+}
+
+/// A light as drawn by the light shader.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(super) struct LightInstance {
+    pub pos: Vector2<f32>,
+    /// Radius, falloff.
+    pub shape: Vector2<f32>,
+    pub color: RGBA8,
+    /// Intensity, height.
+    pub power: Vector2<f32>,
+    /// Unit vector the cone points along.
+    pub direction: Vector2<f32>,
+    /// Cosines of the cone's outer and inner half angles.
+    pub cone: Vector2<f32>,
+    /// 1.0 if the light reads the shadow mask.
+    pub shadowed: f32,
+}
+
+impl VertexDescriptor for LightInstance {
+    const ATTRIBUTES: &'static [VertexAttribute] = &[
+        // Pos, Shape, Color::RGBA8, Power, Direction, Cone, Shadowed
+        VertexAttribute::new(2, VertexInputType::F32, VertexOutputType::F32),
+        VertexAttribute::new(2, VertexInputType::F32, VertexOutputType::F32),
+        VertexAttribute::new(4, VertexInputType::U8, VertexOutputType::NormalizedF32),
+        VertexAttribute::new(2, VertexInputType::F32, VertexOutputType::F32),
+        VertexAttribute::new(2, VertexInputType::F32, VertexOutputType::F32),
+        VertexAttribute::new(2, VertexInputType::F32, VertexOutputType::F32),
+        VertexAttribute::new(1, VertexInputType::F32, VertexOutputType::F32),
+    ];
+}
+
+impl LightInstance {
+    pub fn new(light: &Light, shadowed: bool) -> LightInstance {
+        let (direction, cone) = match light.shape {
+            // The cone test always passes.
+            LightShape::Point => (Vector2::new(1.0, 0.0), Vector2::new(-2.0, -1.5)),
+            LightShape::Cone {
+                direction,
+                angle,
+                softness,
+            } => {
+                let radians = direction * core::f32::consts::PI * 2.0;
+                let outer = angle.clamp(0.0, 1.0) * core::f32::consts::PI;
+                let inner = (outer - softness.max(0.0) * core::f32::consts::PI).max(0.0);
+                (
+                    Vector2::new(cos_rad(radians), sin_rad(radians)),
+                    // Keep the edges apart so the fade is defined.
+                    Vector2::new(cos_rad(outer), cos_rad(inner).max(cos_rad(outer) + 0.0001)),
+                )
+            }
+        };
+        LightInstance {
+            pos: light.pos,
+            shape: Vector2::new(light.radius, light.falloff),
+            color: light.color,
+            power: Vector2::new(light.intensity, light.height),
+            direction,
+            cone,
+            shadowed: if shadowed {
+                1.0
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// A vertex of a shadow in the shadow mask.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(super) struct ShadowVertex {
+    pub pos: Vector2<f32>,
+    /// The shadow's strength is x / y, which lets the soft edges of shadows fade by angle rather
+    /// than by distance.
+    pub shade: Vector2<f32>,
+}
+
+impl VertexDescriptor for ShadowVertex {
+    const STEP_RATE: VertexStepRate = VertexStepRate::PerVertex;
+    const ATTRIBUTES: &'static [VertexAttribute] = &[
+        // Pos, Shade
+        VertexAttribute::new(2, VertexInputType::F32, VertexOutputType::F32),
+        VertexAttribute::new(2, VertexInputType::F32, VertexOutputType::F32),
+    ];
+}
@@ -0,0 +1,40 @@
+precision mediump float;
+
+in vec2 v_offset;
+in vec2 v_shape;
+in vec4 v_color;
+in vec2 v_power;
+in vec2 v_direction;
+in vec2 v_cone;
+in float v_shadowed;
+out vec4 a_color;
+
+uniform sampler2D mask;
+uniform sampler2D normals;
+
+layout(std140) uniform vertex {
+    mat4 ortho;
+    vec2 target_size;
+    float normal_mapping;
+};
+
+void main() {
+    float dist = length(v_offset);
+    float reach = dist / v_shape.x;
+    if (reach >= 1.0) {
+        discard;
+    }
+    float attenuation = pow(1.0 - reach, v_shape.y);
+    vec2 direction = v_offset / max(dist, 0.0001);
+    float cone = smoothstep(v_cone.x, v_cone.y, dot(direction, v_direction));
+    vec2 uv = gl_FragCoord.xy / target_size;
+    float shadow = v_shadowed > 0.5 ? texture(mask, uv).r : 1.0;
+    float diffuse = 1.0;
+    if (normal_mapping > 0.5) {
+        vec3 normal = normalize(texture(normals, uv).rgb * 2.0 - 1.0);
+        vec3 to_light = normalize(vec3(-v_offset, v_power.y));
+        diffuse = max(dot(normal, to_light), 0.0);
+    }
+    float strength = v_color.a * v_power.x * attenuation * cone * shadow * diffuse;
+    a_color = vec4(v_color.rgb * strength, 1.0);
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+precision highp float;
+
+layout(location = 0) in vec2 a_pos;
+layout(location = 1) in vec2 a_shape;
+layout(location = 2) in vec4 a_color;
+layout(location = 3) in vec2 a_power;
+layout(location = 4) in vec2 a_direction;
+layout(location = 5) in vec2 a_cone;
+layout(location = 6) in float a_shadowed;
+
+out vec2 v_offset;
+out vec2 v_shape;
+out vec4 v_color;
+out vec2 v_power;
+out vec2 v_direction;
+out vec2 v_cone;
+out float v_shadowed;
+
+layout(std140) uniform vertex {
+    mat4 ortho;
+    vec2 target_size;
+    float normal_mapping;
+};
+
+vec2 corner_lut[4] = vec2[4](
+    vec2(-1.0, -1.0),  // left bottom
+    vec2(1.0, -1.0),   // right bottom
+    vec2(-1.0, 1.0),   // left top
+    vec2(1.0, 1.0));   // right top
+
+void main() {
+    v_offset = corner_lut[gl_VertexID] * a_shape.x;
+    v_shape = a_shape;
+    v_color = a_color;
+    v_power = a_power;
+    v_direction = a_direction;
+    v_cone = a_cone;
+    v_shadowed = a_shadowed;
+    gl_Position = ortho * vec4(a_pos + v_offset, 0.0, 1.0);
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+mod data;
+mod shader;
+mod shadow;
+
+pub use self::data::{Light, LightShape, Occluder, ShadowMode};
+pub use self::shader::{LightCompositeUniform, LightShader, LightUniform, LightingPass};
+
+use self::data::{LightInstance, ShadowVertex};
@@ -0,0 +1,313 @@
+use crate::color::RGBA8;
+use crate::ctx;
+use crate::graphics::{
+    clear, default_texture, resource, set_render_state,
+    shaders::{
+        light::{shadow, Light, LightInstance, Occluder, ShadowMode, ShadowVertex},
+        post_process::{fit_target, PostVertex, FULLSCREEN_TRIANGLE, POST_VERTEX_SHADER},
+    },
+    viewport_physical_size, AsStd140, BlendMode, Buffer, ClearMode, DrawMode, RenderState, RenderTarget,
+    Shader, ShaderDescriptor, Uniform,
+};
+use alloc::vec::Vec;
+use cgmath::*;
+
+/// Normals pointing straight out of the screen.
+const FLAT_NORMAL: RGBA8 = RGBA8::new(128, 128, 255, 255);
+
+const fn blended(blend: Option<BlendMode>) -> RenderState {
+    RenderState {
+        blend,
+        depth_test: false,
+        depth_write: false,
+        cull: None,
+        scissor: None,
+    }
+}
+
+/// Used to clear the light buffers.
+const CLEAR_STATE: RenderState = blended(None);
+/// Shadows darken the mask by their alpha.
+const SHADOW_STATE: RenderState = blended(Some(BlendMode::Alpha));
+/// Lights add up in the light buffer.
+const LIGHT_STATE: RenderState = blended(Some(BlendMode::Additive));
+/// The light buffer is multiplied over the screen.
+const COMPOSITE_STATE: RenderState = blended(Some(BlendMode::Multiply));
+
+impl ShaderDescriptor<2> for LightProgram {
+    const VERTEX_SHADER: &'static str = include_str!("light_vertex.glsl");
+    const FRAGMENT_SHADER: &'static str = include_str!("light_fragment.glsl");
+    const TEXTURE_NAMES: [&'static str; 2] = ["mask", "normals"];
+    const VERTEX_UNIFORM_NAME: &'static str = "vertex";
+    type VertexUniformType = LightUniform;
+    type VertexDescriptor = LightInstance;
+}
+
+impl ShaderDescriptor<0> for ShadowProgram {
+    const VERTEX_SHADER: &'static str = include_str!("shadow_vertex.glsl");
+    const FRAGMENT_SHADER: &'static str = include_str!("shadow_fragment.glsl");
+    const TEXTURE_NAMES: [&'static str; 0] = [];
+    const VERTEX_UNIFORM_NAME: &'static str = "vertex";
+    type VertexUniformType = LightUniform;
+    type VertexDescriptor = ShadowVertex;
+}
+
+impl ShaderDescriptor<1> for CompositeProgram {
+    const VERTEX_SHADER: &'static str = POST_VERTEX_SHADER;
+    const FRAGMENT_SHADER: &'static str = include_str!("composite.glsl");
+    const TEXTURE_NAMES: [&'static str; 1] = ["tex"];
+    const VERTEX_UNIFORM_NAME: &'static str = "fragment";
+    type VertexUniformType = LightCompositeUniform;
+    type VertexDescriptor = PostVertex;
+}
+
+#[derive(AsStd140)]
+pub struct LightUniform {
+    pub ortho: Matrix4<f32>,
+    /// The size of the light buffer in physical pixels.
+    pub target_size: Vector2<f32>,
+    /// 1.0 if lights read the normal buffer.
+    pub normal_mapping: f32,
+}
+
+#[derive(AsStd140)]
+pub struct LightCompositeUniform {
+    pub strength: f32,
+}
+
+struct LightProgram;
+struct ShadowProgram;
+struct CompositeProgram;
+
+/// The shaders used by [`LightingPass`].
+pub struct LightShader {
+    light: Shader<LightProgram, 2>,
+    shadow: Shader<ShadowProgram, 0>,
+    composite: Shader<CompositeProgram, 1>,
+}
+
+impl LightShader {
+    pub fn new() -> LightShader {
+        LightShader {
+            light: Shader::new(),
+            shadow: Shader::new(),
+            composite: Shader::new(),
+        }
+    }
+}
+
+/// Draws dynamic lights over what's already been drawn. Lights are added into a light buffer that
+/// starts at the ambient color, which is then multiplied over the screen, so anything drawn before
+/// the pass is lit, and anything drawn after it isn't. This works with any pass, such as
+/// [`crate::graphics::shaders::sprite::SpriteShaderPass`], and inside a render target such as a
+/// [`crate::graphics::shaders::post_process::PostProcessStack`].
+///
+/// For normal mapping, draw the normal maps of sprites between [`LightingPass::begin_normals`] and
+/// [`LightingPass::end_normals`] with the same passes and transforms as their colors, before
+/// drawing the pass. Normal maps use the OpenGL convention, with green pointing up the screen, and
+/// aren't rotated with their sprite.
+pub struct LightingPass {
+    /// The lights drawn by the pass.
+    pub lights: Vec<Light>,
+    /// The shapes blocking light.
+    pub occluders: Vec<Occluder>,
+    ortho: Matrix4<f32>,
+    ambient: RGBA8,
+    strength: f32,
+    uniform: Uniform<LightUniform>,
+    composite_uniform: Uniform<LightCompositeUniform>,
+    light_buffer: Buffer<LightInstance>,
+    shadow_buffer: Buffer<ShadowVertex>,
+    quad: Buffer<PostVertex>,
+    instances: Vec<LightInstance>,
+    shadows: Vec<ShadowVertex>,
+    /// Lights with occluders in reach, and their source radius.
+    shadowed: Vec<(usize, f32)>,
+    accumulation: Option<RenderTarget>,
+    mask: Option<RenderTarget>,
+    normals: Option<RenderTarget>,
+    normals_drawn: bool,
+    previous: Option<(resource::Framebuffer, Vector2<u32>)>,
+}
+
+impl LightingPass {
+    /// Creates a new pass with a black ambient color and no lights.
+    pub fn new(ortho: Matrix4<f32>) -> LightingPass {
+        let mut quad = Buffer::new();
+        quad.set(&FULLSCREEN_TRIANGLE);
+        LightingPass {
+            lights: Vec::new(),
+            occluders: Vec::new(),
+            ortho,
+            ambient: RGBA8::BLACK,
+            strength: 1.0,
+            uniform: Uniform::new(LightUniform {
+                ortho,
+                target_size: Vector2::new(1.0, 1.0),
+                normal_mapping: 0.0,
+            }),
+            composite_uniform: Uniform::new(LightCompositeUniform {
+                strength: 1.0,
+            }),
+            light_buffer: Buffer::new(),
+            shadow_buffer: Buffer::new(),
+            quad,
+            instances: Vec::new(),
+            shadows: Vec::new(),
+            shadowed: Vec::new(),
+            accumulation: None,
+            mask: None,
+            normals: None,
+            normals_drawn: false,
+            previous: None,
+        }
+    }
+
+    /// Sets the orthographic projection used to draw this pass. This is usually the same as the
+    /// passes being lit. If none is passed, this function does nothing.
+    pub fn set_ortho(&mut self, ortho: Option<Matrix4<f32>>) {
+        if let Some(ortho) = ortho {
+            self.ortho = ortho;
+        }
+    }
+
+    /// The light everything receives, even outside of every light.
+    pub fn ambient(&self) -> RGBA8 {
+        self.ambient
+    }
+
+    /// Sets the light everything receives, even outside of every light. The default is black.
+    pub fn set_ambient(&mut self, ambient: RGBA8) {
+        self.ambient = ambient;
+    }
+
+    /// Sets how strongly the lighting is applied, from 0.0 leaving the screen unchanged, to 1.0
+    /// fully lit. The default is 1.0.
+    pub fn set_strength(&mut self, strength: f32) {
+        self.strength = strength.clamp(0.0, 1.0);
+        self.composite_uniform.set(LightCompositeUniform {
+            strength: self.strength,
+        });
+    }
+
+    /// Directs subsequent draws into the normal buffer, which is cleared to flat normals. The normal
+    /// buffer is used by the next call to [`LightingPass::draw`].
+    pub fn begin_normals(&mut self) {
+        self.previous = ctx().graphics().render_target();
+        let size = self.target_size();
+        bind(fit_target(&mut self.normals, size));
+        clear(ClearMode::color_depth(FLAT_NORMAL));
+        self.normals_drawn = true;
+    }
+
+    /// Directs draws back to where they were directed before [`LightingPass::begin_normals`].
+    pub fn end_normals(&mut self) {
+        ctx().graphics().set_render_target(self.previous.take());
+    }
+
+    /// Draws the lights into the light buffer, then multiplies it over what's been drawn.
+    pub fn draw(&mut self, shader: &LightShader) {
+        let previous = ctx().graphics().render_target();
+        let size = self.target_size();
+        self.uniform.set(LightUniform {
+            ortho: self.ortho,
+            target_size: size.map(|x| x.max(1) as f32),
+            normal_mapping: if self.normals_drawn {
+                1.0
+            } else {
+                0.0
+            },
+        });
+        let normals = match (&self.normals, self.normals_drawn) {
+            (Some(normals), true) => normals.texture().clone(),
+            _ => default_texture(),
+        };
+
+        // Lights without shadows are drawn together.
+        let accumulation = fit_target(&mut self.accumulation, size);
+        bind(accumulation);
+        set_render_state(&CLEAR_STATE);
+        clear(ClearMode::color(self.ambient));
+        self.instances.clear();
+        self.shadowed.clear();
+        for (index, light) in self.lights.iter().enumerate() {
+            let source_radius = match light.shadows {
+                ShadowMode::Disabled => None,
+                ShadowMode::Hard => Some(0.0),
+                ShadowMode::Soft {
+                    source_radius,
+                } => Some(source_radius),
+            };
+            let bounds = light.bounds();
+            match source_radius {
+                Some(source_radius)
+                    if self.occluders.iter().any(|occluder| occluder.bounds().intersects(&bounds)) =>
+                {
+                    self.shadowed.push((index, source_radius))
+                }
+                _ => self.instances.push(LightInstance::new(light, false)),
+            }
+        }
+        let mask = fit_target(&mut self.mask, size);
+        if !self.instances.is_empty() {
+            self.light_buffer.set(&self.instances);
+            set_render_state(&LIGHT_STATE);
+            shader.light.draw_instanced(
+                DrawMode::TriangleStrip,
+                &self.uniform,
+                [mask.texture(), &normals],
+                &self.light_buffer,
+                4,
+            );
+        }
+
+        // Lights with shadows draw their shadows into the mask first.
+        for &(index, source_radius) in self.shadowed.iter() {
+            let light = &self.lights[index];
+            self.shadows.clear();
+            let bounds = light.bounds();
+            for occluder in self.occluders.iter().filter(|occluder| occluder.bounds().intersects(&bounds)) {
+                shadow::build(light.pos, light.radius, source_radius, occluder, &mut self.shadows);
+            }
+            bind(mask);
+            set_render_state(&CLEAR_STATE);
+            clear(ClearMode::color(RGBA8::WHITE));
+            self.shadow_buffer.set(&self.shadows);
+            set_render_state(&SHADOW_STATE);
+            shader.shadow.draw(DrawMode::Triangles, &self.uniform, [], &self.shadow_buffer);
+
+            bind(accumulation);
+            self.light_buffer.set(&[LightInstance::new(light, true)]);
+            set_render_state(&LIGHT_STATE);
+            shader.light.draw_instanced(
+                DrawMode::TriangleStrip,
+                &self.uniform,
+                [mask.texture(), &normals],
+                &self.light_buffer,
+                4,
+            );
+        }
+
+        ctx().graphics().set_render_target(previous);
+        set_render_state(&COMPOSITE_STATE);
+        shader.composite.draw(
+            DrawMode::Triangles,
+            &self.composite_uniform,
+            [accumulation.texture()],
+            &self.quad,
+        );
+        self.normals_drawn = false;
+    }
+
+    /// The size of the light buffer, matching what draws are currently directed to.
+    fn target_size(&self) -> Vector2<u32> {
+        match ctx().graphics().render_target() {
+            Some((_, size)) => size,
+            None => viewport_physical_size().map(|x| x as u32),
+        }
+    }
+}
+
+fn bind(target: &RenderTarget) {
+    ctx().graphics().set_render_target(Some((target.framebuffer(), target.size())));
+}
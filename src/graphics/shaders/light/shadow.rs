@@ -0,0 +1,200 @@
+use crate::graphics::shaders::light::{Occluder, ShadowVertex};
+use alloc::vec::Vec;
+use cgmath::*;
+
+const UMBRA: Vector2<f32> = Vector2::new(1.0, 1.0);
+
+/// Appends the triangles of the shadow an occluder casts from a light. Each edge facing away from
+/// the light is extruded away from it into the umbra. With a source radius, each corner where the
+/// occluder's outline turns away from the light also gets a fin that fades out from the umbra.
+pub(super) fn build(
+    light: Vector2<f32>,
+    radius: f32,
+    source_radius: f32,
+    occluder: &Occluder,
+    out: &mut Vec<ShadowVertex>,
+) {
+    match occluder {
+        Occluder::Rect(rect) => {
+            let corners = [
+                rect.min,
+                Vector2::new(rect.max.x, rect.min.y),
+                rect.max,
+                Vector2::new(rect.min.x, rect.max.y),
+            ];
+            build_polygon(light, radius, source_radius, &corners, out);
+        }
+        Occluder::Polygon(points) => build_polygon(light, radius, source_radius, points, out),
+    }
+}
+
+fn build_polygon(
+    light: Vector2<f32>,
+    radius: f32,
+    source_radius: f32,
+    points: &[Vector2<f32>],
+    out: &mut Vec<ShadowVertex>,
+) {
+    let count = points.len();
+    if count < 2 {
+        return;
+    }
+    // Far enough that the extruded shadow covers the light's reach.
+    let extent = radius * 2.0;
+    let area: f32 = (0..count).map(|i| cross(points[i], points[(i + 1) % count])).sum();
+    let winding = if area < 0.0 {
+        -1.0
+    } else {
+        1.0
+    };
+    let faces_away = |i: usize| {
+        let a = points[i];
+        let edge = points[(i + 1) % count] - a;
+        let normal = Vector2::new(edge.y, -edge.x) * winding;
+        normal.dot(a - light) > 0.0
+    };
+
+    for i in 0..count {
+        if !faces_away(i) {
+            continue;
+        }
+        let a = points[i];
+        let b = points[(i + 1) % count];
+        let (da, db) = match (direction(light, a), direction(light, b)) {
+            (Some(da), Some(db)) => (da, db),
+            _ => continue,
+        };
+        let middle = match (da + db).normalize() {
+            middle if middle.x.is_finite() && middle.y.is_finite() => middle,
+            _ => continue,
+        };
+        let a_far = a + da * extent;
+        let b_far = b + db * extent;
+        let middle_far = (a + b) * 0.5 + middle * extent;
+        for &pos in &[a, b, b_far, a, b_far, middle_far, a, middle_far, a_far] {
+            out.push(ShadowVertex {
+                pos,
+                shade: UMBRA,
+            });
+        }
+    }
+
+    if source_radius <= 0.0 {
+        return;
+    }
+    for i in 0..count {
+        let previous = (i + count - 1) % count;
+        let (before, after) = (faces_away(previous), faces_away(i));
+        if before == after {
+            continue;
+        }
+        let corner = points[i];
+        let center = match direction(light, corner) {
+            Some(center) => center,
+            None => continue,
+        };
+        // The shadow is on the side of the center ray where the edge facing away continues.
+        let along = if after {
+            points[(i + 1) % count]
+        } else {
+            points[previous]
+        };
+        let side = cross(center, along - corner);
+        let perpendicular = Vector2::new(-center.y, center.x) * source_radius;
+        let outer = [light + perpendicular, light - perpendicular]
+            .iter()
+            .filter_map(|&source| direction(source, corner))
+            .find(|&ray| cross(center, ray) * side < 0.0);
+        if let Some(outer) = outer {
+            out.push(ShadowVertex {
+                pos: corner,
+                shade: Vector2::zero(),
+            });
+            out.push(ShadowVertex {
+                pos: corner + outer * extent,
+                shade: Vector2::new(0.0, 1.0),
+            });
+            out.push(ShadowVertex {
+                pos: corner + center * extent,
+                shade: UMBRA,
+            });
+        }
+    }
+}
+
+fn direction(from: Vector2<f32>, to: Vector2<f32>) -> Option<Vector2<f32>> {
+    let offset = to - from;
+    let length = offset.magnitude();
+    if length > 0.0 {
+        Some(offset / length)
+    } else {
+        None
+    }
+}
+
+fn cross(a: Vector2<f32>, b: Vector2<f32>) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::AABB2D;
+    use alloc::vec;
+
+    fn in_triangle(point: Vector2<f32>, triangle: &[ShadowVertex]) -> bool {
+        let [a, b, c] = [triangle[0].pos, triangle[1].pos, triangle[2].pos];
+        let d1 = cross(b - a, point - a);
+        let d2 = cross(c - b, point - b);
+        let d3 = cross(a - c, point - c);
+        (d1 >= 0.0 && d2 >= 0.0 && d3 >= 0.0) || (d1 <= 0.0 && d2 <= 0.0 && d3 <= 0.0)
+    }
+
+    fn shadowed(point: Vector2<f32>, vertices: &[ShadowVertex]) -> bool {
+        vertices.chunks(3).any(|triangle| triangle[0].shade == UMBRA && in_triangle(point, triangle))
+    }
+
+    #[test]
+    fn rect_casts_from_far_edges() {
+        let rect = Occluder::Rect(AABB2D::new(10.0, -5.0, 20.0, 5.0));
+        let mut vertices = Vec::new();
+        build(Vector2::zero(), 100.0, 0.0, &rect, &mut vertices);
+        // Top, right, and bottom face away from the light.
+        assert_eq!(vertices.len(), 3 * 9);
+        assert!(shadowed(Vector2::new(50.0, 0.0), &vertices));
+        assert!(shadowed(Vector2::new(90.0, 30.0), &vertices));
+        assert!(!shadowed(Vector2::new(5.0, 0.0), &vertices));
+        assert!(!shadowed(Vector2::new(50.0, 40.0), &vertices));
+    }
+
+    #[test]
+    fn soft_shadows_add_fins() {
+        let rect = Occluder::Rect(AABB2D::new(10.0, -5.0, 20.0, 5.0));
+        let mut vertices = Vec::new();
+        build(Vector2::zero(), 100.0, 4.0, &rect, &mut vertices);
+        assert_eq!(vertices.len(), 3 * 9 + 2 * 3);
+        // Fins sit outside the umbra.
+        let fins = &vertices[27..];
+        let outside = Vector2::new(60.0, 33.0);
+        assert!(!shadowed(outside, &vertices));
+        assert!(fins.chunks(3).any(|fin| in_triangle(outside, fin)));
+    }
+
+    #[test]
+    fn winding_is_ignored() {
+        let clockwise = Occluder::Polygon(vec![
+            Vector2::new(10.0, -5.0),
+            Vector2::new(10.0, 5.0),
+            Vector2::new(20.0, 5.0),
+            Vector2::new(20.0, -5.0),
+        ]);
+        let mut vertices = Vec::new();
+        build(Vector2::zero(), 100.0, 0.0, &clockwise, &mut vertices);
+        assert_eq!(vertices.len(), 3 * 9);
+        assert!(shadowed(Vector2::new(50.0, 0.0), &vertices));
+    }
+}
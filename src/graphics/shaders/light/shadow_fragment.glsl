@@ -0,0 +1,10 @@
+precision mediump float;
+
+in vec2 v_shade;
+out vec4 a_color;
+
+void main() {
+    // Dividing after interpolation fades the soft edges by angle around their corner.
+    float shadow = clamp(v_shade.x / max(v_shade.y, 0.0001), 0.0, 1.0);
+    a_color = vec4(0.0, 0.0, 0.0, shadow);
+}
\ No newline at end of file
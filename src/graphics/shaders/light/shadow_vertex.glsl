@@ -0,0 +1,17 @@
+precision highp float;
+
+layout(location = 0) in vec2 a_pos;
+layout(location = 1) in vec2 a_shade;
+
+out vec2 v_shade;
+
+layout(std140) uniform vertex {
+    mat4 ortho;
+    vec2 target_size;
+    float normal_mapping;
+};
+
+void main() {
+    v_shade = a_shade;
+    gl_Position = ortho * vec4(a_pos, 0.0, 1.0);
+}
\ No newline at end of file
@@ -1,4 +1,18 @@
+/// Bundled sample shaders for sprites with a smaller vertex format.
+pub mod compact_sprite;
+/// Bundled sample shaders for sprite rendering from texture arrays.
+pub mod layered_sprite;
+/// Bundled sample shaders for dynamic 2D lights and shadows.
+pub mod light;
+/// Bundled sample shaders for particle emitters simulated on the GPU.
+pub mod particle;
+/// Full-screen post-processing effects drawn over an offscreen scene.
+pub mod post_process;
+/// Bundled sample shaders for anti-aliased lines and shapes.
+pub mod shape;
 /// Bundled sample shaders for basic sprite rendering.
 pub mod sprite;
-/// Bundled sample shaders for basic text rendering.
+/// Bundled sample shaders for text rendering from bitmaps or distance fields.
 pub mod text;
+/// Bundled sample shaders for chunked tilemap rendering.
+pub mod tilemap;
@@ -0,0 +1,267 @@
+use crate::color::RGBA8;
+use crate::graphics::{TextureSection, VertexAttribute, VertexDescriptor, VertexInputType, VertexOutputType};
+use cgmath::*;
+
+/// The most keys a [`Curve`] or [`Gradient`] can hold. Curves are evaluated on the GPU, so they're
+/// kept small enough to fit in a uniform.
+pub const MAX_CURVE_KEYS: usize = 4;
+
+/// A value that changes over a particle's life. Keys are placed at times in [0, 1], where 0 is when
+/// the particle spawns and 1 is when it dies, and the value is linearly interpolated between them.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Curve {
+    times: [f32; MAX_CURVE_KEYS],
+    values: [f32; MAX_CURVE_KEYS],
+}
+
+impl Curve {
+    /// Creates a curve from keys of (time, value), sorted by time. Panics if there are no keys or
+    /// more than [`MAX_CURVE_KEYS`].
+    pub fn new(keys: &[(f32, f32)]) -> Curve {
+        let (times, values) = pad_keys(keys);
+        Curve {
+            times,
+            values,
+        }
+    }
+
+    /// Creates a curve that holds the same value for the whole life.
+    pub fn constant(value: f32) -> Curve {
+        Curve::new(&[(0.0, value)])
+    }
+
+    /// Creates a curve that goes from the start value at spawn to the end value at death.
+    pub fn linear(start: f32, end: f32) -> Curve {
+        Curve::new(&[(0.0, start), (1.0, end)])
+    }
+
+    /// Gets the value at the given time in the particle's life.
+    pub fn sample(&self, t: f32) -> f32 {
+        let (from, to, amount) = segment(&self.times, t);
+        self.values[from] + (self.values[to] - self.values[from]) * amount
+    }
+
+    pub(super) fn times(&self) -> Vector4<f32> {
+        Vector4::from(self.times)
+    }
+
+    pub(super) fn values(&self) -> Vector4<f32> {
+        Vector4::from(self.values)
+    }
+}
+
+/// A color that changes over a particle's life. Works like a [`Curve`], interpolating each channel.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Gradient {
+    times: [f32; MAX_CURVE_KEYS],
+    colors: [RGBA8; MAX_CURVE_KEYS],
+}
+
+impl Gradient {
+    /// Creates a gradient from keys of (time, color), sorted by time. Panics if there are no keys
+    /// or more than [`MAX_CURVE_KEYS`].
+    pub fn new(keys: &[(f32, RGBA8)]) -> Gradient {
+        let (times, colors) = pad_keys(keys);
+        Gradient {
+            times,
+            colors,
+        }
+    }
+
+    /// Creates a gradient that holds the same color for the whole life.
+    pub fn constant(color: RGBA8) -> Gradient {
+        Gradient::new(&[(0.0, color)])
+    }
+
+    /// Creates a gradient that goes from the start color at spawn to the end color at death.
+    pub fn linear(start: RGBA8, end: RGBA8) -> Gradient {
+        Gradient::new(&[(0.0, start), (1.0, end)])
+    }
+
+    /// Gets the color at the given time in the particle's life.
+    pub fn sample(&self, t: f32) -> RGBA8 {
+        let (from, to, amount) = segment(&self.times, t);
+        let from = color_vector(self.colors[from]);
+        let to = color_vector(self.colors[to]);
+        let color = (from + (to - from) * amount) * 255.0;
+        RGBA8::new(color.x.round() as u8, color.y.round() as u8, color.z.round() as u8, color.w.round() as u8)
+    }
+
+    pub(super) fn times(&self) -> Vector4<f32> {
+        Vector4::from(self.times)
+    }
+
+    /// The colors as the columns of a matrix.
+    pub(super) fn colors(&self) -> Matrix4<f32> {
+        Matrix4::from_cols(
+            color_vector(self.colors[0]),
+            color_vector(self.colors[1]),
+            color_vector(self.colors[2]),
+            color_vector(self.colors[3]),
+        )
+    }
+}
+
+/// Fills the unused keys with copies of the last key, so sampling never needs the key count.
+fn pad_keys<T: Copy>(keys: &[(f32, T)]) -> ([f32; MAX_CURVE_KEYS], [T; MAX_CURVE_KEYS]) {
+    assert!(!keys.is_empty(), "Curves need at least one key.");
+    assert!(keys.len() <= MAX_CURVE_KEYS, "Curves can't have more than {} keys.", MAX_CURVE_KEYS);
+    let last = keys[keys.len() - 1];
+    let mut times = [last.0; MAX_CURVE_KEYS];
+    let mut values = [last.1; MAX_CURVE_KEYS];
+    for (index, key) in keys.iter().enumerate() {
+        times[index] = key.0;
+        values[index] = key.1;
+    }
+    (times, values)
+}
+
+/// Finds the keys around the time, and how far the time is between them. This matches the sampling
+/// in the particle vertex shader.
+fn segment(times: &[f32; MAX_CURVE_KEYS], t: f32) -> (usize, usize, f32) {
+    if t <= times[0] {
+        return (0, 0, 0.0);
+    }
+    for index in 1..MAX_CURVE_KEYS {
+        if t <= times[index] {
+            let span = times[index] - times[index - 1];
+            let amount = if span > 0.0 {
+                (t - times[index - 1]) / span
+            } else {
+                1.0
+            };
+            return (index - 1, index, amount);
+        }
+    }
+    (MAX_CURVE_KEYS - 1, MAX_CURVE_KEYS - 1, 0.0)
+}
+
+fn color_vector(color: RGBA8) -> Vector4<f32> {
+    Vector4::new(color.r as f32, color.g as f32, color.b as f32, color.a as f32) / 255.0
+}
+
+/// Describes how an emitter spawns particles, and how they move and look over their life.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct EmitterSettings {
+    /// Particles spawned per second while the emitter is emitting.
+    pub rate: f32,
+    /// The shortest and longest life of a particle in seconds. Each particle picks a life in
+    /// between.
+    pub lifetime: (f32, f32),
+    /// The direction particles are launched in, measured in turns. 0 is right and 0.25 is up.
+    pub direction: f32,
+    /// The width of the cone particles are launched within, measured in turns and centered on the
+    /// direction. 0 launches every particle in the same direction, and 1 launches in all directions.
+    pub spread: f32,
+    /// The slowest and fastest launch speed in units per second.
+    pub speed: (f32, f32),
+    /// Acceleration applied to every particle in units per second squared.
+    pub gravity: Vector2<f32>,
+    /// The fraction of velocity lost per second.
+    pub drag: f32,
+    /// The width and height of a particle in units over its life.
+    pub size: Curve,
+    /// The color multiplier of a particle over its life.
+    pub color: Gradient,
+    /// The texture drawn for each particle. The default is a plain white texture.
+    pub texture: TextureSection,
+}
+
+impl Default for EmitterSettings {
+    fn default() -> EmitterSettings {
+        EmitterSettings {
+            rate: 100.0,
+            lifetime: (1.0, 1.0),
+            direction: 0.25,
+            spread: 0.125,
+            speed: (50.0, 100.0),
+            gravity: Vector2::zero(),
+            drag: 0.0,
+            size: Curve::constant(8.0),
+            color: Gradient::linear(RGBA8::WHITE, RGBA8::new(255, 255, 255, 0)),
+            texture: TextureSection::full(),
+        }
+    }
+}
+
+/// The simulated state of a particle. A particle is dead once its age reaches its lifetime.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(super) struct Particle {
+    pub pos: Vector2<f32>,
+    pub velocity: Vector2<f32>,
+    pub age: f32,
+    pub lifetime: f32,
+}
+
+impl VertexDescriptor for Particle {
+    const ATTRIBUTES: &'static [VertexAttribute] = &[
+        // Pos, Velocity, Age and Lifetime
+        VertexAttribute::new(2, VertexInputType::F32, VertexOutputType::F32),
+        VertexAttribute::new(2, VertexInputType::F32, VertexOutputType::F32),
+        VertexAttribute::new(2, VertexInputType::F32, VertexOutputType::F32),
+    ];
+}
+
+impl Particle {
+    pub const DEAD: Particle = Particle {
+        pos: Vector2::new(0.0, 0.0),
+        velocity: Vector2::new(0.0, 0.0),
+        age: 0.0,
+        lifetime: 0.0,
+    };
+
+    /// Advances the particle by the delta in seconds. This matches the particle update shader.
+    pub fn step(&mut self, gravity: Vector2<f32>, drag: f32, delta: f32) {
+        if self.age < self.lifetime {
+            self.velocity += gravity * delta;
+            self.velocity *= (1.0 - drag * delta).max(0.0);
+            self.pos += self.velocity * delta;
+        }
+        self.age = (self.age + delta).min(self.lifetime);
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn curve_interpolates_between_keys() {
+        let curve = Curve::new(&[(0.0, 0.0), (0.5, 10.0), (1.0, 4.0)]);
+        assert_eq!(curve.sample(-1.0), 0.0);
+        assert_eq!(curve.sample(0.25), 5.0);
+        assert_eq!(curve.sample(0.75), 7.0);
+        assert_eq!(curve.sample(2.0), 4.0);
+        assert_eq!(Curve::constant(3.0).sample(0.5), 3.0);
+    }
+
+    #[test]
+    fn gradient_interpolates_channels() {
+        let gradient = Gradient::linear(RGBA8::new(0, 100, 200, 255), RGBA8::new(200, 100, 0, 55));
+        assert_eq!(gradient.sample(0.5), RGBA8::new(100, 100, 100, 155));
+        assert_eq!(gradient.colors().w, Vector4::new(200.0, 100.0, 0.0, 55.0) / 255.0);
+    }
+
+    #[test]
+    fn particle_moves_until_dead() {
+        let mut particle = Particle {
+            pos: Vector2::zero(),
+            velocity: Vector2::new(10.0, 0.0),
+            age: 0.0,
+            lifetime: 1.0,
+        };
+        particle.step(Vector2::new(0.0, -10.0), 0.0, 0.5);
+        assert_eq!(particle.velocity, Vector2::new(10.0, -5.0));
+        assert_eq!(particle.pos, Vector2::new(5.0, -2.5));
+        particle.step(Vector2::new(0.0, -10.0), 0.0, 0.75);
+        assert_eq!(particle.age, 1.0);
+        let pos = particle.pos;
+        particle.step(Vector2::new(0.0, -10.0), 0.0, 0.5);
+        assert_eq!(particle.pos, pos);
+    }
+}
@@ -0,0 +1,7 @@
+mod data;
+mod shader;
+
+pub use self::data::{Curve, EmitterSettings, Gradient, MAX_CURVE_KEYS};
+pub use self::shader::{ParticleEmitter, ParticleShader, ParticleUniform};
+
+use self::data::Particle;
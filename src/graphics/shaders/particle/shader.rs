@@ -0,0 +1,349 @@
+use crate::graphics::{
+    default_texture, set_render_state,
+    shaders::particle::{EmitterSettings, Particle},
+    AsStd140, BlendMode, Buffer, BufferUsageHint, DrawMode, RenderState, Shader, ShaderDescriptor,
+    ShaderError, Texture, Uniform,
+};
+use crate::math::{cos_rad, sin_rad};
+use alloc::vec;
+use alloc::vec::Vec;
+use cgmath::*;
+use core::sync::atomic::{AtomicU32, Ordering};
+use log::warn;
+
+/// Gives each emitter a different random sequence.
+static NEXT_SEED: AtomicU32 = AtomicU32::new(0x2545_f491);
+
+impl ShaderDescriptor<1> for DrawProgram {
+    const VERTEX_SHADER: &'static str = include_str!("vertex.glsl");
+    const FRAGMENT_SHADER: &'static str = include_str!("fragment.glsl");
+    const TEXTURE_NAMES: [&'static str; 1] = ["tex"];
+    const VERTEX_UNIFORM_NAME: &'static str = "vertex";
+    type VertexUniformType = ParticleUniform;
+    type VertexDescriptor = Particle;
+}
+
+impl ShaderDescriptor<0> for UpdateProgram {
+    const VERTEX_SHADER: &'static str = include_str!("update_vertex.glsl");
+    const FRAGMENT_SHADER: &'static str = include_str!("update_fragment.glsl");
+    const TEXTURE_NAMES: [&'static str; 0] = [];
+    const VERTEX_UNIFORM_NAME: &'static str = "update";
+    type VertexUniformType = UpdateUniform;
+    type VertexDescriptor = Particle;
+    const FEEDBACK_VARYINGS: &'static [&'static str] = &["f_pos", "f_velocity", "f_life"];
+}
+
+#[derive(AsStd140)]
+pub struct ParticleUniform {
+    pub ortho: Matrix4<f32>,
+    /// The particle texture, normalized to [0, 1].
+    pub uv: Vector4<f32>,
+    pub size_times: Vector4<f32>,
+    pub sizes: Vector4<f32>,
+    pub color_times: Vector4<f32>,
+    /// Each column is a color key.
+    pub colors: Matrix4<f32>,
+}
+
+impl ParticleUniform {
+    pub fn new(ortho: Matrix4<f32>, settings: &EmitterSettings) -> ParticleUniform {
+        ParticleUniform {
+            ortho,
+            uv: settings.texture.0.map(|x| x as f32 / 65535.0),
+            size_times: settings.size.times(),
+            sizes: settings.size.values(),
+            color_times: settings.color.times(),
+            colors: settings.color.colors(),
+        }
+    }
+}
+
+#[derive(AsStd140)]
+struct UpdateUniform {
+    gravity: Vector2<f32>,
+    delta: f32,
+    drag: f32,
+}
+
+struct DrawProgram;
+struct UpdateProgram;
+
+/// Draws particle emitters, and simulates them on the GPU with transform feedback when the device
+/// supports it.
+pub struct ParticleShader {
+    shader: Shader<DrawProgram, 1>,
+    update: Option<Shader<UpdateProgram, 0>>,
+}
+
+impl ParticleShader {
+    /// Creates a shader that simulates on the GPU, falling back to the CPU if the update program
+    /// can't be built on this device.
+    pub fn new() -> ParticleShader {
+        let update = match Shader::try_new() {
+            Ok(update) => Some(update),
+            Err(error) => {
+                warn!("Simulating particles on the CPU, the update program failed to build: {}", error);
+                None
+            }
+        };
+        ParticleShader {
+            shader: Shader::new(),
+            update,
+        }
+    }
+
+    /// Creates a shader that always simulates on the CPU.
+    pub fn with_cpu_simulation() -> ParticleShader {
+        ParticleShader {
+            shader: Shader::new(),
+            update: None,
+        }
+    }
+
+    /// Returns true if emitters created with this shader simulate on the GPU.
+    pub fn gpu_simulation(&self) -> bool {
+        self.update.is_some()
+    }
+
+    /// Recompiles the draw program, see [`crate::graphics::Shader::reload`].
+    pub fn reload(&mut self, vertex_shader: &str, fragment_shader: &str) -> Result<(), ShaderError> {
+        self.shader.reload(vertex_shader, fragment_shader)
+    }
+
+    fn draw(&self, uniform: &Uniform<ParticleUniform>, atlas: &Texture, buffer: &Buffer<Particle>) {
+        self.shader.draw_instanced(DrawMode::TriangleStrip, uniform, [atlas], buffer, 4);
+    }
+}
+
+/// Where an emitter's particles are simulated.
+enum Simulation {
+    /// The particles live on the device, and are updated from one buffer into the other.
+    Gpu {
+        next: Buffer<Particle>,
+    },
+    /// The particles are updated on the CPU and uploaded before drawing.
+    Cpu {
+        particles: Vec<Particle>,
+        dirty: bool,
+    },
+}
+
+/// Spawns, simulates and draws a fixed number of particles. When every slot is in use, new particles
+/// replace the oldest ones.
+pub struct ParticleEmitter {
+    /// How particles are spawned and drawn. Changes apply to particles that are already alive.
+    pub settings: EmitterSettings,
+    /// Where new particles spawn.
+    pub pos: Vector2<f32>,
+    /// Particles are spawned at the settings' rate while this is true. The default is true.
+    pub emitting: bool,
+    pub atlas: Texture,
+    ortho: Matrix4<f32>,
+    render_state: RenderState,
+    uniform: Uniform<ParticleUniform>,
+    update_uniform: Uniform<UpdateUniform>,
+    buffer: Buffer<Particle>,
+    simulation: Simulation,
+    capacity: usize,
+    cursor: usize,
+    pending: f32,
+    seed: u32,
+}
+
+impl ParticleEmitter {
+    /// Creates an emitter with room for the given number of particles. The emitter simulates on the
+    /// GPU if the shader supports it.
+    pub fn new(
+        shader: &ParticleShader,
+        settings: EmitterSettings,
+        capacity: usize,
+        ortho: Matrix4<f32>,
+    ) -> ParticleEmitter {
+        let dead = vec![Particle::DEAD; capacity];
+        let (buffer, simulation) = if shader.gpu_simulation() {
+            let mut buffer = Buffer::with_usage(BufferUsageHint::Dynamic);
+            buffer.set(&dead);
+            let mut next = Buffer::with_usage(BufferUsageHint::Dynamic);
+            next.set(&dead);
+            (
+                buffer,
+                Simulation::Gpu {
+                    next,
+                },
+            )
+        } else {
+            let mut buffer = Buffer::with_usage(BufferUsageHint::Stream);
+            buffer.set(&dead);
+            (
+                buffer,
+                Simulation::Cpu {
+                    particles: dead,
+                    dirty: false,
+                },
+            )
+        };
+        ParticleEmitter {
+            uniform: Uniform::new(ParticleUniform::new(ortho, &settings)),
+            settings,
+            pos: Vector2::zero(),
+            emitting: true,
+            atlas: default_texture(),
+            render_state: RenderState::blended(BlendMode::Alpha),
+            ortho,
+            update_uniform: Uniform::new(UpdateUniform {
+                gravity: Vector2::zero(),
+                delta: 0.0,
+                drag: 0.0,
+            }),
+            buffer,
+            simulation,
+            capacity,
+            cursor: 0,
+            pending: 0.0,
+            seed: NEXT_SEED.fetch_add(0x9e37_79b9, Ordering::Relaxed) | 1,
+        }
+    }
+
+    /// The most particles the emitter can have alive at once.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets the orthographic projection used to draw the particles. If none is passed, this
+    /// function does nothing.
+    pub fn set_ortho(&mut self, ortho: Option<Matrix4<f32>>) {
+        if let Some(ortho) = ortho {
+            self.ortho = ortho;
+        }
+    }
+
+    /// Sets the render state applied when drawing the particles. The default is alpha blending.
+    pub fn set_render_state(&mut self, state: RenderState) {
+        self.render_state = state;
+    }
+
+    /// Advances every particle by the delta in seconds, then spawns new particles if the emitter is
+    /// emitting.
+    pub fn update(&mut self, shader: &ParticleShader, delta: f32) {
+        let gravity = self.settings.gravity;
+        let drag = self.settings.drag;
+        match &mut self.simulation {
+            Simulation::Gpu {
+                next,
+            } => {
+                // Emitters are only created on the GPU path when the shader has an update program.
+                if let Some(update) = &shader.update {
+                    self.update_uniform.set(UpdateUniform {
+                        gravity,
+                        delta,
+                        drag,
+                    });
+                    update.feedback(&self.update_uniform, [], &self.buffer, next);
+                    core::mem::swap(&mut self.buffer, next);
+                }
+            }
+            Simulation::Cpu {
+                particles,
+                dirty,
+            } => {
+                for particle in particles.iter_mut() {
+                    particle.step(gravity, drag, delta);
+                }
+                *dirty = true;
+            }
+        }
+
+        if self.emitting {
+            self.pending += self.settings.rate.max(0.0) * delta;
+            let count = self.pending.floor();
+            self.pending -= count;
+            self.burst(count as usize);
+        }
+    }
+
+    /// Spawns the given number of particles at once.
+    pub fn burst(&mut self, count: usize) {
+        let count = count.min(self.capacity);
+        if count == 0 {
+            return;
+        }
+        let spawned: Vec<Particle> = (0..count).map(|_| self.spawn()).collect();
+        // New particles are written into the ring of slots after the last spawned particle,
+        // wrapping to the start.
+        let start = self.cursor;
+        let split = (self.capacity - start).min(count);
+        self.write(start, &spawned[..split]);
+        self.write(0, &spawned[split..]);
+        self.cursor = (start + count) % self.capacity;
+    }
+
+    /// Removes every particle.
+    pub fn clear(&mut self) {
+        let dead = vec![Particle::DEAD; self.capacity];
+        self.write(0, &dead);
+        self.cursor = 0;
+        self.pending = 0.0;
+    }
+
+    /// Draws the particles to the screen.
+    pub fn draw(&mut self, shader: &ParticleShader) {
+        if let Simulation::Cpu {
+            particles,
+            dirty,
+        } = &mut self.simulation
+        {
+            if *dirty {
+                self.buffer.set(particles);
+                *dirty = false;
+            }
+        }
+        self.uniform.set(ParticleUniform::new(self.ortho, &self.settings));
+        set_render_state(&self.render_state);
+        shader.draw(&self.uniform, &self.atlas, &self.buffer);
+    }
+
+    fn write(&mut self, start: usize, items: &[Particle]) {
+        if items.is_empty() {
+            return;
+        }
+        match &mut self.simulation {
+            Simulation::Gpu {
+                ..
+            } => self.buffer.update(start..start + items.len(), items),
+            Simulation::Cpu {
+                particles,
+                dirty,
+            } => {
+                particles[start..start + items.len()].copy_from_slice(items);
+                *dirty = true;
+            }
+        }
+    }
+
+    fn spawn(&mut self) -> Particle {
+        let settings = self.settings;
+        let angle = settings.direction + (self.random() - 0.5) * settings.spread;
+        let angle = angle * core::f32::consts::PI * 2.0;
+        let speed = self.random_between(settings.speed);
+        let lifetime = self.random_between(settings.lifetime).max(0.0);
+        Particle {
+            pos: self.pos,
+            velocity: Vector2::new(cos_rad(angle), sin_rad(angle)) * speed,
+            age: 0.0,
+            lifetime,
+        }
+    }
+
+    fn random_between(&mut self, range: (f32, f32)) -> f32 {
+        range.0 + (range.1 - range.0) * self.random()
+    }
+
+    /// A random value in [0, 1).
+    fn random(&mut self) -> f32 {
+        // Xorshift32.
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        (self.seed >> 8) as f32 / (1 << 24) as f32
+    }
+}
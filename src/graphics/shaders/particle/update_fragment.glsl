@@ -0,0 +1,8 @@
+precision mediump float;
+
+out vec4 a_color;
+
+// Nothing is drawn while updating, but a fragment shader is still required to link.
+void main() {
+    a_color = vec4(0.0);
+}
\ No newline at end of file
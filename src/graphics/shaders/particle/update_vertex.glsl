@@ -0,0 +1,30 @@
+precision highp float;
+
+layout(location = 0) in vec2 a_pos;
+layout(location = 1) in vec2 a_velocity;
+layout(location = 2) in vec2 a_life;
+
+out vec2 f_pos;
+out vec2 f_velocity;
+out vec2 f_life;
+
+layout(std140) uniform update {
+    vec2 gravity;
+    float delta;
+    float drag;
+};
+
+void main() {
+    vec2 velocity = a_velocity;
+    vec2 pos = a_pos;
+    // Dead particles stay in place until they're replaced by a new particle.
+    if (a_life.x < a_life.y) {
+        velocity += gravity * delta;
+        velocity *= max(1.0 - drag * delta, 0.0);
+        pos += velocity * delta;
+    }
+    f_pos = pos;
+    f_velocity = velocity;
+    f_life = vec2(min(a_life.x + delta, a_life.y), a_life.y);
+    gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+}
\ No newline at end of file
@@ -0,0 +1,70 @@
+precision highp float;
+
+layout(location = 0) in vec2 a_pos;
+layout(location = 1) in vec2 a_velocity;
+layout(location = 2) in vec2 a_life;
+
+out vec2 v_uv;
+out vec4 v_color;
+
+layout(std140) uniform vertex {
+    mat4 ortho;
+    // UV Layout: xmin xmax ymin ymax
+    vec4 uv;
+    vec4 size_times;
+    vec4 sizes;
+    vec4 color_times;
+    mat4 colors;
+};
+
+// ymin and ymax are swapped below because OpenGL reads images from bottom row to top row, but
+// they're stored top to bottom on upload, so this corrects that.
+vec4 uv_lut[4] = vec4[4](
+    vec4(1.0, 0.0, 1.0, 0.0),  // left bottom
+    vec4(1.0, 0.0, 0.0, 1.0),  // left top
+    vec4(0.0, 1.0, 1.0, 0.0),  // right bottom
+    vec4(0.0, 1.0, 0.0, 1.0)); // right top
+
+vec2 corner_lut[4] = vec2[4](
+    vec2(-0.5, 0.5),   // left top
+    vec2(-0.5, -0.5),  // left bottom
+    vec2(0.5, 0.5),    // right top
+    vec2(0.5, -0.5));  // right bottom
+
+// Finds the keys around t and how far t is between them, matching Curve::sample. Unused keys are
+// copies of the last key.
+vec3 segment(vec4 times, float t) {
+    if (t <= times[0]) {
+        return vec3(0.0, 0.0, 0.0);
+    }
+    for (int i = 1; i < 4; i++) {
+        if (t <= times[i]) {
+            float span = times[i] - times[i - 1];
+            float amount = span > 0.0 ? (t - times[i - 1]) / span : 1.0;
+            return vec3(float(i - 1), float(i), amount);
+        }
+    }
+    return vec3(3.0, 3.0, 0.0);
+}
+
+void main() {
+    // Dead particles collapse to a point, which draws nothing.
+    if (a_life.x >= a_life.y) {
+        v_uv = vec2(0.0);
+        v_color = vec4(0.0);
+        gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+        return;
+    }
+    float t = a_life.x / a_life.y;
+
+    vec3 size_key = segment(size_times, t);
+    float size = mix(sizes[int(size_key.x)], sizes[int(size_key.y)], size_key.z);
+    vec3 color_key = segment(color_times, t);
+    v_color = mix(colors[int(color_key.x)], colors[int(color_key.y)], color_key.z);
+
+    vec4 temp = uv * uv_lut[gl_VertexID];
+    v_uv = vec2(temp.x + temp.y, temp.z + temp.w);
+
+    vec2 pos = a_pos + corner_lut[gl_VertexID] * size;
+    gl_Position = ortho * vec4(pos, 0.0, 1.0);
+}
\ No newline at end of file
@@ -0,0 +1,149 @@
+use crate::graphics::{
+    set_render_target,
+    shaders::post_process::{
+        fit_target, BlurShader, BlurUniform, PostContext, PostEffect, PostVertex, POST_VERTEX_SHADER,
+    },
+    AsStd140, Buffer, DrawMode, RenderTarget, Shader, ShaderDescriptor, Texture, Uniform,
+};
+use cgmath::*;
+
+impl ShaderDescriptor<1> for BloomExtractShader {
+    const VERTEX_SHADER: &'static str = POST_VERTEX_SHADER;
+    const FRAGMENT_SHADER: &'static str = include_str!("bloom_extract.glsl");
+    const TEXTURE_NAMES: [&'static str; 1] = ["tex"];
+    const VERTEX_UNIFORM_NAME: &'static str = "fragment";
+    type VertexUniformType = BloomExtractUniform;
+    type VertexDescriptor = PostVertex;
+}
+
+impl ShaderDescriptor<2> for BloomCompositeShader {
+    const VERTEX_SHADER: &'static str = POST_VERTEX_SHADER;
+    const FRAGMENT_SHADER: &'static str = include_str!("bloom_composite.glsl");
+    const TEXTURE_NAMES: [&'static str; 2] = ["tex", "bloom"];
+    const VERTEX_UNIFORM_NAME: &'static str = "fragment";
+    type VertexUniformType = BloomCompositeUniform;
+    type VertexDescriptor = PostVertex;
+}
+
+#[derive(AsStd140)]
+pub struct BloomExtractUniform {
+    pub threshold: f32,
+    /// How far below the threshold brightness starts to ease in.
+    pub knee: f32,
+}
+
+#[derive(AsStd140)]
+pub struct BloomCompositeUniform {
+    pub intensity: f32,
+}
+
+pub struct BloomExtractShader {
+    shader: Shader<BloomExtractShader, 1>,
+}
+
+impl BloomExtractShader {
+    pub fn new() -> BloomExtractShader {
+        BloomExtractShader {
+            shader: Shader::new(),
+        }
+    }
+
+    /// Draws the parts of the input brighter than the threshold.
+    pub fn draw(&self, uniform: &Uniform<BloomExtractUniform>, input: &Texture, quad: &Buffer<PostVertex>) {
+        self.shader.draw(DrawMode::Triangles, uniform, [input], quad);
+    }
+}
+
+pub struct BloomCompositeShader {
+    shader: Shader<BloomCompositeShader, 2>,
+}
+
+impl BloomCompositeShader {
+    pub fn new() -> BloomCompositeShader {
+        BloomCompositeShader {
+            shader: Shader::new(),
+        }
+    }
+
+    /// Draws the input with the bloom added on top.
+    pub fn draw(
+        &self,
+        uniform: &Uniform<BloomCompositeUniform>,
+        input: &Texture,
+        bloom: &Texture,
+        quad: &Buffer<PostVertex>,
+    ) {
+        self.shader.draw(DrawMode::Triangles, uniform, [input, bloom], quad);
+    }
+}
+
+/// Makes bright areas glow. Areas brighter than the threshold are extracted at half resolution,
+/// blurred, then added back on top of the input.
+pub struct BloomEffect {
+    /// The brightness areas need to reach to glow, from 0.0 to 1.0, measured on their brightest
+    /// channel.
+    pub threshold: f32,
+    /// How strongly the glow is added to the input.
+    pub intensity: f32,
+    /// The radius of the blur spreading the glow, in half resolution pixels.
+    pub radius: f32,
+    extract: BloomExtractShader,
+    blur: BlurShader,
+    composite: BloomCompositeShader,
+    extract_uniform: Uniform<BloomExtractUniform>,
+    horizontal: Uniform<BlurUniform>,
+    vertical: Uniform<BlurUniform>,
+    composite_uniform: Uniform<BloomCompositeUniform>,
+    targets: [Option<RenderTarget>; 2],
+}
+
+impl BloomEffect {
+    /// Creates a new bloom with a threshold of 0.8, an intensity of 1.0, and a radius of 6.0.
+    pub fn new() -> BloomEffect {
+        BloomEffect {
+            threshold: 0.8,
+            intensity: 1.0,
+            radius: 6.0,
+            extract: BloomExtractShader::new(),
+            blur: BlurShader::new(),
+            composite: BloomCompositeShader::new(),
+            extract_uniform: Uniform::new(BloomExtractUniform {
+                threshold: 1.0,
+                knee: 0.0,
+            }),
+            horizontal: Uniform::new(BlurUniform::new(0.0, true, Vector2::new(1, 1))),
+            vertical: Uniform::new(BlurUniform::new(0.0, false, Vector2::new(1, 1))),
+            composite_uniform: Uniform::new(BloomCompositeUniform {
+                intensity: 0.0,
+            }),
+            targets: [None, None],
+        }
+    }
+}
+
+impl PostEffect for BloomEffect {
+    fn apply(&mut self, context: &PostContext, input: &Texture) {
+        let size = context.size() / 2;
+        self.extract_uniform.set(BloomExtractUniform {
+            threshold: self.threshold,
+            knee: self.threshold * 0.5,
+        });
+        self.horizontal.set(BlurUniform::new(self.radius, true, size));
+        self.vertical.set(BlurUniform::new(self.radius, false, size));
+        self.composite_uniform.set(BloomCompositeUniform {
+            intensity: self.intensity,
+        });
+
+        let [first, second] = &mut self.targets;
+        let first = fit_target(first, size);
+        let second = fit_target(second, size);
+        set_render_target(Some(first));
+        self.extract.draw(&self.extract_uniform, input, context.quad());
+        set_render_target(Some(second));
+        self.blur.draw(&self.horizontal, first.texture(), context.quad());
+        set_render_target(Some(first));
+        self.blur.draw(&self.vertical, second.texture(), context.quad());
+        context.bind_output();
+        self.composite.draw(&self.composite_uniform, input, first.texture(), context.quad());
+    }
+}
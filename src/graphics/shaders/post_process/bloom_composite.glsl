@@ -0,0 +1,16 @@
+precision mediump float;
+
+in vec2 v_uv;
+out vec4 a_color;
+
+uniform sampler2D tex;
+uniform sampler2D bloom;
+
+layout(std140) uniform fragment {
+    float intensity;
+};
+
+void main() {
+    vec4 color = texture(tex, v_uv);
+    a_color = vec4(color.rgb + texture(bloom, v_uv).rgb * intensity, color.a);
+}
\ No newline at end of file
@@ -0,0 +1,21 @@
+precision mediump float;
+
+in vec2 v_uv;
+out vec4 a_color;
+
+uniform sampler2D tex;
+
+layout(std140) uniform fragment {
+    float threshold;
+    float knee;
+};
+
+void main() {
+    vec3 color = texture(tex, v_uv).rgb;
+    float brightness = max(color.r, max(color.g, color.b));
+    // Soft threshold, easing in over the knee below the threshold.
+    float soft = clamp(brightness - threshold + knee, 0.0, 2.0 * knee);
+    soft = soft * soft / (4.0 * knee + 0.0001);
+    float contribution = max(soft, brightness - threshold) / max(brightness, 0.0001);
+    a_color = vec4(color * contribution, 1.0);
+}
\ No newline at end of file
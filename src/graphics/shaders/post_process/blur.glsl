@@ -0,0 +1,23 @@
+precision mediump float;
+
+in vec2 v_uv;
+out vec4 a_color;
+
+uniform sampler2D tex;
+
+layout(std140) uniform fragment {
+    // The distance between taps, in texture coordinates.
+    vec2 direction;
+};
+
+const float WEIGHTS[5] = float[](0.227027, 0.1945946, 0.1216216, 0.054054, 0.016216);
+
+void main() {
+    vec4 sum = texture(tex, v_uv) * WEIGHTS[0];
+    for (int i = 1; i < 5; i++) {
+        vec2 offset = direction * float(i);
+        sum += texture(tex, v_uv + offset) * WEIGHTS[i];
+        sum += texture(tex, v_uv - offset) * WEIGHTS[i];
+    }
+    a_color = sum;
+}
\ No newline at end of file
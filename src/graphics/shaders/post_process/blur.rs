@@ -0,0 +1,92 @@
+use crate::graphics::{
+    set_render_target,
+    shaders::post_process::{fit_target, PostContext, PostEffect, PostVertex, POST_VERTEX_SHADER},
+    AsStd140, Buffer, DrawMode, RenderTarget, Shader, ShaderDescriptor, Texture, Uniform,
+};
+use cgmath::*;
+
+impl ShaderDescriptor<1> for BlurShader {
+    const VERTEX_SHADER: &'static str = POST_VERTEX_SHADER;
+    const FRAGMENT_SHADER: &'static str = include_str!("blur.glsl");
+    const TEXTURE_NAMES: [&'static str; 1] = ["tex"];
+    const VERTEX_UNIFORM_NAME: &'static str = "fragment";
+    type VertexUniformType = BlurUniform;
+    type VertexDescriptor = PostVertex;
+}
+
+#[derive(AsStd140)]
+pub struct BlurUniform {
+    /// The distance between taps, in texture coordinates. The blur samples 4 taps on each side.
+    pub direction: Vector2<f32>,
+}
+
+impl BlurUniform {
+    /// Creates a uniform blurring along one axis of a texture of the given size. The radius is the
+    /// distance to the furthest tap, in texels.
+    pub fn new(radius: f32, horizontal: bool, size: Vector2<u32>) -> BlurUniform {
+        let step = radius / 4.0;
+        let size = size.map(|x| x.max(1) as f32);
+        BlurUniform {
+            direction: if horizontal {
+                Vector2::new(step / size.x, 0.0)
+            } else {
+                Vector2::new(0.0, step / size.y)
+            },
+        }
+    }
+}
+
+pub struct BlurShader {
+    shader: Shader<BlurShader, 1>,
+}
+
+impl BlurShader {
+    pub fn new() -> BlurShader {
+        BlurShader {
+            shader: Shader::new(),
+        }
+    }
+
+    /// Draws the input blurred along the direction in the uniform.
+    pub fn draw(&self, uniform: &Uniform<BlurUniform>, input: &Texture, quad: &Buffer<PostVertex>) {
+        self.shader.draw(DrawMode::Triangles, uniform, [input], quad);
+    }
+}
+
+/// A gaussian blur, drawn as a horizontal pass into an intermediate render target followed by a
+/// vertical pass into the output.
+pub struct BlurEffect {
+    /// The distance to the furthest sample on each side, in physical pixels. Radii much larger than
+    /// 8 leave gaps between samples.
+    pub radius: f32,
+    shader: BlurShader,
+    horizontal: Uniform<BlurUniform>,
+    vertical: Uniform<BlurUniform>,
+    target: Option<RenderTarget>,
+}
+
+impl BlurEffect {
+    /// Creates a new blur with the given radius, in physical pixels.
+    pub fn new(radius: f32) -> BlurEffect {
+        BlurEffect {
+            radius,
+            shader: BlurShader::new(),
+            horizontal: Uniform::new(BlurUniform::new(0.0, true, Vector2::new(1, 1))),
+            vertical: Uniform::new(BlurUniform::new(0.0, false, Vector2::new(1, 1))),
+            target: None,
+        }
+    }
+}
+
+impl PostEffect for BlurEffect {
+    fn apply(&mut self, context: &PostContext, input: &Texture) {
+        let size = context.size();
+        self.horizontal.set(BlurUniform::new(self.radius, true, size));
+        self.vertical.set(BlurUniform::new(self.radius, false, size));
+        let target = fit_target(&mut self.target, size);
+        set_render_target(Some(target));
+        self.shader.draw(&self.horizontal, input, context.quad());
+        context.bind_output();
+        self.shader.draw(&self.vertical, target.texture(), context.quad());
+    }
+}
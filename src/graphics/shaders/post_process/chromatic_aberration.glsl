@@ -0,0 +1,19 @@
+precision mediump float;
+
+in vec2 v_uv;
+out vec4 a_color;
+
+uniform sampler2D tex;
+
+layout(std140) uniform fragment {
+    // The offset of the red and blue channels at the right and top edges, in texture coordinates.
+    vec2 offset;
+};
+
+void main() {
+    vec2 direction = (v_uv - 0.5) * 2.0;
+    vec4 color = texture(tex, v_uv);
+    color.r = texture(tex, v_uv + direction * offset).r;
+    color.b = texture(tex, v_uv - direction * offset).b;
+    a_color = color;
+}
\ No newline at end of file
@@ -0,0 +1,74 @@
+use crate::graphics::{
+    shaders::post_process::{PostContext, PostEffect, PostVertex, POST_VERTEX_SHADER},
+    AsStd140, Buffer, DrawMode, Shader, ShaderDescriptor, Texture, Uniform,
+};
+use cgmath::*;
+
+impl ShaderDescriptor<1> for ChromaticAberrationShader {
+    const VERTEX_SHADER: &'static str = POST_VERTEX_SHADER;
+    const FRAGMENT_SHADER: &'static str = include_str!("chromatic_aberration.glsl");
+    const TEXTURE_NAMES: [&'static str; 1] = ["tex"];
+    const VERTEX_UNIFORM_NAME: &'static str = "fragment";
+    type VertexUniformType = ChromaticAberrationUniform;
+    type VertexDescriptor = PostVertex;
+}
+
+#[derive(AsStd140)]
+pub struct ChromaticAberrationUniform {
+    /// The offset of the red and blue channels at the right and top edges, in texture coordinates.
+    pub offset: Vector2<f32>,
+}
+
+pub struct ChromaticAberrationShader {
+    shader: Shader<ChromaticAberrationShader, 1>,
+}
+
+impl ChromaticAberrationShader {
+    pub fn new() -> ChromaticAberrationShader {
+        ChromaticAberrationShader {
+            shader: Shader::new(),
+        }
+    }
+
+    /// Draws the input with its red and blue channels offset.
+    pub fn draw(
+        &self,
+        uniform: &Uniform<ChromaticAberrationUniform>,
+        input: &Texture,
+        quad: &Buffer<PostVertex>,
+    ) {
+        self.shader.draw(DrawMode::Triangles, uniform, [input], quad);
+    }
+}
+
+/// Splits the red and blue channels apart towards the edges of the screen, like a cheap lens.
+pub struct ChromaticAberrationEffect {
+    /// How far the red and blue channels are offset at the edges of the screen, in physical pixels.
+    /// The offset shrinks to nothing at the center.
+    pub amount: f32,
+    shader: ChromaticAberrationShader,
+    uniform: Uniform<ChromaticAberrationUniform>,
+}
+
+impl ChromaticAberrationEffect {
+    /// Creates a new effect with the given amount, in physical pixels.
+    pub fn new(amount: f32) -> ChromaticAberrationEffect {
+        ChromaticAberrationEffect {
+            amount,
+            shader: ChromaticAberrationShader::new(),
+            uniform: Uniform::new(ChromaticAberrationUniform {
+                offset: Vector2::zero(),
+            }),
+        }
+    }
+}
+
+impl PostEffect for ChromaticAberrationEffect {
+    fn apply(&mut self, context: &PostContext, input: &Texture) {
+        let size = context.size().map(|x| x.max(1) as f32);
+        self.uniform.set(ChromaticAberrationUniform {
+            offset: Vector2::new(self.amount / size.x, self.amount / size.y),
+        });
+        self.shader.draw(&self.uniform, input, context.quad());
+    }
+}
@@ -0,0 +1,27 @@
+precision mediump float;
+
+in vec2 v_uv;
+out vec4 a_color;
+
+uniform sampler2D tex;
+uniform sampler2D lut;
+
+layout(std140) uniform fragment {
+    // The number of slices in the LUT, which is also the width and height of each slice.
+    float lut_size;
+    float intensity;
+};
+
+void main() {
+    vec4 color = texture(tex, v_uv);
+    vec3 c = clamp(color.rgb, 0.0, 1.0);
+    float n = lut_size;
+    float blue = c.b * (n - 1.0);
+    float slice0 = floor(blue);
+    float slice1 = min(slice0 + 1.0, n - 1.0);
+    vec2 uv = vec2((c.r * (n - 1.0) + 0.5) / (n * n), (c.g * (n - 1.0) + 0.5) / n);
+    vec3 graded0 = texture(lut, uv + vec2(slice0 / n, 0.0)).rgb;
+    vec3 graded1 = texture(lut, uv + vec2(slice1 / n, 0.0)).rgb;
+    vec3 graded = mix(graded0, graded1, blue - slice0);
+    a_color = vec4(mix(color.rgb, graded, intensity), color.a);
+}
\ No newline at end of file
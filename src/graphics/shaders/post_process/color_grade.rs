@@ -0,0 +1,132 @@
+use crate::color::RGBA8;
+use crate::graphics::{
+    shaders::post_process::{PostContext, PostEffect, PostVertex, POST_VERTEX_SHADER},
+    AsStd140, Buffer, DrawMode, Shader, ShaderDescriptor, Texture, TextureFilter, Uniform,
+};
+use crate::image::Image;
+use alloc::vec::Vec;
+
+impl ShaderDescriptor<2> for ColorGradeShader {
+    const VERTEX_SHADER: &'static str = POST_VERTEX_SHADER;
+    const FRAGMENT_SHADER: &'static str = include_str!("color_grade.glsl");
+    const TEXTURE_NAMES: [&'static str; 2] = ["tex", "lut"];
+    const VERTEX_UNIFORM_NAME: &'static str = "fragment";
+    type VertexUniformType = ColorGradeUniform;
+    type VertexDescriptor = PostVertex;
+}
+
+#[derive(AsStd140)]
+pub struct ColorGradeUniform {
+    /// The number of slices in the LUT, which is also the width and height of each slice.
+    pub lut_size: f32,
+    pub intensity: f32,
+}
+
+pub struct ColorGradeShader {
+    shader: Shader<ColorGradeShader, 2>,
+}
+
+impl ColorGradeShader {
+    pub fn new() -> ColorGradeShader {
+        ColorGradeShader {
+            shader: Shader::new(),
+        }
+    }
+
+    /// Draws the input with its colors remapped through the LUT.
+    pub fn draw(
+        &self,
+        uniform: &Uniform<ColorGradeUniform>,
+        input: &Texture,
+        lut: &Texture,
+        quad: &Buffer<PostVertex>,
+    ) {
+        self.shader.draw(DrawMode::Triangles, uniform, [input, lut], quad);
+    }
+}
+
+/// Remaps colors through a lookup table (LUT), the usual way to apply a grade authored in an image
+/// editor. Colors between entries in the table are interpolated.
+///
+/// The LUT is a strip of N slices laid out left to right, where each slice is N by N pixels, so the
+/// texture is N * N pixels wide and N pixels tall. Blue increases from slice to slice, red increases
+/// left to right within a slice, and green increases top to bottom. To author one, grade
+/// [`ColorGradeEffect::neutral_lut`] in an image editor alongside a screenshot.
+pub struct ColorGradeEffect {
+    /// How much of the grade is applied, from 0.0 to 1.0.
+    pub intensity: f32,
+    lut: Texture,
+    shader: ColorGradeShader,
+    uniform: Uniform<ColorGradeUniform>,
+}
+
+impl ColorGradeEffect {
+    /// Creates a new effect with the given LUT, fully applied. The LUT's filter is set to
+    /// [`TextureFilter::Linear`]. Panics if the LUT's width isn't the square of its height.
+    pub fn new(lut: Texture) -> ColorGradeEffect {
+        let size = lut.height();
+        assert_eq!(lut.width(), size * size, "A LUT's width must be the square of its height.");
+        lut.set_filter(TextureFilter::Linear);
+        ColorGradeEffect {
+            intensity: 1.0,
+            lut,
+            shader: ColorGradeShader::new(),
+            uniform: Uniform::new(ColorGradeUniform {
+                lut_size: size as f32,
+                intensity: 1.0,
+            }),
+        }
+    }
+
+    /// The LUT colors are remapped through.
+    pub fn lut(&self) -> &Texture {
+        &self.lut
+    }
+
+    /// Creates a LUT with the given number of slices that leaves colors unchanged. 16 and 32 are
+    /// common sizes.
+    pub fn neutral_lut(size: u32) -> Image<RGBA8> {
+        let max = size.max(2) - 1;
+        let level = |index: u32| ((index * 255 + max / 2) / max) as u8;
+        let mut pixels = Vec::with_capacity((size * size * size) as usize);
+        for green in 0..size {
+            for blue in 0..size {
+                for red in 0..size {
+                    pixels.push(RGBA8::new(level(red), level(green), level(blue), 255));
+                }
+            }
+        }
+        Image::from_vec(pixels, size * size, size)
+    }
+}
+
+impl PostEffect for ColorGradeEffect {
+    fn apply(&mut self, context: &PostContext, input: &Texture) {
+        self.uniform.set(ColorGradeUniform {
+            lut_size: self.lut.height() as f32,
+            intensity: self.intensity,
+        });
+        self.shader.draw(&self.uniform, input, &self.lut, context.quad());
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neutral_lut_layout() {
+        let lut = ColorGradeEffect::neutral_lut(4);
+        assert_eq!((lut.width(), lut.height()), (16, 4));
+        assert_eq!(lut.get(0, 0), RGBA8::new(0, 0, 0, 255));
+        // Red across the first slice, green down the rows, blue across slices.
+        assert_eq!(lut.get(3, 0), RGBA8::new(255, 0, 0, 255));
+        assert_eq!(lut.get(0, 3), RGBA8::new(0, 255, 0, 255));
+        assert_eq!(lut.get(12, 0), RGBA8::new(0, 0, 255, 255));
+        assert_eq!(lut.get(15, 3), RGBA8::new(255, 255, 255, 255));
+    }
+}
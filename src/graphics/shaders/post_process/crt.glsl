@@ -0,0 +1,34 @@
+precision mediump float;
+
+in vec2 v_uv;
+out vec4 a_color;
+
+uniform sampler2D tex;
+
+layout(std140) uniform fragment {
+    float curvature;
+    float scanline_intensity;
+    float scanline_count;
+    float mask_intensity;
+};
+
+const float PI = 3.14159265;
+
+void main() {
+    vec2 centered = v_uv * 2.0 - 1.0;
+    centered *= 1.0 + curvature * (centered.yx * centered.yx);
+    vec2 uv = centered * 0.5 + 0.5;
+    if (uv.x < 0.0 || uv.x > 1.0 || uv.y < 0.0 || uv.y > 1.0) {
+        a_color = vec4(0.0, 0.0, 0.0, 1.0);
+        return;
+    }
+    vec4 color = texture(tex, uv);
+    float scanline = sin(uv.y * scanline_count * 2.0 * PI) * 0.5 + 0.5;
+    color.rgb *= 1.0 - scanline_intensity * scanline;
+    // Aperture grille, dimming two of the three channels in each column of pixels.
+    int column = int(mod(gl_FragCoord.x, 3.0));
+    vec3 mask = vec3(1.0 - mask_intensity);
+    mask[column] = 1.0;
+    color.rgb *= mask;
+    a_color = color;
+}
\ No newline at end of file
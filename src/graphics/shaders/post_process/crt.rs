@@ -0,0 +1,86 @@
+use crate::graphics::{
+    shaders::post_process::{PostContext, PostEffect, PostVertex, POST_VERTEX_SHADER},
+    AsStd140, Buffer, DrawMode, Shader, ShaderDescriptor, Texture, Uniform,
+};
+
+impl ShaderDescriptor<1> for CrtShader {
+    const VERTEX_SHADER: &'static str = POST_VERTEX_SHADER;
+    const FRAGMENT_SHADER: &'static str = include_str!("crt.glsl");
+    const TEXTURE_NAMES: [&'static str; 1] = ["tex"];
+    const VERTEX_UNIFORM_NAME: &'static str = "fragment";
+    type VertexUniformType = CrtUniform;
+    type VertexDescriptor = PostVertex;
+}
+
+#[derive(AsStd140)]
+pub struct CrtUniform {
+    pub curvature: f32,
+    pub scanline_intensity: f32,
+    pub scanline_count: f32,
+    pub mask_intensity: f32,
+}
+
+pub struct CrtShader {
+    shader: Shader<CrtShader, 1>,
+}
+
+impl CrtShader {
+    pub fn new() -> CrtShader {
+        CrtShader {
+            shader: Shader::new(),
+        }
+    }
+
+    /// Draws the input as if on a CRT.
+    pub fn draw(&self, uniform: &Uniform<CrtUniform>, input: &Texture, quad: &Buffer<PostVertex>) {
+        self.shader.draw(DrawMode::Triangles, uniform, [input], quad);
+    }
+}
+
+/// Makes the screen look like a CRT, with a curved screen, scanlines, and an aperture grille.
+pub struct CrtEffect {
+    /// How much the screen bulges. 0.0 is flat. Areas curved off the screen are black.
+    pub curvature: f32,
+    /// How dark the gaps between scanlines are, from 0.0 to 1.0.
+    pub scanline_intensity: f32,
+    /// The number of scanlines from the bottom to the top of the screen. If None, there is one
+    /// scanline for every two physical pixels. Set this to the height of a virtual resolution to give
+    /// each virtual pixel its own scanline.
+    pub scanline_count: Option<f32>,
+    /// How dark the inactive channels of the aperture grille are, from 0.0 to 1.0.
+    pub mask_intensity: f32,
+    shader: CrtShader,
+    uniform: Uniform<CrtUniform>,
+}
+
+impl CrtEffect {
+    /// Creates a new effect with a curvature of 0.05, a scanline intensity of 0.3, and a mask
+    /// intensity of 0.2.
+    pub fn new() -> CrtEffect {
+        CrtEffect {
+            curvature: 0.05,
+            scanline_intensity: 0.3,
+            scanline_count: None,
+            mask_intensity: 0.2,
+            shader: CrtShader::new(),
+            uniform: Uniform::new(CrtUniform {
+                curvature: 0.0,
+                scanline_intensity: 0.0,
+                scanline_count: 0.0,
+                mask_intensity: 0.0,
+            }),
+        }
+    }
+}
+
+impl PostEffect for CrtEffect {
+    fn apply(&mut self, context: &PostContext, input: &Texture) {
+        self.uniform.set(CrtUniform {
+            curvature: self.curvature,
+            scanline_intensity: self.scanline_intensity,
+            scanline_count: self.scanline_count.unwrap_or(context.size().y as f32 * 0.5),
+            mask_intensity: self.mask_intensity,
+        });
+        self.shader.draw(&self.uniform, input, context.quad());
+    }
+}
@@ -0,0 +1,32 @@
+use crate::graphics::{VertexAttribute, VertexDescriptor, VertexInputType, VertexOutputType, VertexStepRate};
+use cgmath::*;
+
+/// A vertex of the triangle covering the screen in full-screen passes. Custom passes use this as
+/// their vertex descriptor.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PostVertex {
+    /// Position of the vertex in normalized device coordinates.
+    pub pos: Vector2<f32>,
+}
+
+impl VertexDescriptor for PostVertex {
+    const STEP_RATE: VertexStepRate = VertexStepRate::PerVertex;
+    const ATTRIBUTES: &'static [VertexAttribute] = &[
+        // Pos
+        VertexAttribute::new(2, VertexInputType::F32, VertexOutputType::F32),
+    ];
+}
+
+/// A single triangle that covers the whole screen.
+pub(crate) const FULLSCREEN_TRIANGLE: [PostVertex; 3] = [
+    PostVertex {
+        pos: Vector2::new(-1.0, -1.0),
+    },
+    PostVertex {
+        pos: Vector2::new(3.0, -1.0),
+    },
+    PostVertex {
+        pos: Vector2::new(-1.0, 3.0),
+    },
+];
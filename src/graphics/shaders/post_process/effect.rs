@@ -0,0 +1,98 @@
+use crate::color::RGBA8;
+use crate::graphics::{
+    set_render_target, shaders::post_process::PostVertex, Buffer, DrawMode, RenderTarget, Shader,
+    ShaderDescriptor, ShaderError, Texture, TextureFilter, Uniform,
+};
+use cgmath::*;
+
+/// The vertex shader shared by full-screen passes. It outputs `v_uv`, the texture coordinate of the
+/// fragment, with (0, 0) at the bottom left of the screen. Use this as the `VERTEX_SHADER` of custom
+/// passes.
+pub const POST_VERTEX_SHADER: &str = include_str!("vertex.glsl");
+
+/// A full-screen pass in a [`crate::graphics::shaders::post_process::PostProcessStack`].
+pub trait PostEffect {
+    /// Reads the input and draws the result with a full-screen draw. The output is bound, and blending
+    /// and depth testing are disabled before this is called. Effects that draw to their own render
+    /// targets first must call [`PostContext::bind_output`] before drawing the result.
+    fn apply(&mut self, context: &PostContext, input: &Texture);
+}
+
+/// What an effect needs to draw to its output.
+pub struct PostContext<'a> {
+    pub(super) output: Option<&'a RenderTarget>,
+    pub(super) size: Vector2<u32>,
+    pub(super) quad: &'a Buffer<PostVertex>,
+}
+
+impl<'a> PostContext<'a> {
+    /// Directs draws to the effect's output again.
+    pub fn bind_output(&self) {
+        set_render_target(self.output);
+    }
+
+    /// The size of the output in physical pixels. This is also the size of the input.
+    pub fn size(&self) -> Vector2<u32> {
+        self.size
+    }
+
+    /// A buffer with a single triangle covering the screen. Draw it with [`DrawMode::Triangles`].
+    pub fn quad(&self) -> &Buffer<PostVertex> {
+        self.quad
+    }
+}
+
+/// A full-screen pass with a shader written through [`ShaderDescriptor`]. The shader has one
+/// texture, which is the input, and its `VERTEX_SHADER` is usually [`POST_VERTEX_SHADER`]. Since the
+/// shared vertex shader has no uniform block, the block named by `VERTEX_UNIFORM_NAME` is declared
+/// in the fragment shader. Effects that need more textures or render targets implement
+/// [`PostEffect`] directly.
+pub struct CustomEffect<T: ShaderDescriptor<1, VertexDescriptor = PostVertex>> {
+    shader: Shader<T, 1>,
+    uniform: Uniform<T::VertexUniformType>,
+}
+
+impl<T: ShaderDescriptor<1, VertexDescriptor = PostVertex>> CustomEffect<T> {
+    /// Creates a new effect. Panics if the shader fails to compile.
+    pub fn new(uniform: T::VertexUniformType) -> CustomEffect<T> {
+        CustomEffect {
+            shader: Shader::new(),
+            uniform: Uniform::new(uniform),
+        }
+    }
+
+    /// Sets the uniform passed to the shader.
+    pub fn set_uniform(&mut self, uniform: T::VertexUniformType) {
+        self.uniform.set(uniform);
+    }
+
+    /// Recompiles the shader, see [`crate::graphics::Shader::reload`].
+    pub fn reload(&mut self, vertex_shader: &str, fragment_shader: &str) -> Result<(), ShaderError> {
+        self.shader.reload(vertex_shader, fragment_shader)
+    }
+}
+
+impl<T: ShaderDescriptor<1, VertexDescriptor = PostVertex>> PostEffect for CustomEffect<T> {
+    fn apply(&mut self, context: &PostContext, input: &Texture) {
+        self.shader.draw(DrawMode::Triangles, &self.uniform, [input], context.quad());
+    }
+}
+
+/// Converts a color into the vector form used by uniforms.
+pub(crate) fn color_vector(color: RGBA8) -> Vector4<f32> {
+    let (red, green, blue, alpha) = color.into();
+    Vector4::new(red, green, blue, alpha)
+}
+
+/// Gets the render target, first replacing it with a new one if it doesn't match the size.
+pub(crate) fn fit_target(target: &mut Option<RenderTarget>, size: Vector2<u32>) -> &RenderTarget {
+    let size = size.map(|x| x.max(1));
+    if target.as_ref().map(|target| (target.width(), target.height())) != Some((size.x, size.y)) {
+        // Release the old target before allocating its replacement.
+        *target = None;
+        let new = RenderTarget::new::<RGBA8>(size.x, size.y);
+        new.texture().set_filter(TextureFilter::Linear);
+        *target = Some(new);
+    }
+    target.as_ref().unwrap()
+}
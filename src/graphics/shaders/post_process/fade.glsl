@@ -0,0 +1,16 @@
+precision mediump float;
+
+in vec2 v_uv;
+out vec4 a_color;
+
+uniform sampler2D tex;
+
+layout(std140) uniform fragment {
+    vec4 color;
+    float amount;
+};
+
+void main() {
+    vec4 source = texture(tex, v_uv);
+    a_color = vec4(mix(source.rgb, color.rgb, amount * color.a), source.a);
+}
\ No newline at end of file
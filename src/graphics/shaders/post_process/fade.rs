@@ -0,0 +1,74 @@
+use crate::color::RGBA8;
+use crate::graphics::{
+    shaders::post_process::{color_vector, PostContext, PostEffect, PostVertex, POST_VERTEX_SHADER},
+    AsStd140, Buffer, DrawMode, Shader, ShaderDescriptor, Texture, Uniform,
+};
+use cgmath::*;
+
+impl ShaderDescriptor<1> for FadeShader {
+    const VERTEX_SHADER: &'static str = POST_VERTEX_SHADER;
+    const FRAGMENT_SHADER: &'static str = include_str!("fade.glsl");
+    const TEXTURE_NAMES: [&'static str; 1] = ["tex"];
+    const VERTEX_UNIFORM_NAME: &'static str = "fragment";
+    type VertexUniformType = FadeUniform;
+    type VertexDescriptor = PostVertex;
+}
+
+#[derive(AsStd140)]
+pub struct FadeUniform {
+    pub color: Vector4<f32>,
+    pub amount: f32,
+}
+
+pub struct FadeShader {
+    shader: Shader<FadeShader, 1>,
+}
+
+impl FadeShader {
+    pub fn new() -> FadeShader {
+        FadeShader {
+            shader: Shader::new(),
+        }
+    }
+
+    /// Draws the input, faded towards the color in the uniform.
+    pub fn draw(&self, uniform: &Uniform<FadeUniform>, input: &Texture, quad: &Buffer<PostVertex>) {
+        self.shader.draw(DrawMode::Triangles, uniform, [input], quad);
+    }
+}
+
+/// Fades the screen towards a color. This is useful for transitions between scenes.
+pub struct FadeEffect {
+    /// The color faded towards. Its alpha scales the amount.
+    pub color: RGBA8,
+    /// How far the screen is faded, from 0.0 showing the input unchanged, to 1.0 showing only the
+    /// color.
+    pub amount: f32,
+    shader: FadeShader,
+    uniform: Uniform<FadeUniform>,
+}
+
+impl FadeEffect {
+    /// Creates a new effect that fades towards the color. The amount starts at 0.0.
+    pub fn new(color: RGBA8) -> FadeEffect {
+        FadeEffect {
+            color,
+            amount: 0.0,
+            shader: FadeShader::new(),
+            uniform: Uniform::new(FadeUniform {
+                color: color_vector(color),
+                amount: 0.0,
+            }),
+        }
+    }
+}
+
+impl PostEffect for FadeEffect {
+    fn apply(&mut self, context: &PostContext, input: &Texture) {
+        self.uniform.set(FadeUniform {
+            color: color_vector(self.color),
+            amount: self.amount.clamp(0.0, 1.0),
+        });
+        self.shader.draw(&self.uniform, input, context.quad());
+    }
+}
@@ -0,0 +1,28 @@
+mod bloom;
+mod blur;
+mod chromatic_aberration;
+mod color_grade;
+mod crt;
+mod data;
+mod effect;
+mod fade;
+mod stack;
+mod vignette;
+
+pub use self::bloom::{
+    BloomCompositeShader, BloomCompositeUniform, BloomEffect, BloomExtractShader, BloomExtractUniform,
+};
+pub use self::blur::{BlurEffect, BlurShader, BlurUniform};
+pub use self::chromatic_aberration::{
+    ChromaticAberrationEffect, ChromaticAberrationShader, ChromaticAberrationUniform,
+};
+pub use self::color_grade::{ColorGradeEffect, ColorGradeShader, ColorGradeUniform};
+pub use self::crt::{CrtEffect, CrtShader, CrtUniform};
+pub use self::data::PostVertex;
+pub use self::effect::{CustomEffect, PostContext, PostEffect, POST_VERTEX_SHADER};
+pub use self::fade::{FadeEffect, FadeShader, FadeUniform};
+pub use self::stack::PostProcessStack;
+pub use self::vignette::{VignetteEffect, VignetteShader, VignetteUniform};
+
+pub(crate) use self::data::FULLSCREEN_TRIANGLE;
+pub(crate) use self::effect::{color_vector, fit_target};
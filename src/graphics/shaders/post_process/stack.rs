@@ -0,0 +1,108 @@
+use crate::color::RGBA8;
+use crate::graphics::{
+    clear, render_state, set_render_state, set_render_target,
+    shaders::post_process::{
+        fit_target, FadeEffect, PostContext, PostEffect, PostVertex, FULLSCREEN_TRIANGLE,
+    },
+    viewport_physical_pos, viewport_physical_size, window_physical_size, Buffer, ClearMode, RenderState,
+    RenderTarget,
+};
+use cgmath::*;
+
+/// The render state used by full-screen passes, which replace everything they cover.
+const POST_RENDER_STATE: RenderState = RenderState {
+    blend: None,
+    depth_test: false,
+    depth_write: false,
+    cull: None,
+    scissor: None,
+};
+
+/// Draws the scene into an offscreen render target, then runs an ordered list of full-screen
+/// effects over it, the last of which draws into the window.
+///
+/// ```ignore
+/// stack.begin();
+/// clear(ClearMode::color_depth(RGBA8::BLACK));
+/// sprites.draw(&sprite_shader);
+/// stack.end(&mut [&mut bloom, &mut vignette]);
+/// ```
+pub struct PostProcessStack {
+    scene: Option<RenderTarget>,
+    targets: [Option<RenderTarget>; 2],
+    quad: Buffer<PostVertex>,
+    copy: FadeEffect,
+}
+
+impl PostProcessStack {
+    /// Creates a new stack. Render targets are created on first use, matching the physical size of
+    /// the viewport.
+    pub fn new() -> PostProcessStack {
+        let mut quad = Buffer::new();
+        quad.set(&FULLSCREEN_TRIANGLE);
+        PostProcessStack {
+            scene: None,
+            targets: [None, None],
+            quad,
+            copy: FadeEffect::new(RGBA8::BLACK),
+        }
+    }
+
+    /// Directs subsequent draws into the scene's render target, resizing it first if the viewport's
+    /// physical size changed. Clear it afterwards as you would the window.
+    pub fn begin(&mut self) {
+        let size = viewport_physical_size().map(|x| x as u32);
+        set_render_target(Some(fit_target(&mut self.scene, size)));
+    }
+
+    /// The scene's render target, if [`PostProcessStack::begin`] has been called.
+    pub fn scene(&self) -> Option<&RenderTarget> {
+        self.scene.as_ref()
+    }
+
+    /// Runs the effects in order over the scene, each reading the previous one's result, and the last
+    /// drawing into the window's viewport. With no effects, the scene is copied to the window as is.
+    /// Draws are directed to the window afterwards, and the render state is restored. Any bars
+    /// around the viewport are cleared to black.
+    pub fn end(&mut self, effects: &mut [&mut dyn PostEffect]) {
+        let scene = match &self.scene {
+            Some(scene) => scene,
+            None => return,
+        };
+        let previous = render_state();
+        let size = scene.size();
+        let mut input = scene.texture().clone();
+        let count = effects.len().max(1);
+        for index in 0..count {
+            let effect: &mut dyn PostEffect = match effects.get_mut(index) {
+                Some(effect) => &mut **effect,
+                None => &mut self.copy,
+            };
+            let output = if index + 1 == count {
+                set_render_target(None);
+                set_render_state(&POST_RENDER_STATE);
+                if viewport_physical_pos() != Vector2::zero()
+                    || viewport_physical_size() != window_physical_size()
+                {
+                    clear(ClearMode::color(RGBA8::BLACK));
+                }
+                None
+            } else {
+                let target = fit_target(&mut self.targets[index % 2], size);
+                set_render_target(Some(target));
+                Some(target)
+            };
+            set_render_state(&POST_RENDER_STATE);
+            let context = PostContext {
+                output,
+                size,
+                quad: &self.quad,
+            };
+            effect.apply(&context, &input);
+            if let Some(output) = output {
+                input = output.texture().clone();
+            }
+        }
+        set_render_state(&previous);
+    }
+}
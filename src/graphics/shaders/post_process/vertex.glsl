@@ -0,0 +1,10 @@
+precision highp float;
+
+layout(location = 0) in vec2 a_pos;
+
+out vec2 v_uv;
+
+void main() {
+    v_uv = a_pos * 0.5 + 0.5;
+    gl_Position = vec4(a_pos, 0.0, 1.0);
+}
\ No newline at end of file
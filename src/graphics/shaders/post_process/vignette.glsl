@@ -0,0 +1,25 @@
+precision mediump float;
+
+in vec2 v_uv;
+out vec4 a_color;
+
+uniform sampler2D tex;
+
+layout(std140) uniform fragment {
+    vec4 color;
+    // Width divided by height of the output.
+    float aspect;
+    float intensity;
+    float radius;
+    float softness;
+};
+
+void main() {
+    vec4 source = texture(tex, v_uv);
+    vec2 offset = (v_uv - 0.5) * vec2(aspect, 1.0);
+    // The distance is 1.0 at the midpoint of the top and bottom edges.
+    float dist = length(offset) * 2.0;
+    float shade = 1.0 - smoothstep(radius - softness, radius, dist);
+    float amount = (1.0 - shade) * intensity * color.a;
+    a_color = vec4(mix(source.rgb, color.rgb, amount), source.a);
+}
\ No newline at end of file
@@ -0,0 +1,91 @@
+use crate::color::RGBA8;
+use crate::graphics::{
+    shaders::post_process::{color_vector, PostContext, PostEffect, PostVertex, POST_VERTEX_SHADER},
+    AsStd140, Buffer, DrawMode, Shader, ShaderDescriptor, Texture, Uniform,
+};
+use cgmath::*;
+
+impl ShaderDescriptor<1> for VignetteShader {
+    const VERTEX_SHADER: &'static str = POST_VERTEX_SHADER;
+    const FRAGMENT_SHADER: &'static str = include_str!("vignette.glsl");
+    const TEXTURE_NAMES: [&'static str; 1] = ["tex"];
+    const VERTEX_UNIFORM_NAME: &'static str = "fragment";
+    type VertexUniformType = VignetteUniform;
+    type VertexDescriptor = PostVertex;
+}
+
+#[derive(AsStd140)]
+pub struct VignetteUniform {
+    pub color: Vector4<f32>,
+    /// Width divided by height of the output.
+    pub aspect: f32,
+    pub intensity: f32,
+    pub radius: f32,
+    pub softness: f32,
+}
+
+pub struct VignetteShader {
+    shader: Shader<VignetteShader, 1>,
+}
+
+impl VignetteShader {
+    pub fn new() -> VignetteShader {
+        VignetteShader {
+            shader: Shader::new(),
+        }
+    }
+
+    /// Draws the input with a vignette.
+    pub fn draw(&self, uniform: &Uniform<VignetteUniform>, input: &Texture, quad: &Buffer<PostVertex>) {
+        self.shader.draw(DrawMode::Triangles, uniform, [input], quad);
+    }
+}
+
+/// Darkens the edges of the screen. Distances are measured from the center of the screen, where 1.0
+/// reaches the middle of the top and bottom edges.
+pub struct VignetteEffect {
+    /// The color the edges are shaded with. Its alpha scales the intensity.
+    pub color: RGBA8,
+    /// How strongly the edges are shaded, from 0.0 to 1.0.
+    pub intensity: f32,
+    /// The distance where the edges are fully shaded.
+    pub radius: f32,
+    /// The distance inside the radius over which the shading fades in.
+    pub softness: f32,
+    shader: VignetteShader,
+    uniform: Uniform<VignetteUniform>,
+}
+
+impl VignetteEffect {
+    /// Creates a new black vignette with an intensity of 0.5, a radius of 1.4, and a softness of 0.8.
+    pub fn new() -> VignetteEffect {
+        VignetteEffect {
+            color: RGBA8::BLACK,
+            intensity: 0.5,
+            radius: 1.4,
+            softness: 0.8,
+            shader: VignetteShader::new(),
+            uniform: Uniform::new(VignetteUniform {
+                color: color_vector(RGBA8::BLACK),
+                aspect: 1.0,
+                intensity: 0.0,
+                radius: 1.0,
+                softness: 0.0,
+            }),
+        }
+    }
+}
+
+impl PostEffect for VignetteEffect {
+    fn apply(&mut self, context: &PostContext, input: &Texture) {
+        let size = context.size();
+        self.uniform.set(VignetteUniform {
+            color: color_vector(self.color),
+            aspect: size.x as f32 / size.y.max(1) as f32,
+            intensity: self.intensity,
+            radius: self.radius,
+            softness: self.softness,
+        });
+        self.shader.draw(&self.uniform, input, context.quad());
+    }
+}
@@ -0,0 +1,32 @@
+use crate::color::RGBA8;
+use crate::graphics::{VertexAttribute, VertexDescriptor, VertexInputType, VertexOutputType, VertexStepRate};
+use cgmath::*;
+
+/// A single vertex of a tessellated shape.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ShapeVertex {
+    /// Position of the vertex. The Z coordinate represents depth. Units are measured in pixels.
+    pub pos: Vector3<f32>,
+    /// Color of the vertex. Colors are interpolated across each triangle.
+    pub color: RGBA8,
+}
+
+impl VertexDescriptor for ShapeVertex {
+    const STEP_RATE: VertexStepRate = VertexStepRate::PerVertex;
+    const ATTRIBUTES: &'static [VertexAttribute] = &[
+        // Pos, Color::RGBA8
+        VertexAttribute::new(3, VertexInputType::F32, VertexOutputType::F32),
+        VertexAttribute::new(4, VertexInputType::U8, VertexOutputType::NormalizedF32),
+    ];
+}
+
+impl ShapeVertex {
+    /// Creates a new vertex.
+    pub fn new(pos: Vector3<f32>, color: RGBA8) -> ShapeVertex {
+        ShapeVertex {
+            pos,
+            color,
+        }
+    }
+}
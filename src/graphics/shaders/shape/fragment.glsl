@@ -0,0 +1,11 @@
+precision mediump float;
+
+in vec4 v_color;
+out vec4 a_color;
+
+void main() {
+    a_color = v_color;
+    if (a_color.a <= 0.0) {
+        discard;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,6 @@
+mod data;
+mod shader;
+mod tessellate;
+
+pub use self::data::ShapeVertex;
+pub use self::shader::{ShapeShader, ShapeShaderPass, ShapeUniform};
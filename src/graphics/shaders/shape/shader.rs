@@ -0,0 +1,202 @@
+use crate::color::RGBA8;
+use crate::graphics::{
+    set_render_state,
+    shaders::shape::{tessellate, ShapeVertex},
+    AsStd140, Buffer, DrawMode, RenderState, Shader, ShaderDescriptor, ShaderError, Uniform,
+};
+use crate::math::AABB2D;
+use alloc::vec::Vec;
+use cgmath::*;
+
+impl ShaderDescriptor<0> for ShapeShader {
+    const VERTEX_SHADER: &'static str = include_str!("vertex.glsl");
+    const FRAGMENT_SHADER: &'static str = include_str!("fragment.glsl");
+    const TEXTURE_NAMES: [&'static str; 0] = [];
+    const VERTEX_UNIFORM_NAME: &'static str = "vertex";
+    type VertexUniformType = ShapeUniform;
+    type VertexDescriptor = ShapeVertex;
+}
+
+#[derive(AsStd140)]
+pub struct ShapeUniform {
+    pub ortho: Matrix4<f32>,
+}
+
+impl ShapeUniform {
+    pub fn new(ortho: Matrix4<f32>) -> ShapeUniform {
+        ShapeUniform {
+            ortho,
+        }
+    }
+}
+
+pub struct ShapeShader {
+    shader: Shader<ShapeShader, 0>,
+}
+
+impl ShapeShader {
+    pub fn new() -> ShapeShader {
+        ShapeShader {
+            shader: Shader::new(),
+        }
+    }
+
+    /// Recompiles the shader, see [`crate::graphics::Shader::reload`].
+    pub fn reload(&mut self, vertex_shader: &str, fragment_shader: &str) -> Result<(), ShaderError> {
+        self.shader.reload(vertex_shader, fragment_shader)
+    }
+
+    /// Draws to the screen.
+    pub fn draw(&self, uniform: &Uniform<ShapeUniform>, buffer: &Buffer<ShapeVertex>) {
+        self.shader.draw(DrawMode::Triangles, uniform, [], buffer);
+    }
+}
+
+/// Batches anti-aliased lines and shapes into a single draw. Shapes are tessellated as they're
+/// added, and stay in the pass until it's cleared, so a pass can either be rebuilt every frame for
+/// debug drawing, or built once for static vector art.
+///
+/// Shapes are drawn in the order they're added. By default the pass doesn't cull faces or write
+/// depth, so the transparent edges of shapes don't hide the shapes drawn after them.
+pub struct ShapeShaderPass {
+    uniform: Uniform<ShapeUniform>,
+    buffer: Buffer<ShapeVertex>,
+
+    vertices: Vec<ShapeVertex>,
+    depth: f32,
+    dirty: bool,
+    render_state: RenderState,
+}
+
+impl ShapeShaderPass {
+    pub fn new(ortho: Matrix4<f32>) -> ShapeShaderPass {
+        ShapeShaderPass {
+            uniform: Uniform::new(ShapeUniform::new(ortho)),
+            buffer: Buffer::new(),
+
+            vertices: Vec::new(),
+            depth: 0.0,
+            dirty: false,
+            render_state: RenderState {
+                depth_write: false,
+                cull: None,
+                ..RenderState::default()
+            },
+        }
+    }
+
+    /// Sets the orthographic projection used to draw this pass. If none is passed, this function
+    /// does nothing.
+    pub fn set_ortho(&mut self, ortho: Option<Matrix4<f32>>) {
+        if let Some(ortho) = ortho {
+            self.uniform.set(ShapeUniform::new(ortho));
+        }
+    }
+
+    /// Sets the render state applied when drawing this pass.
+    pub fn set_render_state(&mut self, state: RenderState) {
+        self.render_state = state;
+    }
+
+    /// Sets the depth value used for shapes added after this call. The default is 0.
+    pub fn set_depth(&mut self, depth: f32) {
+        self.depth = depth;
+    }
+
+    /// Draws the pass to the screen.
+    pub fn draw(&mut self, shader: &ShapeShader) {
+        if self.vertices.len() > 0 {
+            set_render_state(&self.render_state);
+            if self.dirty {
+                self.dirty = false;
+                self.buffer.set(&self.vertices);
+            }
+            shader.draw(&self.uniform, &self.buffer);
+        }
+    }
+
+    /// Clears all the shapes in the pass.
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.dirty = true;
+    }
+
+    /// Adds a line between two points. Units are measured in pixels.
+    pub fn line(&mut self, from: Vector2<f32>, to: Vector2<f32>, thickness: f32, color: RGBA8) {
+        self.polyline(&[from, to], thickness, color);
+    }
+
+    /// Adds a connected line through the points, with mitered joins.
+    pub fn polyline(&mut self, points: &[Vector2<f32>], thickness: f32, color: RGBA8) {
+        tessellate::stroke(&mut self.vertices, points, false, thickness, self.depth, color);
+        self.dirty = true;
+    }
+
+    /// Adds an arrow from one point to another, with the head at the destination. The size of the
+    /// head scales with the thickness of the line.
+    pub fn arrow(&mut self, from: Vector2<f32>, to: Vector2<f32>, thickness: f32, color: RGBA8) {
+        let delta = to - from;
+        let length = delta.magnitude();
+        if length <= 0.0 {
+            return;
+        }
+        let direction = delta / length;
+        let normal = Vector2::new(-direction.y, direction.x);
+        let head = (thickness * 4.0).max(8.0).min(length);
+        let base = to - direction * head;
+        self.line(from, base, thickness, color);
+        self.polygon(&[to, base + normal * (head * 0.5), base - normal * (head * 0.5)], color);
+    }
+
+    /// Adds a filled rectangle.
+    pub fn rect(&mut self, rect: &AABB2D, color: RGBA8) {
+        self.polygon(&rect_points(rect), color);
+    }
+
+    /// Adds the outline of a rectangle. The outline is centered on the rectangle's edges.
+    pub fn rect_outline(&mut self, rect: &AABB2D, thickness: f32, color: RGBA8) {
+        self.polygon_outline(&rect_points(rect), thickness, color);
+    }
+
+    /// Adds a filled circle.
+    pub fn circle(&mut self, center: Vector2<f32>, radius: f32, color: RGBA8) {
+        self.polygon(&tessellate::circle_points(center, radius), color);
+    }
+
+    /// Adds the outline of a circle. The outline is centered on the circle's edge.
+    pub fn circle_outline(&mut self, center: Vector2<f32>, radius: f32, thickness: f32, color: RGBA8) {
+        self.polygon_outline(&tessellate::circle_points(center, radius), thickness, color);
+    }
+
+    /// Adds an arc of a circle's outline. Angles are measured in turns, counter-clockwise from the
+    /// positive X axis. For example, an arc from 0.0 to 0.25 is the top right quarter of a circle.
+    pub fn arc(
+        &mut self,
+        center: Vector2<f32>,
+        radius: f32,
+        start: f32,
+        end: f32,
+        thickness: f32,
+        color: RGBA8,
+    ) {
+        self.polyline(&tessellate::arc_points(center, radius, start, end), thickness, color);
+    }
+
+    /// Adds a filled convex polygon. The points may be in either winding order. Concave polygons
+    /// are not supported and will draw incorrectly.
+    pub fn polygon(&mut self, points: &[Vector2<f32>], color: RGBA8) {
+        tessellate::fill_convex(&mut self.vertices, points, self.depth, color);
+        self.dirty = true;
+    }
+
+    /// Adds the outline of a polygon, connecting the last point back to the first. The outline is
+    /// centered on the polygon's edges.
+    pub fn polygon_outline(&mut self, points: &[Vector2<f32>], thickness: f32, color: RGBA8) {
+        tessellate::stroke(&mut self.vertices, points, true, thickness, self.depth, color);
+        self.dirty = true;
+    }
+}
+
+fn rect_points(rect: &AABB2D) -> [Vector2<f32>; 4] {
+    [rect.min, Vector2::new(rect.max.x, rect.min.y), rect.max, Vector2::new(rect.min.x, rect.max.y)]
+}
@@ -0,0 +1,302 @@
+use crate::color::RGBA8;
+use crate::graphics::shaders::shape::ShapeVertex;
+use alloc::vec::Vec;
+use cgmath::*;
+
+/// Width of the anti-aliased fringe around every edge, in pixels. Fringe vertices are fully
+/// transparent, so the interpolated alpha fades the edge out.
+const FEATHER: f32 = 1.0;
+/// The longest a miter join can be, as a multiple of half the stroke's width. Sharper joins are
+/// clamped to this length.
+const MITER_LIMIT: f32 = 4.0;
+
+/// Splits a stroke's thickness into the half width of its solid core and the color to draw it with.
+/// Strokes thinner than the fringe have no core, and fade their color instead.
+fn stroke_core(thickness: f32, color: RGBA8) -> (f32, RGBA8) {
+    if thickness >= FEATHER {
+        ((thickness - FEATHER) * 0.5, color)
+    } else {
+        let alpha = color.a as f32 * (thickness.max(0.0) / FEATHER);
+        (
+            0.0,
+            RGBA8 {
+                a: alpha as u8,
+                ..color
+            },
+        )
+    }
+}
+
+fn transparent(color: RGBA8) -> RGBA8 {
+    RGBA8 {
+        a: 0,
+        ..color
+    }
+}
+
+/// The left hand normal of a direction.
+fn normal(direction: Vector2<f32>) -> Vector2<f32> {
+    Vector2::new(-direction.y, direction.x).normalize()
+}
+
+/// The offset to move a vertex by to push both of its edges out by one unit.
+fn miter(incoming: Option<Vector2<f32>>, outgoing: Option<Vector2<f32>>) -> Vector2<f32> {
+    match (incoming, outgoing) {
+        (Some(incoming), Some(outgoing)) => {
+            let sum = incoming + outgoing;
+            if sum.magnitude2() < 1e-6 {
+                return incoming;
+            }
+            let miter = sum.normalize();
+            let scale = (1.0 / miter.dot(incoming)).min(MITER_LIMIT);
+            miter * scale
+        }
+        (Some(normal), None) | (None, Some(normal)) => normal,
+        (None, None) => Vector2::zero(),
+    }
+}
+
+/// Removes consecutive duplicate points, which have no direction to derive normals from.
+fn dedup(points: &[Vector2<f32>], closed: bool) -> Vec<Vector2<f32>> {
+    let mut result: Vec<Vector2<f32>> = Vec::with_capacity(points.len());
+    for &point in points {
+        match result.last() {
+            Some(&last) if (point - last).magnitude2() <= 1e-6 => {}
+            _ => result.push(point),
+        }
+    }
+    if closed && result.len() > 1 && (result[0] - result[result.len() - 1]).magnitude2() <= 1e-6 {
+        result.pop();
+    }
+    result
+}
+
+/// The vertex normals of a polyline, scaled so edges offset by them move by one unit.
+fn miters(points: &[Vector2<f32>], closed: bool) -> Vec<Vector2<f32>> {
+    let count = points.len();
+    (0..count)
+        .map(|i| {
+            let prev = if i > 0 {
+                Some(points[i - 1])
+            } else if closed {
+                Some(points[count - 1])
+            } else {
+                None
+            };
+            let next = if i + 1 < count {
+                Some(points[i + 1])
+            } else if closed {
+                Some(points[0])
+            } else {
+                None
+            };
+            miter(prev.map(|prev| normal(points[i] - prev)), next.map(|next| normal(next - points[i])))
+        })
+        .collect()
+}
+
+fn triangle(output: &mut Vec<ShapeVertex>, depth: f32, vertices: [(Vector2<f32>, RGBA8); 3]) {
+    for (pos, color) in vertices.iter() {
+        output.push(ShapeVertex::new(pos.extend(depth), *color));
+    }
+}
+
+/// Pushes two triangles covering the quad a, b, c, d, where the vertices are given in order around
+/// the quad.
+fn quad(output: &mut Vec<ShapeVertex>, depth: f32, vertices: [(Vector2<f32>, RGBA8); 4]) {
+    triangle(output, depth, [vertices[0], vertices[1], vertices[2]]);
+    triangle(output, depth, [vertices[0], vertices[2], vertices[3]]);
+}
+
+/// A cross section of a stroke: a center point, the direction to extrude it in, and its color.
+#[derive(Copy, Clone)]
+struct Section {
+    center: Vector2<f32>,
+    offset: Vector2<f32>,
+    color: RGBA8,
+}
+
+impl Section {
+    /// The vertices across the section, from the outer fringe on one side to the other.
+    fn vertices(&self, half: f32) -> [(Vector2<f32>, RGBA8); 4] {
+        let fade = transparent(self.color);
+        [
+            (self.center - self.offset * (half + FEATHER), fade),
+            (self.center - self.offset * half, self.color),
+            (self.center + self.offset * half, self.color),
+            (self.center + self.offset * (half + FEATHER), fade),
+        ]
+    }
+}
+
+fn stroke_between(output: &mut Vec<ShapeVertex>, depth: f32, half: f32, a: Section, b: Section) {
+    let a = a.vertices(half);
+    let b = b.vertices(half);
+    for i in 0..3 {
+        quad(output, depth, [a[i], a[i + 1], b[i + 1], b[i]]);
+    }
+}
+
+/// Tessellates a stroke along the points. Closed strokes connect the last point back to the first.
+/// Joins are mitered, and the ends of open strokes are butt capped.
+pub fn stroke(
+    output: &mut Vec<ShapeVertex>,
+    points: &[Vector2<f32>],
+    closed: bool,
+    thickness: f32,
+    depth: f32,
+    color: RGBA8,
+) {
+    let points = dedup(points, closed);
+    if points.len() < 2 || thickness <= 0.0 {
+        return;
+    }
+    let (half, color) = stroke_core(thickness, color);
+    let mut sections: Vec<Section> = points
+        .iter()
+        .zip(miters(&points, closed))
+        .map(|(&center, offset)| Section {
+            center,
+            offset,
+            color,
+        })
+        .collect();
+    if closed {
+        sections.push(sections[0]);
+    } else {
+        // Extend each end by the fringe, fading out along the length of the stroke.
+        let start = sections[0];
+        let end = sections[sections.len() - 1];
+        let start_direction = (points[0] - points[1]).normalize();
+        let end_direction = (points[points.len() - 1] - points[points.len() - 2]).normalize();
+        sections.insert(
+            0,
+            Section {
+                center: start.center + start_direction * FEATHER,
+                offset: start.offset,
+                color: transparent(color),
+            },
+        );
+        sections.push(Section {
+            center: end.center + end_direction * FEATHER,
+            offset: end.offset,
+            color: transparent(color),
+        });
+    }
+    for pair in sections.windows(2) {
+        stroke_between(output, depth, half, pair[0], pair[1]);
+    }
+}
+
+/// Tessellates a filled convex polygon. The points may be in either winding order.
+pub fn fill_convex(output: &mut Vec<ShapeVertex>, points: &[Vector2<f32>], depth: f32, color: RGBA8) {
+    let points = dedup(points, true);
+    if points.len() < 3 {
+        return;
+    }
+    let count = points.len();
+    let area: f32 = (0..count).map(|i| points[i].perp_dot(points[(i + 1) % count])).sum();
+    // Normals point left of each edge, which is inward for counter-clockwise polygons.
+    let outward = if area > 0.0 {
+        -1.0
+    } else {
+        1.0
+    };
+    let fade = transparent(color);
+    let half = FEATHER * 0.5 * outward;
+    let miters = miters(&points, true);
+    let inner: Vec<Vector2<f32>> =
+        points.iter().zip(&miters).map(|(&point, &miter)| point - miter * half).collect();
+    let outer: Vec<Vector2<f32>> =
+        points.iter().zip(&miters).map(|(&point, &miter)| point + miter * half).collect();
+    for i in 1..count - 1 {
+        triangle(output, depth, [(inner[0], color), (inner[i], color), (inner[i + 1], color)]);
+    }
+    for i in 0..count {
+        let next = (i + 1) % count;
+        quad(output, depth, [(inner[i], color), (outer[i], fade), (outer[next], fade), (inner[next], color)]);
+    }
+}
+
+/// The number of segments to approximate an arc with so the error stays well under a pixel.
+fn arc_segments(radius: f32, turns: f32) -> usize {
+    let full = (radius.max(0.0).sqrt() * 8.0).clamp(12.0, 256.0);
+    ((full * turns.abs()).ceil() as usize).max(1)
+}
+
+/// Points along an arc. Angles are measured in turns, counter-clockwise from the positive X axis.
+pub fn arc_points(center: Vector2<f32>, radius: f32, start: f32, end: f32) -> Vec<Vector2<f32>> {
+    let segments = arc_segments(radius, end - start);
+    (0..=segments)
+        .map(|i| {
+            let angle = Rad::full_turn() * (start + (end - start) * (i as f32 / segments as f32));
+            let (sin, cos) = angle.sin_cos();
+            center + Vector2::new(cos, sin) * radius
+        })
+        .collect()
+}
+
+/// Points around a circle, without repeating the first point.
+pub fn circle_points(center: Vector2<f32>, radius: f32) -> Vec<Vector2<f32>> {
+    let mut points = arc_points(center, radius, 0.0, 1.0);
+    points.pop();
+    points
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn miter_extends_corners() {
+        let offset = miter(Some(Vector2::new(0.0, 1.0)), Some(Vector2::new(-1.0, 0.0)));
+        assert!((offset - Vector2::new(-1.0, 1.0)).magnitude() < 1e-5);
+        let sharp = miter(Some(Vector2::new(0.0, 1.0)), Some(normal(Vector2::new(-1.0, -0.01))));
+        assert!(sharp.magnitude() <= MITER_LIMIT + 1e-5);
+    }
+
+    #[test]
+    fn thin_strokes_fade() {
+        let color = RGBA8::new(255, 255, 255, 200);
+        assert_eq!(stroke_core(3.0, color), (1.0, color));
+        assert_eq!(stroke_core(0.5, color), (0.0, RGBA8::new(255, 255, 255, 100)));
+    }
+
+    #[test]
+    fn fill_is_winding_independent() {
+        let square = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(10.0, 0.0),
+            Vector2::new(10.0, 10.0),
+            Vector2::new(0.0, 10.0),
+        ];
+        let mut reversed = square;
+        reversed.reverse();
+        for points in [square, reversed].iter() {
+            let mut output = Vec::new();
+            fill_convex(&mut output, points, 0.0, RGBA8::WHITE);
+            // Two triangles for the interior, and two per edge for the fringe.
+            assert_eq!(output.len(), (2 + 4 * 2) * 3);
+            for vertex in &output {
+                let inside =
+                    vertex.pos.x > 0.0 && vertex.pos.x < 10.0 && vertex.pos.y > 0.0 && vertex.pos.y < 10.0;
+                assert_eq!(inside, vertex.color.a == 255);
+            }
+        }
+    }
+
+    #[test]
+    fn degenerate_strokes_are_skipped() {
+        let mut output = Vec::new();
+        let point = Vector2::new(5.0, 5.0);
+        stroke(&mut output, &[point, point], false, 2.0, 0.0, RGBA8::WHITE);
+        assert!(output.is_empty());
+        stroke(&mut output, &[point, point + Vector2::new(1.0, 0.0)], false, 2.0, 0.0, RGBA8::WHITE);
+        // Three segments (two caps), each with three quads.
+        assert_eq!(output.len(), 3 * 3 * 6);
+    }
+}
@@ -0,0 +1,15 @@
+precision highp float;
+
+layout(location = 0) in vec3 a_pos;
+layout(location = 1) in vec4 a_color;
+
+out vec4 v_color;
+
+layout(std140) uniform vertex {
+    mat4 ortho;
+};
+
+void main() {
+    v_color = a_color;
+    gl_Position = ortho * vec4(a_pos, 1.0);
+}
\ No newline at end of file
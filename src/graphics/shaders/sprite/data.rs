@@ -10,24 +10,35 @@ pub struct Sprite {
     /// Position of the sprite. The X and Y coordinates represent the bottom left corner of the
     /// sprite. The Z coordinate represents sprite depth. Units are measured in pixels.
     pub pos: Vector3<f32>,
-    /// Units are measured in pixels.
-    pub size: Vector2<u16>,
+    /// Units are measured in pixels. Sizes may be fractional or negative. A negative size extends
+    /// the sprite left or down from its position, mirroring it.
+    pub size: Vector2<f32>,
     /// Texture to apply to the sprite. The default is a plain white texture.
     pub texture: TextureSection,
     /// Color multiplier to apply to the sprite. The default is white.
     pub color: RGBA8,
-    /// Rotation of the sprite. Units are 1/65536th of a turn.
+    /// Rotation of the sprite around its pivot. Units are 1/65536th of a turn.
     pub rotation: u16,
+    /// Mirrors the texture horizontally when set. The default is false.
+    pub flip_x: bool,
+    /// Mirrors the texture vertically when set. The default is false.
+    pub flip_y: bool,
+    /// The point the sprite rotates around, relative to the sprite's size. (0, 0) is the bottom
+    /// left corner and (1, 1) is the top right corner. Values outside of [0, 1] place the pivot
+    /// outside of the sprite. The default is the center, (0.5, 0.5).
+    pub pivot: Vector2<f32>,
 }
 
 impl VertexDescriptor for Sprite {
     const ATTRIBUTES: &'static [VertexAttribute] = &[
-        // Pos, Size, Texture, Color::RGBA8, Rotation
+        // Pos, Size, Texture, Color::RGBA8, Rotation, Flip, Pivot
         VertexAttribute::new(3, VertexInputType::F32, VertexOutputType::F32),
-        VertexAttribute::new(2, VertexInputType::U16, VertexOutputType::F32),
+        VertexAttribute::new(2, VertexInputType::F32, VertexOutputType::F32),
         VertexAttribute::new(4, VertexInputType::U16, VertexOutputType::NormalizedF32),
         VertexAttribute::new(4, VertexInputType::U8, VertexOutputType::NormalizedF32),
         VertexAttribute::new(1, VertexInputType::U16, VertexOutputType::NormalizedF32),
+        VertexAttribute::new(2, VertexInputType::U8, VertexOutputType::F32),
+        VertexAttribute::new(2, VertexInputType::F32, VertexOutputType::F32),
     ];
 }
 
@@ -35,19 +46,22 @@ impl Default for Sprite {
     fn default() -> Sprite {
         Sprite {
             pos: Vector3::new(0.0, 0.0, 0.0),
-            size: Vector2::new(100, 100),
+            size: Vector2::new(100.0, 100.0),
             texture: TextureSection::default(),
             color: RGBA8::WHITE,
             rotation: 0,
+            flip_x: false,
+            flip_y: false,
+            pivot: Vector2::new(0.5, 0.5),
         }
     }
 }
 
 impl Sprite {
-    /// Creates aa new sprite. This converts the rotation and size from floats automatically. Size
-    /// is measured in pixels, and is limited to 65535. Rotation is measured in turns from [0, 1).
-    /// Values outside of the range are wrapped into the range. For example, 1.75 is wrapped into
-    /// 0.75, -0.4 is wrapped into 0.6.
+    /// Creates a new sprite that rotates around its center. This converts the rotation from a float
+    /// automatically. Size is measured in pixels. Rotation is measured in turns from [0, 1). Values
+    /// outside of the range are wrapped into the range. For example, 1.75 is wrapped into 0.75,
+    /// -0.4 is wrapped into 0.6.
     pub fn new(
         pos: Vector3<f32>,
         size: Vector2<f32>,
@@ -57,22 +71,19 @@ impl Sprite {
     ) -> Sprite {
         Sprite {
             pos,
-            size: {
-                let x = (size.x as u32) & 0xFFFF;
-                let y = (size.y as u32) & 0xFFFF;
-                Vector2::new(x as u16, y as u16)
-            },
+            size,
             texture,
             color,
             rotation: (rotation.fract() * 65536.0) as u16,
+            ..Sprite::default()
         }
     }
 
-    /// Creates a new sprite. This does not perform conversions and represents exactly the members
-    /// of the sprite type.
+    /// Creates a new sprite. This does not perform conversions. The sprite isn't flipped and
+    /// rotates around its center, which [`Sprite::with_flip`] and [`Sprite::with_pivot`] change.
     pub fn new_raw(
         pos: Vector3<f32>,
-        size: Vector2<u16>,
+        size: Vector2<f32>,
         texture: TextureSection,
         color: RGBA8,
         rotation: u16,
@@ -83,12 +94,35 @@ impl Sprite {
             texture,
             color,
             rotation,
+            ..Sprite::default()
+        }
+    }
+
+    /// Returns the sprite with the given pivot. See [`Sprite::pivot`].
+    pub fn with_pivot(self, pivot: Vector2<f32>) -> Sprite {
+        Sprite {
+            pivot,
+            ..self
+        }
+    }
+
+    /// Returns the sprite with its texture mirrored on the given axes.
+    pub fn with_flip(self, flip_x: bool, flip_y: bool) -> Sprite {
+        Sprite {
+            flip_x,
+            flip_y,
+            ..self
         }
     }
 }
 
 impl From<Sprite> for AABB2D {
     fn from(sprite: Sprite) -> Self {
-        AABB2D::from_pos_size(sprite.pos.truncate(), sprite.size.cast().unwrap())
+        let a = sprite.pos.truncate();
+        let b = a + sprite.size;
+        AABB2D::from_min_max(
+            Vector2::new(a.x.min(b.x), a.y.min(b.y)),
+            Vector2::new(a.x.max(b.x), a.y.max(b.y)),
+        )
     }
 }
@@ -1,5 +1,7 @@
 mod data;
+mod nine_slice;
 mod shader;
 
 pub use self::data::Sprite;
+pub use self::nine_slice::{NineSlice, NineSliceInsets, NineSliceMode};
 pub use self::shader::{SpriteShader, SpriteShaderPass, SpriteUniform};
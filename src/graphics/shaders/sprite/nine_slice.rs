@@ -0,0 +1,222 @@
+use crate::color::RGBA8;
+use crate::graphics::{shaders::sprite::Sprite, Texture, TextureSection};
+use alloc::vec::Vec;
+use cgmath::*;
+
+/// How the edges and center of a nine-slice are filled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NineSliceMode {
+    /// The edges and center are stretched to fill the space between the borders.
+    Stretch,
+    /// The edges and center are repeated at their original size to fill the space between the
+    /// borders. The last repetition is cropped to fit.
+    Tile,
+}
+
+/// Border sizes of a nine-slice, measured in texels of the source texture section. Borders are
+/// drawn at one pixel per texel.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NineSliceInsets {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+impl NineSliceInsets {
+    /// Creates insets with the same size on every side.
+    pub const fn uniform(inset: f32) -> NineSliceInsets {
+        NineSliceInsets {
+            left: inset,
+            right: inset,
+            top: inset,
+            bottom: inset,
+        }
+    }
+}
+
+/// A rectangle drawn from a texture section split into a 3x3 grid. The corners keep their size,
+/// while the edges and center are stretched or tiled to fill the rectangle. This is useful for UI
+/// panels and dialogue boxes. Nine-slices are expanded into sprites with [`NineSlice::write`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NineSlice {
+    /// Position of the nine-slice. The X and Y coordinates represent the bottom left corner. The Z
+    /// coordinate represents depth. Units are measured in pixels.
+    pub pos: Vector3<f32>,
+    /// Units are measured in pixels. If the size is smaller than the borders, the borders are
+    /// scaled down to fit.
+    pub size: Vector2<f32>,
+    /// Texture to split into the nine slices.
+    pub texture: TextureSection,
+    /// Size of the texture section in texels.
+    pub texture_size: Vector2<f32>,
+    /// Border sizes, measured in texels of the texture section.
+    pub insets: NineSliceInsets,
+    /// How the edges and center are filled.
+    pub mode: NineSliceMode,
+    /// Color multiplier to apply to the nine-slice.
+    pub color: RGBA8,
+}
+
+impl NineSlice {
+    /// Creates a new nine-slice from a subsection of a texture. The texture section's size in
+    /// texels is derived from the texture.
+    pub fn new(
+        pos: Vector3<f32>,
+        size: Vector2<f32>,
+        texture: &Texture,
+        section: TextureSection,
+        insets: NineSliceInsets,
+        mode: NineSliceMode,
+    ) -> NineSlice {
+        let extent = section.0.cast::<f32>().unwrap() / 65536.0;
+        NineSlice {
+            pos,
+            size,
+            texture: section,
+            texture_size: Vector2::new(
+                (extent.y - extent.x).abs() * texture.width() as f32,
+                (extent.w - extent.z).abs() * texture.height() as f32,
+            ),
+            insets,
+            mode,
+            color: RGBA8::WHITE,
+        }
+    }
+
+    /// Expands the nine-slice into sprites, appending them to the output.
+    pub fn write(&self, output: &mut Vec<Sprite>) {
+        let section = self.texture.0.cast::<f32>().unwrap();
+        let columns = spans(self.size.x, self.insets.left, self.insets.right, self.texture_size.x, self.mode);
+        // Rows are laid out bottom to top, while texture sections are measured top to bottom.
+        let rows = spans(self.size.y, self.insets.bottom, self.insets.top, self.texture_size.y, self.mode);
+        for row in &rows {
+            let top = lerp(section.w, section.z, row.src_end);
+            let bottom = lerp(section.w, section.z, row.src_start);
+            for column in &columns {
+                let left = lerp(section.x, section.y, column.src_start);
+                let right = lerp(section.x, section.y, column.src_end);
+                output.push(Sprite::new(
+                    self.pos + Vector3::new(column.dst_start, row.dst_start, 0.0),
+                    Vector2::new(column.dst_end - column.dst_start, row.dst_end - row.dst_start),
+                    TextureSection(Vector4::new(left, right, top, bottom).map(|x| x.round() as u16)),
+                    self.color,
+                    0.0,
+                ));
+            }
+        }
+    }
+}
+
+fn lerp(start: f32, end: f32, t: f32) -> f32 {
+    start + (end - start) * t
+}
+
+/// A segment along one axis of a nine-slice. Destination values are in pixels, rounded to whole
+/// pixels so neighboring segments don't leave gaps. Source values are fractions of the texture
+/// section.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Span {
+    dst_start: f32,
+    dst_end: f32,
+    src_start: f32,
+    src_end: f32,
+}
+
+/// Splits one axis of a nine-slice into spans: the leading border, the middle, and the trailing
+/// border. In tile mode, the middle is split into a span per repetition.
+fn spans(length: f32, lead: f32, trail: f32, src_length: f32, mode: NineSliceMode) -> Vec<Span> {
+    let mut spans = Vec::new();
+    if length <= 0.0 || src_length <= 0.0 {
+        return spans;
+    }
+    let scale = if lead + trail > length {
+        length / (lead + trail)
+    } else {
+        1.0
+    };
+    let mut push = |dst_start: f32, dst_end: f32, src_start: f32, src_end: f32| {
+        let dst_start = dst_start.round();
+        let dst_end = dst_end.round();
+        if dst_end > dst_start {
+            spans.push(Span {
+                dst_start,
+                dst_end,
+                src_start: src_start / src_length,
+                src_end: src_end / src_length,
+            });
+        }
+    };
+
+    let middle_start = lead * scale;
+    let middle_end = length - trail * scale;
+    let src_middle_start = lead;
+    let src_middle_end = src_length - trail;
+    push(0.0, middle_start, 0.0, src_middle_start);
+    match mode {
+        NineSliceMode::Stretch => push(middle_start, middle_end, src_middle_start, src_middle_end),
+        NineSliceMode::Tile => {
+            let tile = src_middle_end - src_middle_start;
+            if tile > 0.0 {
+                let mut start = middle_start;
+                while start < middle_end {
+                    let end = (start + tile).min(middle_end);
+                    push(start, end, src_middle_start, src_middle_start + (end - start));
+                    start = end;
+                }
+            }
+        }
+    }
+    push(middle_end, length, src_middle_end, src_length);
+    spans
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(dst_start: f32, dst_end: f32, src_start: f32, src_end: f32) -> Span {
+        Span {
+            dst_start,
+            dst_end,
+            src_start,
+            src_end,
+        }
+    }
+
+    #[test]
+    fn stretch_keeps_borders() {
+        let result = spans(100.0, 4.0, 8.0, 16.0, NineSliceMode::Stretch);
+        assert_eq!(
+            result,
+            [span(0.0, 4.0, 0.0, 0.25), span(4.0, 92.0, 0.25, 0.5), span(92.0, 100.0, 0.5, 1.0)]
+        );
+    }
+
+    #[test]
+    fn tile_crops_last_repetition() {
+        let result = spans(20.0, 4.0, 4.0, 12.0, NineSliceMode::Tile);
+        assert_eq!(
+            result,
+            [
+                span(0.0, 4.0, 0.0, 1.0 / 3.0),
+                span(4.0, 8.0, 1.0 / 3.0, 2.0 / 3.0),
+                span(8.0, 12.0, 1.0 / 3.0, 2.0 / 3.0),
+                span(12.0, 16.0, 1.0 / 3.0, 2.0 / 3.0),
+                span(16.0, 20.0, 2.0 / 3.0, 1.0),
+            ]
+        );
+        let cropped = spans(18.0, 4.0, 4.0, 12.0, NineSliceMode::Tile);
+        assert_eq!(cropped[3], span(12.0, 14.0, 1.0 / 3.0, 0.5));
+    }
+
+    #[test]
+    fn small_sizes_scale_borders() {
+        let result = spans(6.0, 4.0, 8.0, 16.0, NineSliceMode::Stretch);
+        assert_eq!(result, [span(0.0, 2.0, 0.0, 0.25), span(2.0, 6.0, 0.5, 1.0)]);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::graphics::{
-    default_texture, shaders::sprite::Sprite, AsStd140, Buffer, DrawMode, Shader, ShaderDescriptor, Texture,
-    Uniform,
+    default_texture, set_render_state, shaders::sprite::Sprite, AsStd140, Buffer, DrawMode, RenderState,
+    Shader, ShaderDescriptor, ShaderError, Texture, Uniform,
 };
 use cgmath::Matrix4;
 
@@ -37,6 +37,11 @@ impl SpriteShader {
         }
     }
 
+    /// Recompiles the shader, see [`crate::graphics::Shader::reload`].
+    pub fn reload(&mut self, vertex_shader: &str, fragment_shader: &str) -> Result<(), ShaderError> {
+        self.shader.reload(vertex_shader, fragment_shader)
+    }
+
     /// Draws to the screen.
     pub fn draw(&self, uniform: &Uniform<SpriteUniform>, atlas: &Texture, buffer: &Buffer<Sprite>) {
         self.shader.draw_instanced(DrawMode::TriangleStrip, uniform, [atlas], buffer, 4);
@@ -47,6 +52,7 @@ pub struct SpriteShaderPass {
     pub uniform: Uniform<SpriteUniform>,
     pub atlas: Texture,
     pub buffer: Buffer<Sprite>,
+    render_state: RenderState,
 }
 
 impl SpriteShaderPass {
@@ -55,6 +61,7 @@ impl SpriteShaderPass {
             uniform: Uniform::new(SpriteUniform::new(ortho)),
             atlas: default_texture(),
             buffer: Buffer::new(),
+            render_state: RenderState::default(),
         }
     }
 
@@ -66,8 +73,14 @@ impl SpriteShaderPass {
         }
     }
 
+    /// Sets the render state applied when drawing this pass.
+    pub fn set_render_state(&mut self, state: RenderState) {
+        self.render_state = state;
+    }
+
     /// Draws the pass to the screen.
     pub fn draw(&mut self, shader: &SpriteShader) {
+        set_render_state(&self.render_state);
         shader.draw(&self.uniform, &self.atlas, &self.buffer);
     }
 }
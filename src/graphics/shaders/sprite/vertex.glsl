@@ -7,6 +7,8 @@ layout(location = 1) in vec2 a_size;
 layout(location = 2) in vec4 a_uv;
 layout(location = 3) in vec4 a_color;
 layout(location = 4) in float a_rotation;
+layout(location = 5) in vec2 a_flip;
+layout(location = 6) in vec2 a_pivot;
 
 out vec2 v_uv;
 out vec4 v_color;
@@ -34,9 +36,7 @@ vec4 rotateZ(vec3 pos) {
     float psi = TWO_PI * a_rotation;
     float sina = sin(psi);
     float cosa = cos(psi);
-    vec2 origin = vec2(
-        a_pos.x + (a_size.x * 0.5),
-        a_pos.y + (a_size.y * 0.5));
+    vec2 origin = a_pos.xy + (a_size * a_pivot);
     return vec4(
         (cosa * (pos.x - origin.x)) - (sina * (pos.y - origin.y)) + origin.x,
         (sina * (pos.x - origin.x)) + (cosa * (pos.y - origin.y)) + origin.y,
@@ -45,11 +45,20 @@ vec4 rotateZ(vec3 pos) {
 }
 
 void main() {
-    vec4 temp = a_uv * uv_lut[gl_VertexID];
+    // Negative sizes are drawn as positive sizes from the opposite corner with the texture
+    // mirrored, which keeps the winding order of the quad the same for face culling.
+    vec4 uv = a_uv;
+    if ((a_flip.x > 0.5) != (a_size.x < 0.0)) {
+        uv.xy = uv.yx;
+    }
+    if ((a_flip.y > 0.5) != (a_size.y < 0.0)) {
+        uv.zw = uv.wz;
+    }
+    vec4 temp = uv * uv_lut[gl_VertexID];
     v_uv = vec2(temp.x + temp.y, temp.z + temp.w);
     v_color = a_color;
 
-    vec3 size = vec3(a_size * size_lut[gl_VertexID], 0.0);
-    vec3 pos = a_pos + size;
+    vec3 size = vec3(abs(a_size) * size_lut[gl_VertexID], 0.0);
+    vec3 pos = a_pos + vec3(min(a_size, 0.0), 0.0) + size;
     gl_Position = ortho * rotateZ(pos);
 }
\ No newline at end of file
@@ -0,0 +1,304 @@
+use crate::graphics::{max_texture_size, shaders::text::TextSprite, Texture, TextureSection};
+use crate::image::{Image, Packer, Rect};
+use alloc::vec::Vec;
+use cgmath::Vector2;
+use core::cmp::Reverse;
+use core::hash::Hash;
+use hashbrown::{HashMap, HashSet};
+use log::warn;
+
+/// The share of the atlas a repack fills with glyphs that aren't in use, leaving the rest free so
+/// new glyphs don't immediately cause another repack.
+const REPACK_FILL: f32 = 0.75;
+
+/// A glyph in the atlas.
+#[derive(Debug, Copy, Clone)]
+pub(super) struct AtlasGlyph {
+    pub uv: TextureSection,
+    /// The size of the glyph's bitmap in pixels.
+    pub size: Vector2<f32>,
+}
+
+/// Caches glyph bitmaps in a texture. When the texture runs out of room, the least recently used
+/// glyphs are evicted and the rest are repacked. Glyphs in use by drawn text are never evicted, and
+/// if they fill the texture, glyphs that don't fit are skipped with a warning.
+pub(super) struct GlyphAtlas<K: Hash + Eq + Copy> {
+    texture: Texture,
+    cache: GlyphCache<K>,
+}
+
+impl<K: Hash + Eq + Copy> GlyphAtlas<K> {
+    pub fn new() -> GlyphAtlas<K> {
+        let max = max_texture_size().min(4096) as u32;
+        GlyphAtlas {
+            texture: Texture::from_image(&Image::from_color(0u8, max, max)),
+            cache: GlyphCache::new(max, max),
+        }
+    }
+
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// Gets a glyph, rasterizing it into the atlas if it isn't cached. Glyphs without a bitmap
+    /// have nothing to draw, and glyphs that don't fit in the atlas are skipped, so both return
+    /// None. The returned bool is true if the atlas was repacked, in which case the texture
+    /// coordinates of every glyph in use have changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The glyph to get.
+    /// * `in_use` - The glyphs used by drawn text, which must stay in the atlas.
+    /// * `rasterize` - Creates the glyph's bitmap if it isn't cached.
+    pub fn glyph(
+        &mut self,
+        key: K,
+        in_use: &[K],
+        rasterize: impl FnOnce() -> Option<Image<u8>>,
+    ) -> (Option<AtlasGlyph>, bool) {
+        let repacked = if self.cache.touch(key) {
+            false
+        } else {
+            let repacked = self.cache.insert(key, rasterize(), in_use);
+            if repacked {
+                self.upload_all();
+            } else {
+                self.upload(key);
+            }
+            repacked
+        };
+        (self.get(key), repacked)
+    }
+
+    /// Gets a cached glyph without changing how recently it was used.
+    pub fn get(&self, key: K) -> Option<AtlasGlyph> {
+        let glyph = self.cache.glyphs.get(&key)?;
+        let rect = glyph.rect?;
+        Some(AtlasGlyph {
+            uv: self.texture.subsection(rect.x, rect.x + rect.w, rect.y, rect.y + rect.h),
+            size: Vector2::new(rect.w as f32, rect.h as f32),
+        })
+    }
+
+    /// Points each sprite at the current place of its glyph in the atlas. This is needed after the
+    /// atlas is repacked. Sprites whose glyph no longer fits are hidden.
+    pub fn refresh(&self, sprites: &mut [TextSprite], keys: &[K]) {
+        for (sprite, key) in sprites.iter_mut().zip(keys) {
+            match self.get(*key) {
+                Some(glyph) => sprite.texture = glyph.uv,
+                None => sprite.size = Vector2::new(0.0, 0.0),
+            }
+        }
+    }
+
+    /// Evicts every glyph that isn't in use, and repacks the rest. The texture coordinates of every
+    /// glyph in use change.
+    pub fn evict_unused(&mut self, in_use: &[K]) {
+        self.cache.evict_unused(in_use);
+        self.upload_all();
+    }
+
+    fn upload(&self, key: K) {
+        if let Some(glyph) = self.cache.glyphs.get(&key) {
+            if let (Some(rect), Some(image)) = (glyph.rect, &glyph.image) {
+                self.texture.set(rect.x, rect.y, image);
+            }
+        }
+    }
+
+    fn upload_all(&self) {
+        for glyph in self.cache.glyphs.values() {
+            if let (Some(rect), Some(image)) = (glyph.rect, &glyph.image) {
+                self.texture.set(rect.x, rect.y, image);
+            }
+        }
+    }
+}
+
+struct CachedGlyph {
+    /// Kept so the glyph can be repacked without rasterizing it again.
+    image: Option<Image<u8>>,
+    /// Where the glyph is in the atlas. Glyphs without an image, or that didn't fit, have no rect.
+    rect: Option<Rect>,
+    last_used: u64,
+}
+
+impl CachedGlyph {
+    fn size(&self) -> Option<(u32, u32)> {
+        self.image.as_ref().map(|image| (image.width(), image.height()))
+    }
+}
+
+/// The packing and eviction behind a [`GlyphAtlas`], independent of the texture.
+struct GlyphCache<K: Hash + Eq + Copy> {
+    packer: Packer,
+    area: u32,
+    glyphs: HashMap<K, CachedGlyph>,
+    tick: u64,
+}
+
+impl<K: Hash + Eq + Copy> GlyphCache<K> {
+    fn new(width: u32, height: u32) -> GlyphCache<K> {
+        GlyphCache {
+            packer: Packer::new(width, height),
+            area: width * height,
+            glyphs: HashMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Marks the glyph as used, returning false if it isn't cached.
+    fn touch(&mut self, key: K) -> bool {
+        self.tick += 1;
+        match self.glyphs.get_mut(&key) {
+            Some(glyph) => {
+                glyph.last_used = self.tick;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds a glyph, returning true if the cache had to be repacked to fit it.
+    fn insert(&mut self, key: K, image: Option<Image<u8>>, in_use: &[K]) -> bool {
+        let size = image.as_ref().map(|image| (image.width(), image.height()));
+        self.tick += 1;
+        self.glyphs.insert(
+            key,
+            CachedGlyph {
+                image,
+                rect: None,
+                last_used: self.tick,
+            },
+        );
+        let (width, height) = match size {
+            Some(size) => size,
+            None => return false,
+        };
+        if let Some(rect) = self.packer.pack(width, height) {
+            self.glyphs.get_mut(&key).unwrap().rect = Some(rect);
+            return false;
+        }
+        self.repack(in_use, Some(key));
+        true
+    }
+
+    fn evict_unused(&mut self, in_use: &[K]) {
+        let in_use: HashSet<K> = in_use.iter().copied().collect();
+        self.glyphs.retain(|key, _| in_use.contains(key));
+        self.repack(&[], None);
+    }
+
+    /// Packs glyphs from scratch. Glyphs in use are packed first, then the new glyph, followed by
+    /// the rest from most to least recently used until the atlas is mostly full. Glyphs that aren't
+    /// packed are evicted, except for the glyphs in use and the new glyph, which are kept without a
+    /// rect so they aren't rasterized again.
+    fn repack(&mut self, in_use: &[K], new: Option<K>) {
+        let in_use: HashSet<K> = in_use.iter().copied().collect();
+        let priority = |key: &K| {
+            if in_use.contains(key) {
+                0
+            } else if Some(*key) == new {
+                1
+            } else {
+                2
+            }
+        };
+        let mut order: Vec<(u8, u64, K)> =
+            self.glyphs.iter().map(|(key, glyph)| (priority(key), glyph.last_used, *key)).collect();
+        order.sort_unstable_by_key(|&(priority, last_used, _)| (priority, Reverse(last_used)));
+
+        self.packer.clear();
+        let budget = (self.area as f32 * REPACK_FILL) as u32;
+        let mut used = 0;
+        let mut skipped = 0;
+        for (priority, _, key) in order {
+            let required = priority < 2;
+            let glyph = self.glyphs.get_mut(&key).unwrap();
+            glyph.rect = None;
+            let (width, height) = match glyph.size() {
+                Some(size) => size,
+                None => continue,
+            };
+            let rect = if required || used + width * height <= budget {
+                self.packer.pack(width, height)
+            } else {
+                None
+            };
+            match rect {
+                Some(rect) => {
+                    glyph.rect = Some(rect);
+                    used += width * height;
+                }
+                None if required => skipped += 1,
+                None => {
+                    self.glyphs.remove(&key);
+                }
+            }
+        }
+        if skipped > 0 {
+            warn!("The glyph atlas is too small for the text in use, so {} glyphs aren't drawn.", skipped);
+        }
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glyph() -> Option<Image<u8>> {
+        Some(Image::from_color(255u8, 4, 4))
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        // Room for four glyphs.
+        let mut cache = GlyphCache::new(8, 8);
+        for key in ['a', 'b', 'c', 'd'] {
+            assert!(!cache.insert(key, glyph(), &[]));
+        }
+        assert!(cache.touch('a'));
+        assert!(cache.insert('e', glyph(), &['b']));
+        // 'b' is in use and 'e' is new, then 'a' was used most recently. That fills three quarters
+        // of the atlas, so 'c' and 'd' are evicted.
+        assert!(cache.glyphs.contains_key(&'a'));
+        assert!(cache.glyphs.contains_key(&'b'));
+        assert!(cache.glyphs.contains_key(&'e'));
+        assert!(!cache.glyphs.contains_key(&'c'));
+        assert!(!cache.glyphs.contains_key(&'d'));
+        assert!(cache.glyphs.values().all(|glyph| glyph.rect.is_some()));
+        // There's room left, so the next glyph doesn't repack.
+        assert!(!cache.insert('f', glyph(), &[]));
+    }
+
+    #[test]
+    fn evict_unused_keeps_in_use() {
+        let mut cache = GlyphCache::new(8, 8);
+        for key in ['a', 'b', 'c'] {
+            cache.insert(key, glyph(), &[]);
+        }
+        cache.insert(' ', None, &[]);
+        cache.evict_unused(&['c']);
+        assert_eq!(cache.glyphs.len(), 1);
+        assert_eq!(cache.glyphs[&'c'].rect.map(|rect| (rect.x, rect.y)), Some((0, 0)));
+    }
+
+    #[test]
+    fn skips_glyphs_when_text_in_use_does_not_fit() {
+        let mut cache = GlyphCache::new(8, 8);
+        for key in ['a', 'b', 'c', 'd'] {
+            cache.insert(key, glyph(), &[]);
+        }
+        assert!(cache.insert('e', glyph(), &['a', 'b', 'c', 'd']));
+        for key in ['a', 'b', 'c', 'd'] {
+            assert!(cache.glyphs[&key].rect.is_some());
+        }
+        // The new glyph is kept without a rect, so it isn't rasterized or repacked again.
+        assert!(cache.glyphs[&'e'].rect.is_none());
+        assert!(cache.touch('e'));
+    }
+}
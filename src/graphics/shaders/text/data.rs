@@ -31,6 +31,41 @@ impl<'a> Into<TextStyle<'a, TextUserData>> for &Text<'a> {
     }
 }
 
+/// Effects drawn around distance field text by [`super::SdfTextShaderPass`]. Widths, offsets and
+/// softness are measured in Em units, so they scale with the text. The outline plus any glow, shadow
+/// offset or shadow softness is cut off past [`super::MAX_TEXT_EFFECT_WIDTH`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TextEffects {
+    /// How far the outline extends past the edge of the glyph. The default is 0, drawing no
+    /// outline.
+    pub outline_width: f32,
+    pub outline_color: RGBA8,
+    /// How far the shadow is moved from the text. Positive values move it right and up.
+    pub shadow_offset: Vector2<f32>,
+    /// How far the edge of the shadow is blurred.
+    pub shadow_softness: f32,
+    /// The default is transparent, drawing no shadow.
+    pub shadow_color: RGBA8,
+    /// How far the glow fades out past the edge of the glyph and outline. The default is 0, drawing
+    /// no glow.
+    pub glow_width: f32,
+    pub glow_color: RGBA8,
+}
+
+impl Default for TextEffects {
+    fn default() -> TextEffects {
+        TextEffects {
+            outline_width: 0.0,
+            outline_color: RGBA8::BLACK,
+            shadow_offset: Vector2::new(0.0, 0.0),
+            shadow_softness: 0.0,
+            shadow_color: RGBA8::TRANSPARENT,
+            glow_width: 0.0,
+            glow_color: RGBA8::WHITE,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub(crate) struct TextUserData {
     pub color: RGBA8,
@@ -41,7 +76,7 @@ pub(crate) struct TextUserData {
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct TextSprite {
     pub pos: Vector3<f32>,
-    pub size: Vector2<u16>,
+    pub size: Vector2<f32>,
     pub texture: TextureSection,
     pub color: RGBA8,
 }
@@ -50,7 +85,7 @@ impl VertexDescriptor for TextSprite {
     const ATTRIBUTES: &'static [VertexAttribute] = &[
         // Position, Size, UV, Color::RGBA8
         VertexAttribute::new(3, VertexInputType::F32, VertexOutputType::F32),
-        VertexAttribute::new(2, VertexInputType::U16, VertexOutputType::F32),
+        VertexAttribute::new(2, VertexInputType::F32, VertexOutputType::F32),
         VertexAttribute::new(4, VertexInputType::U16, VertexOutputType::NormalizedF32),
         VertexAttribute::new(4, VertexInputType::U8, VertexOutputType::NormalizedF32),
     ];
@@ -60,11 +95,7 @@ impl TextSprite {
     pub fn new(pos: Vector3<f32>, size: Vector2<f32>, texture: TextureSection, color: RGBA8) -> TextSprite {
         TextSprite {
             pos,
-            size: {
-                let x = (size.x as u32) & 0xFFFF;
-                let y = (size.y as u32) & 0xFFFF;
-                Vector2::new(x as u16, y as u16)
-            },
+            size,
             texture,
             color,
         }
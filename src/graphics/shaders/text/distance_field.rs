@@ -0,0 +1,94 @@
+use alloc::vec::Vec;
+
+/// Converts a coverage bitmap into a signed distance field. The field is padded by the spread on
+/// every side, so it's `width + 2 * spread` by `height + 2 * spread`. Each value is the distance to
+/// the nearest edge, mapped so 128 lies on the edge, 255 is the spread inside the glyph, and 0 is
+/// the spread outside it.
+pub(crate) fn distance_field(coverage: &[u8], width: usize, height: usize, spread: usize) -> Vec<u8> {
+    let inside = |x: isize, y: isize| -> bool {
+        x >= 0
+            && y >= 0
+            && (x as usize) < width
+            && (y as usize) < height
+            && coverage[y as usize * width + x as usize] >= 128
+    };
+    let out_width = width + 2 * spread;
+    let out_height = height + 2 * spread;
+    let radius = spread as isize;
+    let mut field = Vec::with_capacity(out_width * out_height);
+    for oy in 0..out_height {
+        for ox in 0..out_width {
+            let x = ox as isize - radius;
+            let y = oy as isize - radius;
+            let state = inside(x, y);
+
+            // The nearest pixel on the other side of the edge, within the spread.
+            let mut nearest = isize::MAX;
+            for dy in -radius..=radius {
+                for dx in -radius..=radius {
+                    let distance = dx * dx + dy * dy;
+                    if distance < nearest && inside(x + dx, y + dy) != state {
+                        nearest = distance;
+                    }
+                }
+            }
+
+            let signed = if nearest <= 1 {
+                // Pixels on the edge know how far across it they are from their coverage.
+                let value = if x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height {
+                    coverage[y as usize * width + x as usize]
+                } else {
+                    0
+                };
+                value as f32 / 255.0 - 0.5
+            } else {
+                // The edge lies halfway between a pixel and its neighbor.
+                let distance = if nearest == isize::MAX {
+                    spread as f32
+                } else {
+                    (nearest as f32).sqrt() - 0.5
+                };
+                if state {
+                    distance
+                } else {
+                    -distance
+                }
+            };
+            let value = 0.5 + signed / (2.0 * spread.max(1) as f32);
+            field.push((value.clamp(0.0, 1.0) * 255.0).round() as u8);
+        }
+    }
+    field
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn square_field() {
+        let coverage = vec![255u8; 100];
+        let field = distance_field(&coverage, 10, 10, 4);
+        assert_eq!(field.len(), 18 * 18);
+        let at = |x: usize, y: usize| field[y * 18 + x];
+        assert_eq!(at(0, 0), 0);
+        assert_eq!(at(9, 9), 255);
+        assert_eq!(at(4, 9), 143);
+        assert_eq!(at(3, 9), 112);
+        assert!(at(2, 9) < at(3, 9));
+        assert!(at(6, 9) > at(5, 9));
+    }
+
+    #[test]
+    fn partial_coverage_on_edge() {
+        let coverage = [255, 64, 0];
+        let field = distance_field(&coverage, 3, 1, 2);
+        // A pixel a quarter covered lies just outside of the edge.
+        assert_eq!(field[2 * 7 + 3], 112);
+    }
+}
@@ -0,0 +1,383 @@
+use crate::graphics::shaders::text::{Text, TextUserData};
+use crate::math::AABB2D;
+use alloc::vec::Vec;
+use cgmath::*;
+use fontdue::{
+    layout::{CoordinateSystem, GlyphPosition, HorizontalAlign, Layout, LayoutSettings, VerticalAlign},
+    Font, Metrics,
+};
+
+/// A character placed by a [`TextLayout`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TextGlyph {
+    pub character: char,
+    /// The index of the [`Text`] the character came from.
+    pub style: usize,
+    /// The byte offset of the character in the text it came from.
+    pub byte_offset: usize,
+    /// The index of the line the character is on.
+    pub line: usize,
+    /// Where the character starts on the baseline. This is where a caret before it is drawn.
+    pub pos: Vector2<f32>,
+    /// How far the character moves the characters after it to the right.
+    pub advance: f32,
+    /// The area covered by the character's bitmap. This is empty for whitespace.
+    pub bounds: AABB2D,
+}
+
+/// A line of text placed by a [`TextLayout`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TextLine {
+    /// The index of the first character on the line.
+    pub start: usize,
+    /// The index after the last character on the line. This equals the start on an empty line.
+    pub end: usize,
+    /// The height of the baseline.
+    pub baseline: f32,
+    /// How far the tallest font on the line reaches above the baseline.
+    pub ascent: f32,
+    /// The distance from the top of this line to the top of the next.
+    pub height: f32,
+    /// The area from the start of the first character to the end of the last, and from the top of
+    /// the line to the top of the next.
+    pub bounds: AABB2D,
+}
+
+/// Lays out text the same way the text passes draw it, for measuring it and mapping between
+/// character indices and positions. Indices count characters across every [`Text`] laid out, and
+/// the caret at an index goes before that character.
+pub struct TextLayout {
+    layout: Layout<TextUserData>,
+    glyphs: Vec<TextGlyph>,
+    lines: Vec<TextLine>,
+    origin: Vector2<f32>,
+}
+
+impl Default for TextLayout {
+    fn default() -> TextLayout {
+        TextLayout::new()
+    }
+}
+
+impl TextLayout {
+    pub fn new() -> TextLayout {
+        TextLayout {
+            layout: Layout::new(CoordinateSystem::PositiveYUp),
+            glyphs: Vec::new(),
+            lines: Vec::new(),
+            origin: Vector2::zero(),
+        }
+    }
+
+    /// Replaces the laid out text.
+    pub fn set(&mut self, fonts: &[Font], settings: &LayoutSettings, styles: &[Text]) {
+        self.layout.reset(settings);
+        for style in styles {
+            self.layout.append(fonts, &style.into());
+        }
+        self.glyphs.clear();
+        self.lines.clear();
+        self.origin = Vector2::new(settings.x, settings.y);
+
+        let characters = styles.iter().enumerate().flat_map(|(style, text)| {
+            text.text.char_indices().map(move |(byte_offset, character)| (style, byte_offset, character))
+        });
+        for (glyph, (style, byte_offset, character)) in self.layout.glyphs().iter().zip(characters) {
+            let font = &fonts[glyph.font_index];
+            let metrics = if glyph.char_data.is_control() {
+                Metrics::default()
+            } else {
+                font.metrics_indexed(glyph.key.glyph_index, glyph.key.px)
+            };
+            // The layout places the bitmap, so undo the bitmap's offset to find the pen position.
+            let pos =
+                Vector2::new(glyph.x - metrics.bounds.xmin.floor(), glyph.y - metrics.bounds.ymin.floor());
+            let (ascent, height) = line_metrics(font, glyph.key.px);
+            let index = self.glyphs.len();
+            match self.lines.last_mut() {
+                // Glyphs on the same line share a baseline.
+                Some(line) if (line.baseline - pos.y).abs() < 0.5 => {
+                    line.end = index + 1;
+                    line.ascent = line.ascent.max(ascent);
+                    line.height = line.height.max(height);
+                }
+                _ => self.lines.push(TextLine {
+                    start: index,
+                    end: index + 1,
+                    baseline: pos.y,
+                    ascent,
+                    height,
+                    bounds: AABB2D::from_pos_size(pos, Vector2::zero()),
+                }),
+            }
+            self.glyphs.push(TextGlyph {
+                character,
+                style,
+                byte_offset,
+                line: self.lines.len() - 1,
+                pos,
+                advance: metrics.advance_width.ceil(),
+                bounds: AABB2D::from_pos_size(
+                    Vector2::new(glyph.x, glyph.y),
+                    Vector2::new(glyph.width as f32, glyph.height as f32),
+                ),
+            });
+        }
+        for line in &mut self.lines {
+            let first = &self.glyphs[line.start];
+            let last = &self.glyphs[line.end - 1];
+            let top = line.baseline + line.ascent;
+            line.bounds = AABB2D::new(first.pos.x, top - line.height, last.pos.x + last.advance, top);
+        }
+
+        // Text that ends in a line break continues on an empty line, where the final caret goes.
+        if !matches!(self.glyphs.last(), Some(glyph) if glyph.character != '\n') {
+            let index = self.glyphs.len();
+            let (x, baseline, ascent, height) = match self.lines.last() {
+                Some(line) => {
+                    let (x, _) = aligned_origin(settings, line.height);
+                    (x, line.baseline - line.height, line.ascent, line.height)
+                }
+                None => match styles.first() {
+                    Some(style) => {
+                        let (ascent, height) = line_metrics(&fonts[style.font_index], style.px);
+                        let (x, y) = aligned_origin(settings, height);
+                        (x, y - ascent, ascent, height)
+                    }
+                    None => (self.origin.x, self.origin.y, 0.0, 0.0),
+                },
+            };
+            let top = baseline + ascent;
+            self.lines.push(TextLine {
+                start: index,
+                end: index,
+                baseline,
+                ascent,
+                height,
+                bounds: AABB2D::new(x, top - height, x, top),
+            });
+        }
+    }
+
+    /// The laid out characters, in the order they were given.
+    pub fn glyphs(&self) -> &[TextGlyph] {
+        &self.glyphs
+    }
+
+    /// The laid out lines, from top to bottom. Once text is set there's always at least one line,
+    /// even if the text is empty.
+    pub fn lines(&self) -> &[TextLine] {
+        &self.lines
+    }
+
+    /// The area covered by every line.
+    pub fn bounds(&self) -> AABB2D {
+        let mut lines = self.lines.iter();
+        let mut bounds = match lines.next() {
+            Some(line) => line.bounds,
+            None => return AABB2D::from_pos_size(self.origin, Vector2::zero()),
+        };
+        for line in lines {
+            bounds.min =
+                Vector2::new(bounds.min.x.min(line.bounds.min.x), bounds.min.y.min(line.bounds.min.y));
+            bounds.max =
+                Vector2::new(bounds.max.x.max(line.bounds.max.x), bounds.max.y.max(line.bounds.max.y));
+        }
+        bounds
+    }
+
+    /// Gets the caret before the character at the index, as a box with no width spanning the
+    /// character's line. Indices past the last character place the caret after it.
+    pub fn caret(&self, index: usize) -> AABB2D {
+        let (x, line) = match self.glyphs.get(index) {
+            Some(glyph) => (glyph.pos.x, &self.lines[glyph.line]),
+            None => match self.lines.last() {
+                Some(line) => (line.bounds.max.x, line),
+                None => return AABB2D::from_pos_size(self.origin, Vector2::zero()),
+            },
+        };
+        AABB2D::new(x, line.bounds.min.y, x, line.bounds.max.y)
+    }
+
+    /// Finds the index of the caret nearest to the position. This is where a caret goes when the
+    /// text is clicked.
+    pub fn caret_at(&self, pos: Vector2<f32>) -> usize {
+        let line = match self.line_at(pos.y) {
+            Some(line) => &self.lines[line],
+            None => return 0,
+        };
+        for index in line.start..line.end {
+            let glyph = &self.glyphs[index];
+            if glyph.character == '\n' || pos.x < glyph.pos.x + glyph.advance / 2.0 {
+                return index;
+            }
+        }
+        line.end
+    }
+
+    /// Finds the index of the character under the position, if there is one. Each character covers
+    /// the space it advances across, from the top of its line to the top of the next.
+    pub fn glyph_at(&self, pos: Vector2<f32>) -> Option<usize> {
+        let line = &self.lines[self.line_at(pos.y)?];
+        if !line.bounds.contains_point(&pos) {
+            return None;
+        }
+        (line.start..line.end).find(|&index| {
+            let glyph = &self.glyphs[index];
+            pos.x >= glyph.pos.x && pos.x < glyph.pos.x + glyph.advance
+        })
+    }
+
+    /// The positions of the glyphs to draw.
+    pub(super) fn positions(&mut self) -> &[GlyphPosition<TextUserData>] {
+        self.layout.glyphs()
+    }
+
+    /// Finds the line at the height, clamped to the first and last lines.
+    fn line_at(&self, y: f32) -> Option<usize> {
+        if self.lines.is_empty() {
+            return None;
+        }
+        Some(self.lines.iter().position(|line| y >= line.bounds.min.y).unwrap_or(self.lines.len() - 1))
+    }
+}
+
+/// The ascent and line height of the font, rounded the same way as the layout.
+fn line_metrics(font: &Font, px: f32) -> (f32, f32) {
+    match font.horizontal_line_metrics(px) {
+        Some(metrics) => (metrics.ascent.ceil(), metrics.new_line_size.ceil()),
+        None => (0.0, 0.0),
+    }
+}
+
+/// Where the layout starts a line with no text, after alignment.
+fn aligned_origin(settings: &LayoutSettings, height: f32) -> (f32, f32) {
+    let x = match settings.max_width {
+        Some(width) => match settings.horizontal_align {
+            HorizontalAlign::Left => 0.0,
+            HorizontalAlign::Center => (width * 0.5).floor(),
+            HorizontalAlign::Right => width.floor(),
+        },
+        None => 0.0,
+    };
+    let y = match settings.max_height {
+        Some(max_height) => match settings.vertical_align {
+            VerticalAlign::Top => 0.0,
+            VerticalAlign::Middle => ((max_height - height) * 0.5).floor(),
+            VerticalAlign::Bottom => (max_height - height).floor(),
+        },
+        None => 0.0,
+    };
+    (settings.x + x, settings.y - y)
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::RGBA8;
+
+    static FONT: &[u8] = include_bytes!("../../../../examples/resources/Roboto-Regular.ttf");
+
+    fn layout(text: &str) -> TextLayout {
+        layout_with(
+            text,
+            &LayoutSettings {
+                x: 10.0,
+                y: 200.0,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn layout_with(text: &str, settings: &LayoutSettings) -> TextLayout {
+        let fonts = [Font::from_bytes(FONT, Default::default()).unwrap()];
+        let mut layout = TextLayout::new();
+        let text = [Text {
+            text,
+            px: 20.0,
+            font_index: 0,
+            color: RGBA8::WHITE,
+            depth: 0.0,
+        }];
+        layout.set(&fonts, settings, &text);
+        layout
+    }
+
+    #[test]
+    fn lines_and_bounds() {
+        let layout = layout("ab\ncd");
+        assert_eq!(layout.glyphs().len(), 5);
+        assert_eq!(layout.lines().len(), 2);
+        assert_eq!((layout.lines()[0].start, layout.lines()[0].end), (0, 3));
+        assert_eq!((layout.lines()[1].start, layout.lines()[1].end), (3, 5));
+        assert_eq!(layout.glyphs()[4].byte_offset, 4);
+
+        let first = layout.lines()[0];
+        assert_eq!(first.bounds.max.y, 200.0);
+        assert_eq!(first.bounds.min.y, layout.lines()[1].bounds.max.y);
+        assert_eq!(layout.glyphs()[0].pos.x, 10.0);
+
+        let bounds = layout.bounds();
+        assert_eq!(bounds.min, Vector2::new(10.0, layout.lines()[1].bounds.min.y));
+        assert_eq!(bounds.max.y, 200.0);
+    }
+
+    #[test]
+    fn caret_round_trips() {
+        let layout = layout("ab\ncd");
+        for index in 0..=layout.glyphs().len() {
+            let caret = layout.caret(index);
+            let center = (caret.min + caret.max) / 2.0;
+            assert_eq!(layout.caret_at(center), index);
+        }
+        // Clicking past the end of a line places the caret before the line break.
+        let line = layout.lines()[0].bounds;
+        assert_eq!(layout.caret_at(Vector2::new(1000.0, line.min.y + 1.0)), 2);
+        // Clicking above or below the text uses the nearest line.
+        assert_eq!(layout.caret_at(Vector2::new(0.0, 1000.0)), 0);
+        assert_eq!(layout.caret_at(Vector2::new(1000.0, -1000.0)), 5);
+    }
+
+    #[test]
+    fn glyph_under_position() {
+        let layout = layout("ab");
+        let b = layout.glyphs()[1];
+        assert_eq!(layout.glyph_at(b.pos + Vector2::new(1.0, 1.0)), Some(1));
+        assert_eq!(layout.glyph_at(b.pos + Vector2::new(b.advance + 1.0, 1.0)), None);
+        assert_eq!(layout.glyph_at(Vector2::new(b.pos.x, 1000.0)), None);
+    }
+
+    #[test]
+    fn empty_lines_hold_a_caret() {
+        let empty = layout("");
+        assert_eq!(empty.lines().len(), 1);
+        assert_eq!(empty.caret(0).min.x, 10.0);
+        assert_eq!(empty.caret(0).max.y, 200.0);
+
+        let trailing = layout("a\n");
+        assert_eq!(trailing.lines().len(), 2);
+        let caret = trailing.caret(2);
+        assert_eq!(caret.min.x, 10.0);
+        assert_eq!(caret.max.y, trailing.lines()[0].bounds.min.y);
+    }
+
+    #[test]
+    fn empty_lines_follow_alignment() {
+        let settings = LayoutSettings {
+            x: 10.0,
+            y: 200.0,
+            max_width: Some(200.0),
+            horizontal_align: HorizontalAlign::Center,
+            ..Default::default()
+        };
+        assert_eq!(layout_with("", &settings).caret(0).min.x, 110.0);
+
+        let trailing = layout_with("a\n", &settings);
+        assert!(trailing.glyphs()[0].pos.x > 100.0);
+        assert_eq!(trailing.caret(2).min.x, 110.0);
+    }
+}
@@ -1,7 +1,16 @@
+mod atlas;
 mod data;
+mod distance_field;
+mod layout;
+mod sdf;
 mod shader;
 
 pub(crate) use self::data::{TextSprite, TextUserData};
 
-pub use self::data::Text;
+pub use self::data::{Text, TextEffects};
+pub use self::layout::{TextGlyph, TextLayout, TextLine};
+pub use self::sdf::{SdfTextShader, SdfTextShaderPass, SdfTextUniform, MAX_TEXT_EFFECT_WIDTH};
 pub use self::shader::{TextShader, TextShaderPass, TextUniform};
+
+use self::atlas::GlyphAtlas;
+use self::distance_field::distance_field;
@@ -0,0 +1,249 @@
+use crate::graphics::{
+    set_render_state,
+    shaders::{
+        post_process::color_vector,
+        text::{distance_field, GlyphAtlas, Text, TextEffects, TextLayout, TextSprite},
+    },
+    AsStd140, Buffer, DrawMode, RenderState, Shader, ShaderDescriptor, ShaderError, Texture, TextureFilter,
+    Uniform,
+};
+use crate::image::Image;
+use alloc::vec::Vec;
+use cgmath::*;
+use fontdue::{layout::LayoutSettings, Font};
+
+/// The size glyphs are rasterized at before they're converted into distance fields.
+const SDF_PX: f32 = 48.0;
+/// How far the distance field extends around a glyph, in pixels at [`SDF_PX`].
+const SDF_SPREAD: usize = 8;
+/// The furthest text effects can reach past the edge of a glyph, in Em units.
+pub const MAX_TEXT_EFFECT_WIDTH: f32 = SDF_SPREAD as f32 / SDF_PX;
+
+impl ShaderDescriptor<1> for SdfTextShader {
+    const VERTEX_SHADER: &'static str = include_str!("sdf_vertex.glsl");
+    const FRAGMENT_SHADER: &'static str = include_str!("sdf_fragment.glsl");
+    const TEXTURE_NAMES: [&'static str; 1] = ["tex"];
+    const VERTEX_UNIFORM_NAME: &'static str = "vertex";
+    type VertexUniformType = SdfTextUniform;
+    type VertexDescriptor = TextSprite;
+}
+
+#[derive(AsStd140)]
+pub struct SdfTextUniform {
+    ortho: Matrix4<f32>,
+    outline_color: Vector4<f32>,
+    shadow_color: Vector4<f32>,
+    glow_color: Vector4<f32>,
+    /// The shadow offset in atlas texture coordinates.
+    shadow_offset: Vector2<f32>,
+    outline_width: f32,
+    shadow_softness: f32,
+    glow_width: f32,
+    /// The distance in Em units between the edge and the end of the distance field.
+    spread: f32,
+}
+
+impl SdfTextUniform {
+    pub fn new(ortho: Matrix4<f32>, effects: &TextEffects, atlas_size: Vector2<f32>) -> SdfTextUniform {
+        // Texture coordinates run top to bottom, so the offset's y is flipped.
+        let shadow_offset = Vector2::new(effects.shadow_offset.x, -effects.shadow_offset.y) * SDF_PX;
+        SdfTextUniform {
+            ortho,
+            outline_color: color_vector(effects.outline_color),
+            shadow_color: color_vector(effects.shadow_color),
+            glow_color: color_vector(effects.glow_color),
+            shadow_offset: shadow_offset.div_element_wise(atlas_size),
+            outline_width: effects.outline_width.max(0.0),
+            shadow_softness: effects.shadow_softness.max(0.0),
+            glow_width: effects.glow_width.max(0.0),
+            spread: MAX_TEXT_EFFECT_WIDTH,
+        }
+    }
+}
+
+pub struct SdfTextShader {
+    shader: Shader<SdfTextShader, 1>,
+}
+
+impl SdfTextShader {
+    pub fn new() -> SdfTextShader {
+        SdfTextShader {
+            shader: Shader::new(),
+        }
+    }
+
+    /// Recompiles the shader, see [`crate::graphics::Shader::reload`].
+    pub fn reload(&mut self, vertex_shader: &str, fragment_shader: &str) -> Result<(), ShaderError> {
+        self.shader.reload(vertex_shader, fragment_shader)
+    }
+
+    /// Draws to the screen.
+    pub fn draw(&self, uniform: &Uniform<SdfTextUniform>, atlas: &Texture, buffer: &Buffer<TextSprite>) {
+        self.shader.draw_instanced(DrawMode::TriangleStrip, uniform, [atlas], buffer, 4);
+    }
+}
+
+/// Glyphs are keyed by font hash and glyph index.
+type SdfGlyphKey = (usize, u16);
+
+/// Draws text from distance fields. Unlike [`super::TextShaderPass`], each glyph is rasterized
+/// once regardless of its size, and stays sharp when scaled by the projection. Outlines, shadows and
+/// glows can be drawn with [`SdfTextShaderPass::set_effects`].
+pub struct SdfTextShaderPass {
+    uniform: Uniform<SdfTextUniform>,
+    atlas: GlyphAtlas<SdfGlyphKey>,
+    buffer: Buffer<TextSprite>,
+
+    ortho: Matrix4<f32>,
+    effects: TextEffects,
+    sprites: Vec<TextSprite>,
+    /// The glyph drawn by each sprite.
+    sprite_keys: Vec<SdfGlyphKey>,
+    layout: TextLayout,
+    dirty: bool,
+    render_state: RenderState,
+}
+
+impl SdfTextShaderPass {
+    pub fn new(ortho: Matrix4<f32>) -> SdfTextShaderPass {
+        let atlas = GlyphAtlas::new();
+        atlas.texture().set_filter(TextureFilter::Linear);
+        let effects = TextEffects::default();
+        SdfTextShaderPass {
+            uniform: Uniform::new(SdfTextUniform::new(ortho, &effects, atlas_size(atlas.texture()))),
+            atlas,
+            buffer: Buffer::new(),
+
+            ortho,
+            effects,
+            sprites: Vec::new(),
+            sprite_keys: Vec::new(),
+            layout: TextLayout::new(),
+            dirty: false,
+            render_state: RenderState::default(),
+        }
+    }
+
+    /// Sets the orthographic projection used to draw this pass. If none is passed, this function
+    /// does nothing.
+    pub fn set_ortho(&mut self, ortho: Option<Matrix4<f32>>) {
+        if let Some(ortho) = ortho {
+            self.ortho = ortho;
+            self.update_uniform();
+        }
+    }
+
+    /// The effects drawn around all text in this pass.
+    pub fn effects(&self) -> TextEffects {
+        self.effects
+    }
+
+    /// Sets the effects drawn around all text in this pass.
+    pub fn set_effects(&mut self, effects: TextEffects) {
+        self.effects = effects;
+        self.update_uniform();
+    }
+
+    /// Sets the render state applied when drawing this pass.
+    pub fn set_render_state(&mut self, state: RenderState) {
+        self.render_state = state;
+    }
+
+    /// Draws the pass to the screen.
+    pub fn draw(&mut self, shader: &SdfTextShader) {
+        if !self.sprites.is_empty() {
+            set_render_state(&self.render_state);
+            if self.dirty {
+                self.dirty = false;
+                self.buffer.set(&self.sprites);
+            }
+            shader.draw(&self.uniform, self.atlas.texture(), &self.buffer);
+        }
+    }
+
+    /// Appends text to the instance. The layout of the appended text can be measured with
+    /// [`Self::layout`].
+    pub fn append(&mut self, fonts: &[Font], layout: &LayoutSettings, styles: &[Text]) {
+        self.layout.set(fonts, layout, styles);
+        for glyph in self.layout.positions() {
+            if glyph.width == 0 {
+                continue;
+            }
+            let key = (glyph.key.font_hash, glyph.key.glyph_index);
+            let font = &fonts[glyph.font_index];
+            let (value, repacked) =
+                self.atlas.glyph(key, &self.sprite_keys, || rasterize(font, glyph.key.glyph_index));
+            if repacked {
+                self.atlas.refresh(&mut self.sprites, &self.sprite_keys);
+                self.dirty = true;
+            }
+            if let Some(value) = value {
+                let scale = glyph.key.px / SDF_PX;
+                let padding = SDF_SPREAD as f32 * scale;
+                self.sprites.push(TextSprite::new(
+                    Vector3::new(glyph.x - padding, glyph.y - padding, glyph.user_data.depth),
+                    value.size * scale,
+                    value.uv,
+                    glyph.user_data.color,
+                ));
+                self.sprite_keys.push(key);
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Rasterizes the characters ahead of time, so appending text with them later doesn't have to.
+    /// Glyphs added this way can still be evicted once the atlas fills up.
+    pub fn prewarm(&mut self, fonts: &[Font], font_index: usize, characters: &str) {
+        let font = &fonts[font_index];
+        for character in characters.chars() {
+            let glyph_index = font.lookup_glyph_index(character);
+            let key = (font.file_hash(), glyph_index);
+            let (_, repacked) = self.atlas.glyph(key, &self.sprite_keys, || rasterize(font, glyph_index));
+            if repacked {
+                self.atlas.refresh(&mut self.sprites, &self.sprite_keys);
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Evicts every glyph from the atlas that isn't used by the current text. Clear the text first
+    /// to empty the atlas entirely.
+    pub fn clear_cache(&mut self) {
+        self.atlas.evict_unused(&self.sprite_keys);
+        self.atlas.refresh(&mut self.sprites, &self.sprite_keys);
+        self.dirty = true;
+    }
+
+    /// The layout of the most recently appended text.
+    pub fn layout(&self) -> &TextLayout {
+        &self.layout
+    }
+
+    /// Clears all the text, drawing nothing.
+    pub fn clear_text(&mut self) {
+        self.sprites.clear();
+        self.sprite_keys.clear();
+        self.dirty = true;
+    }
+
+    fn update_uniform(&mut self) {
+        self.uniform.set(SdfTextUniform::new(self.ortho, &self.effects, atlas_size(self.atlas.texture())));
+    }
+}
+
+fn atlas_size(atlas: &Texture) -> Vector2<f32> {
+    Vector2::new(atlas.width() as f32, atlas.height() as f32)
+}
+
+/// Rasterizes the glyph into a distance field. Glyphs with nothing to draw have no field.
+fn rasterize(font: &Font, glyph_index: u16) -> Option<Image<u8>> {
+    let (metrics, bitmap) = font.rasterize_indexed(glyph_index, SDF_PX);
+    if metrics.width == 0 || metrics.height == 0 {
+        return None;
+    }
+    let field = distance_field(&bitmap, metrics.width, metrics.height, SDF_SPREAD);
+    let width = (metrics.width + 2 * SDF_SPREAD) as u32;
+    let height = (metrics.height + 2 * SDF_SPREAD) as u32;
+    Some(Image::from_vec(field, width, height))
+}
@@ -0,0 +1,68 @@
+precision highp float;
+
+in vec2 v_uv;
+in vec4 v_color;
+flat in vec4 v_rect;
+out vec4 a_color;
+
+uniform sampler2D tex;
+
+// Shared with the vertex shader, so the declarations must match.
+layout(std140) uniform vertex {
+    mat4 ortho;
+    vec4 outline_color;
+    vec4 shadow_color;
+    vec4 glow_color;
+    vec2 shadow_offset;
+    float outline_width;
+    float shadow_softness;
+    float glow_width;
+    float spread;
+};
+
+// The signed distance to the glyph's edge in Em units, positive inside. Samples are kept within
+// the glyph's section of the atlas. v_rect is (xmin, ymin, xmax, ymax).
+float edge_distance(vec2 uv) {
+    float field = texture(tex, clamp(uv, v_rect.xy, v_rect.zw)).r;
+    return (field - 0.5) * 2.0 * spread;
+}
+
+// Draws a over b, with colors that aren't premultiplied.
+vec4 over(vec4 a, vec4 b) {
+    float alpha = a.a + b.a * (1.0 - a.a);
+    if (alpha <= 0.0) {
+        return vec4(0.0);
+    }
+    return vec4((a.rgb * a.a + b.rgb * b.a * (1.0 - a.a)) / alpha, alpha);
+}
+
+void main() {
+    float dist = edge_distance(v_uv);
+    // Half a screen pixel in Em units, so edges stay sharp at any scale.
+    float aa = max(fwidth(dist) * 0.5, 0.00001);
+
+    float fill = smoothstep(-aa, aa, dist);
+    vec4 color = vec4(v_color.rgb, v_color.a * fill);
+    if (outline_width > 0.0) {
+        float outline = smoothstep(-aa, aa, dist + outline_width);
+        color = over(color, vec4(outline_color.rgb, outline_color.a * outline));
+    }
+
+    // Glow and shadow extend from the outer edge, including the outline.
+    float outer = dist + outline_width;
+    if (glow_width > 0.0) {
+        float glow = clamp(1.0 + outer / glow_width, 0.0, 1.0);
+        color = over(color, vec4(glow_color.rgb, glow_color.a * glow * glow));
+    }
+    if (shadow_color.a > 0.0) {
+        float shadow_dist = edge_distance(v_uv - shadow_offset) + outline_width;
+        float soft = shadow_softness + aa;
+        float shadow = smoothstep(-soft, soft, shadow_dist);
+        color = over(color, vec4(shadow_color.rgb, shadow_color.a * shadow));
+    }
+
+    a_color = color;
+    if (a_color.a <= 0.0) {
+        discard;
+    }
+}
\ No newline at end of file
@@ -1,17 +1,16 @@
 use crate::graphics::{
-    max_texture_size,
-    shaders::text::{Text, TextSprite, TextUserData},
-    AsStd140, Buffer, DrawMode, Shader, ShaderDescriptor, Texture, TextureSection, Uniform,
+    set_render_state,
+    shaders::text::{GlyphAtlas, Text, TextLayout, TextSprite},
+    AsStd140, Buffer, DrawMode, RenderState, Shader, ShaderDescriptor, ShaderError, Texture, Uniform,
 };
-use crate::image::{Image, Packer};
+use crate::image::Image;
 use crate::*;
 use alloc::vec::Vec;
 use cgmath::*;
 use fontdue::{
-    layout::{CoordinateSystem, GlyphRasterConfig, Layout, LayoutSettings},
+    layout::{GlyphRasterConfig, LayoutSettings},
     Font,
 };
-use hashbrown::HashMap;
 
 impl ShaderDescriptor<1> for TextShader {
     const VERTEX_SHADER: &'static str = include_str!("vertex.glsl");
@@ -46,44 +45,44 @@ impl TextShader {
         }
     }
 
+    /// Recompiles the shader, see [`crate::graphics::Shader::reload`].
+    pub fn reload(&mut self, vertex_shader: &str, fragment_shader: &str) -> Result<(), ShaderError> {
+        self.shader.reload(vertex_shader, fragment_shader)
+    }
+
     /// Draws to the screen.
     pub fn draw(&self, uniform: &Uniform<TextUniform>, atlas: &Texture, buffer: &Buffer<TextSprite>) {
         self.shader.draw_instanced(DrawMode::TriangleStrip, uniform, [atlas], buffer, 4);
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-struct CharCacheValue {
-    uv: TextureSection,
-    size: Vector2<f32>,
-}
-
+/// Draws text from glyph bitmaps, rasterized once for each size they're drawn at. When the glyph
+/// atlas fills up, the least recently used glyphs that aren't in the current text are evicted.
 pub struct TextShaderPass {
     uniform: Uniform<TextUniform>,
-    atlas: Texture,
+    atlas: GlyphAtlas<GlyphRasterConfig>,
     buffer: Buffer<TextSprite>,
 
     sprites: Vec<TextSprite>,
-    layout: Layout<TextUserData>,
-    packer: Packer,
-    cache: HashMap<GlyphRasterConfig, CharCacheValue>,
+    /// The glyph drawn by each sprite.
+    sprite_keys: Vec<GlyphRasterConfig>,
+    layout: TextLayout,
     dirty: bool,
+    render_state: RenderState,
 }
 
 impl TextShaderPass {
     pub fn new(ortho: Matrix4<f32>) -> TextShaderPass {
-        let max = max_texture_size().min(4096) as u32;
-        let atlas = Texture::from_image(&Image::from_color(0u8, max, max));
         TextShaderPass {
             uniform: Uniform::new(TextUniform::new(ortho)),
-            atlas,
+            atlas: GlyphAtlas::new(),
             buffer: Buffer::new(),
 
             sprites: Vec::new(),
-            layout: Layout::new(CoordinateSystem::PositiveYUp),
-            packer: Packer::new(max, max),
-            cache: HashMap::new(),
+            sprite_keys: Vec::new(),
+            layout: TextLayout::new(),
             dirty: false,
+            render_state: RenderState::default(),
         }
     }
 
@@ -95,63 +94,95 @@ impl TextShaderPass {
         }
     }
 
+    /// Sets the render state applied when drawing this pass.
+    pub fn set_render_state(&mut self, state: RenderState) {
+        self.render_state = state;
+    }
+
     /// Draws the pass to the screen.
     pub fn draw(&mut self, shader: &TextShader) {
         if self.sprites.len() > 0 {
+            set_render_state(&self.render_state);
             if self.dirty {
                 self.dirty = false;
                 self.buffer.set(&self.sprites);
             }
-            shader.draw(&self.uniform, &self.atlas, &self.buffer);
+            shader.draw(&self.uniform, self.atlas.texture(), &self.buffer);
         }
     }
 
-    /// Appends text to the instance.
+    /// Appends text to the instance. The layout of the appended text can be measured with
+    /// [`Self::layout`].
     pub fn append(&mut self, fonts: &[Font], layout: &LayoutSettings, styles: &[Text]) {
-        self.layout.reset(layout);
-        for style in styles {
-            self.layout.append(fonts, &style.into());
-        }
-        for glyph in self.layout.glyphs() {
+        self.layout.set(fonts, layout, styles);
+        for glyph in self.layout.positions() {
             if glyph.width == 0 {
                 continue;
             }
-            let value = match self.cache.get(&glyph.key).copied() {
-                Some(value) => value,
-                None => {
-                    let font = &fonts[glyph.font_index];
-                    let (metrics, bitmap) = font.rasterize_config(glyph.key);
-                    // info!("{:?}", metrics); // Debug
-                    let rect = self
-                        .packer
-                        .pack(metrics.width as u32, metrics.height as u32)
-                        .expect("Text packer is full.");
-                    self.atlas.set(
-                        rect.x,
-                        rect.y,
-                        &Image::from_vec(bitmap, metrics.width as u32, metrics.height as u32),
-                    );
-                    let value = CharCacheValue {
-                        uv: self.atlas.subsection(rect.x, rect.x + rect.w, rect.y, rect.y + rect.h),
-                        size: Vector2::new(metrics.width as f32, metrics.height as f32),
-                    };
-                    self.cache.insert(glyph.key, value);
-                    value
-                }
+            let font = &fonts[glyph.font_index];
+            let (value, repacked) =
+                self.atlas.glyph(glyph.key, &self.sprite_keys, || rasterize(font, glyph.key));
+            if repacked {
+                self.atlas.refresh(&mut self.sprites, &self.sprite_keys);
+                self.dirty = true;
+            }
+            if let Some(value) = value {
+                self.sprites.push(TextSprite::new(
+                    Vector3::new(glyph.x, glyph.y, glyph.user_data.depth),
+                    value.size,
+                    value.uv,
+                    glyph.user_data.color,
+                ));
+                self.sprite_keys.push(glyph.key);
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Rasterizes the characters at the given size ahead of time, so appending text with them
+    /// later doesn't have to. Glyphs added this way can still be evicted once the atlas fills up.
+    pub fn prewarm(&mut self, fonts: &[Font], font_index: usize, px: f32, characters: &str) {
+        let font = &fonts[font_index];
+        for character in characters.chars() {
+            let key = GlyphRasterConfig {
+                glyph_index: font.lookup_glyph_index(character),
+                px,
+                font_hash: font.file_hash(),
             };
-            self.sprites.push(TextSprite::new(
-                Vector3::new(glyph.x, glyph.y, glyph.user_data.depth),
-                value.size,
-                value.uv,
-                glyph.user_data.color,
-            ));
-            self.dirty = true;
+            let (_, repacked) = self.atlas.glyph(key, &self.sprite_keys, || rasterize(font, key));
+            if repacked {
+                self.atlas.refresh(&mut self.sprites, &self.sprite_keys);
+                self.dirty = true;
+            }
         }
     }
 
+    /// Evicts every glyph from the atlas that isn't used by the current text. Clear the text first
+    /// to empty the atlas entirely.
+    pub fn clear_cache(&mut self) {
+        self.atlas.evict_unused(&self.sprite_keys);
+        self.atlas.refresh(&mut self.sprites, &self.sprite_keys);
+        self.dirty = true;
+    }
+
+    /// The layout of the most recently appended text.
+    pub fn layout(&self) -> &TextLayout {
+        &self.layout
+    }
+
     /// Clears all the text, drawing nothing.
     pub fn clear_text(&mut self) {
         self.sprites.clear();
+        self.sprite_keys.clear();
         self.dirty = true;
     }
 }
+
+/// Rasterizes the glyph into a bitmap. Glyphs with nothing to draw have no bitmap.
+fn rasterize(font: &Font, key: GlyphRasterConfig) -> Option<Image<u8>> {
+    let (metrics, bitmap) = font.rasterize_config(key);
+    if metrics.width == 0 || metrics.height == 0 {
+        return None;
+    }
+    Some(Image::from_vec(bitmap, metrics.width as u32, metrics.height as u32))
+}
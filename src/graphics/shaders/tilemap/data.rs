@@ -0,0 +1,77 @@
+use crate::graphics::{TextureSection, VertexAttribute, VertexDescriptor, VertexInputType, VertexOutputType};
+use alloc::vec::Vec;
+use cgmath::*;
+
+/// A single tile, as drawn by the tilemap shader.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TileInstance {
+    /// Position of the tile. The X and Y coordinates represent the bottom left corner of the tile.
+    /// The Z coordinate represents depth. Units are measured in pixels.
+    pub pos: Vector3<f32>,
+    /// The section of the tileset to draw.
+    pub texture: TextureSection,
+}
+
+impl VertexDescriptor for TileInstance {
+    const ATTRIBUTES: &'static [VertexAttribute] = &[
+        // Pos, Texture
+        VertexAttribute::new(3, VertexInputType::F32, VertexOutputType::F32),
+        VertexAttribute::new(4, VertexInputType::U16, VertexOutputType::NormalizedF32),
+    ];
+}
+
+/// An animation for a tile. Wherever the animated tile is placed in a layer, it cycles through
+/// the frames instead.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TileAnimation {
+    /// The tiles to cycle through, in order.
+    pub frames: Vec<u16>,
+    /// How long each frame is shown for, in seconds.
+    pub frame_duration: f32,
+}
+
+impl TileAnimation {
+    /// Creates a new animation.
+    pub fn new(frames: Vec<u16>, frame_duration: f32) -> TileAnimation {
+        TileAnimation {
+            frames,
+            frame_duration,
+        }
+    }
+
+    /// Gets the tile shown at the given time, in seconds. Animations loop. Returns None if the
+    /// animation has no frames.
+    pub fn frame(&self, time: f32) -> Option<u16> {
+        if self.frames.is_empty() {
+            return None;
+        }
+        let index = if self.frame_duration > 0.0 {
+            (time / self.frame_duration).floor().max(0.0) as usize % self.frames.len()
+        } else {
+            0
+        };
+        Some(self.frames[index])
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn animation_loops() {
+        let animation = TileAnimation::new(vec![4, 5, 6], 0.25);
+        assert_eq!(animation.frame(0.0), Some(4));
+        assert_eq!(animation.frame(0.3), Some(5));
+        assert_eq!(animation.frame(0.5), Some(6));
+        assert_eq!(animation.frame(0.75), Some(4));
+        assert_eq!(animation.frame(-1.0), Some(4));
+        assert_eq!(TileAnimation::new(vec![], 0.25).frame(1.0), None);
+    }
+}
@@ -0,0 +1,202 @@
+use crate::graphics::{
+    shaders::tilemap::{TileInstance, TilemapShader, TilemapUniform},
+    Buffer, Texture, TextureSection, Uniform,
+};
+use crate::math::AABB2D;
+use alloc::{vec, vec::Vec};
+use cgmath::*;
+use hashbrown::HashMap;
+
+/// The width and height of a chunk, in tiles.
+const CHUNK_SIZE: u32 = 32;
+/// Marks an empty tile in a layer.
+const EMPTY: u16 = u16::MAX;
+
+/// What a layer needs from its pass to build the instances for its tiles.
+pub(super) struct TileLookup<'a> {
+    pub tile_size: Vector2<f32>,
+    pub sections: &'a [TextureSection],
+    /// The current frame of each animated tile.
+    pub frames: &'a HashMap<u16, u16>,
+}
+
+struct Chunk {
+    buffer: Buffer<TileInstance>,
+    dirty: bool,
+    /// If the chunk contained animated tiles when it was last built.
+    animated: bool,
+}
+
+/// A grid of tiles drawn at a single depth. Tile (0, 0) is the bottom left of the layer. Layers
+/// are split into chunks of 32x32 tiles, and only chunks with changed tiles are rebuilt.
+pub struct TilemapLayer {
+    width: u32,
+    height: u32,
+    depth: f32,
+    tiles: Vec<u16>,
+    chunks_x: u32,
+    chunks: Vec<Chunk>,
+}
+
+impl TilemapLayer {
+    pub(super) fn new(width: u32, height: u32, depth: f32) -> TilemapLayer {
+        let chunks_x = width.div_ceil(CHUNK_SIZE);
+        let chunks_y = height.div_ceil(CHUNK_SIZE);
+        TilemapLayer {
+            width,
+            height,
+            depth,
+            tiles: vec![EMPTY; width as usize * height as usize],
+            chunks_x,
+            chunks: (0..chunks_x * chunks_y)
+                .map(|_| Chunk {
+                    buffer: Buffer::new(),
+                    dirty: true,
+                    animated: false,
+                })
+                .collect(),
+        }
+    }
+
+    /// The width of the layer in tiles.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the layer in tiles.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The depth value the layer is drawn at.
+    pub fn depth(&self) -> f32 {
+        self.depth
+    }
+
+    /// Sets the depth value the layer is drawn at. This rebuilds every chunk in the layer.
+    pub fn set_depth(&mut self, depth: f32) {
+        self.depth = depth;
+        self.mark_all_dirty();
+    }
+
+    /// Gets the tile at the given position, or None if the position is empty. Panics if the
+    /// position is outside of the layer.
+    pub fn get(&self, x: u32, y: u32) -> Option<u16> {
+        match self.tiles[self.index(x, y)] {
+            EMPTY => None,
+            tile => Some(tile),
+        }
+    }
+
+    /// Sets the tile at the given position, where the tile is an index into the tileset. Tiles are
+    /// numbered left to right, then top to bottom, starting at 0. Panics if the position is outside
+    /// of the layer.
+    pub fn set(&mut self, x: u32, y: u32, tile: Option<u16>) {
+        let index = self.index(x, y);
+        let tile = tile.unwrap_or(EMPTY);
+        if self.tiles[index] != tile {
+            self.tiles[index] = tile;
+            let chunk = (y / CHUNK_SIZE) * self.chunks_x + (x / CHUNK_SIZE);
+            self.chunks[chunk as usize].dirty = true;
+        }
+    }
+
+    /// Sets every tile in the layer. Tiles are given row by row, starting from the bottom row. Panics
+    /// if the number of tiles doesn't match the size of the layer.
+    pub fn set_all(&mut self, tiles: &[Option<u16>]) {
+        assert_eq!(tiles.len(), self.tiles.len(), "Tile count doesn't match the size of the layer.");
+        for (dst, src) in self.tiles.iter_mut().zip(tiles) {
+            *dst = src.unwrap_or(EMPTY);
+        }
+        self.mark_all_dirty();
+    }
+
+    /// Removes every tile from the layer.
+    pub fn clear(&mut self) {
+        for tile in self.tiles.iter_mut() {
+            *tile = EMPTY;
+        }
+        self.mark_all_dirty();
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        assert!(x < self.width && y < self.height, "Tile position is outside of the layer.");
+        y as usize * self.width as usize + x as usize
+    }
+
+    pub(super) fn mark_all_dirty(&mut self) {
+        for chunk in self.chunks.iter_mut() {
+            chunk.dirty = true;
+        }
+    }
+
+    pub(super) fn mark_animated_dirty(&mut self) {
+        for chunk in self.chunks.iter_mut() {
+            chunk.dirty |= chunk.animated;
+        }
+    }
+
+    /// Draws every chunk that overlaps the visible area, rebuilding dirty chunks first.
+    pub(super) fn draw(
+        &mut self,
+        shader: &TilemapShader,
+        uniform: &Uniform<TilemapUniform>,
+        atlas: &Texture,
+        visible: &AABB2D,
+        lookup: &TileLookup,
+        scratch: &mut Vec<TileInstance>,
+    ) {
+        let chunk_size = lookup.tile_size * CHUNK_SIZE as f32;
+        for index in 0..self.chunks.len() {
+            let chunk_x = index as u32 % self.chunks_x;
+            let chunk_y = index as u32 / self.chunks_x;
+            let bounds = AABB2D::from_pos_size(
+                Vector2::new(chunk_x as f32 * chunk_size.x, chunk_y as f32 * chunk_size.y),
+                chunk_size,
+            );
+            if !bounds.intersects(visible) {
+                continue;
+            }
+            if self.chunks[index].dirty {
+                self.build(chunk_x, chunk_y, lookup, scratch);
+            }
+            shader.draw(uniform, atlas, &self.chunks[index].buffer);
+        }
+    }
+
+    fn build(&mut self, chunk_x: u32, chunk_y: u32, lookup: &TileLookup, scratch: &mut Vec<TileInstance>) {
+        scratch.clear();
+        let mut animated = false;
+        let x_end = ((chunk_x + 1) * CHUNK_SIZE).min(self.width);
+        let y_end = ((chunk_y + 1) * CHUNK_SIZE).min(self.height);
+        for y in chunk_y * CHUNK_SIZE..y_end {
+            for x in chunk_x * CHUNK_SIZE..x_end {
+                let tile = self.tiles[y as usize * self.width as usize + x as usize];
+                if tile == EMPTY {
+                    continue;
+                }
+                let tile = match lookup.frames.get(&tile) {
+                    Some(&frame) => {
+                        animated = true;
+                        frame
+                    }
+                    None => tile,
+                };
+                if let Some(&texture) = lookup.sections.get(tile as usize) {
+                    scratch.push(TileInstance {
+                        pos: Vector3::new(
+                            x as f32 * lookup.tile_size.x,
+                            y as f32 * lookup.tile_size.y,
+                            self.depth,
+                        ),
+                        texture,
+                    });
+                }
+            }
+        }
+        let chunk = &mut self.chunks[(chunk_y * self.chunks_x + chunk_x) as usize];
+        chunk.buffer.set(scratch);
+        chunk.dirty = false;
+        chunk.animated = animated;
+    }
+}
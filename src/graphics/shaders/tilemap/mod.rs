@@ -0,0 +1,7 @@
+mod data;
+mod layer;
+mod shader;
+
+pub use self::data::{TileAnimation, TileInstance};
+pub use self::layer::TilemapLayer;
+pub use self::shader::{TilemapShader, TilemapShaderPass, TilemapUniform};
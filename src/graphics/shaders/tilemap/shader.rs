@@ -0,0 +1,198 @@
+use crate::graphics::{
+    set_render_state,
+    shaders::tilemap::{layer::TileLookup, TileAnimation, TileInstance, TilemapLayer},
+    AsStd140, Buffer, DrawMode, RenderState, Shader, ShaderDescriptor, ShaderError, Texture, TextureSection,
+    Uniform,
+};
+use crate::math::AABB2D;
+use alloc::vec::Vec;
+use cgmath::*;
+use hashbrown::HashMap;
+
+impl ShaderDescriptor<1> for TilemapShader {
+    const VERTEX_SHADER: &'static str = include_str!("vertex.glsl");
+    const FRAGMENT_SHADER: &'static str = include_str!("fragment.glsl");
+    const TEXTURE_NAMES: [&'static str; 1] = ["tex"];
+    const VERTEX_UNIFORM_NAME: &'static str = "vertex";
+    type VertexUniformType = TilemapUniform;
+    type VertexDescriptor = TileInstance;
+}
+
+#[derive(AsStd140)]
+pub struct TilemapUniform {
+    pub ortho: Matrix4<f32>,
+    /// The size of each tile in pixels.
+    pub tile_size: Vector2<f32>,
+}
+
+impl TilemapUniform {
+    pub fn new(ortho: Matrix4<f32>, tile_size: Vector2<f32>) -> TilemapUniform {
+        TilemapUniform {
+            ortho,
+            tile_size,
+        }
+    }
+}
+
+pub struct TilemapShader {
+    shader: Shader<TilemapShader, 1>,
+}
+
+impl TilemapShader {
+    pub fn new() -> TilemapShader {
+        TilemapShader {
+            shader: Shader::new(),
+        }
+    }
+
+    /// Recompiles the shader, see [`crate::graphics::Shader::reload`].
+    pub fn reload(&mut self, vertex_shader: &str, fragment_shader: &str) -> Result<(), ShaderError> {
+        self.shader.reload(vertex_shader, fragment_shader)
+    }
+
+    /// Draws to the screen.
+    pub fn draw(&self, uniform: &Uniform<TilemapUniform>, atlas: &Texture, buffer: &Buffer<TileInstance>) {
+        self.shader.draw_instanced(DrawMode::TriangleStrip, uniform, [atlas], buffer, 4);
+    }
+}
+
+/// Draws layers of tiles from a tileset. Each layer keeps its tiles in static buffers, split into
+/// chunks that are only rebuilt when their tiles change, and only chunks overlapping the visible
+/// area are drawn.
+pub struct TilemapShaderPass {
+    uniform: Uniform<TilemapUniform>,
+    atlas: Texture,
+    tile_size: Vector2<f32>,
+    sections: Vec<TextureSection>,
+
+    layers: Vec<TilemapLayer>,
+    animations: HashMap<u16, TileAnimation>,
+    frames: HashMap<u16, u16>,
+    time: f32,
+    scratch: Vec<TileInstance>,
+    render_state: RenderState,
+}
+
+impl TilemapShaderPass {
+    /// Creates a new pass. The tileset is split into a grid of tiles of the given size in pixels,
+    /// which are numbered left to right, then top to bottom, starting at 0.
+    pub fn new(ortho: Matrix4<f32>, tileset: Texture, tile_size: Vector2<u32>) -> TilemapShaderPass {
+        let columns = tileset.width() / tile_size.x.max(1);
+        let rows = tileset.height() / tile_size.y.max(1);
+        let sections = (0..rows)
+            .flat_map(|row| (0..columns).map(move |column| (column, row)))
+            .map(|(column, row)| {
+                TextureSection::from_texture(
+                    &tileset,
+                    column * tile_size.x,
+                    (column + 1) * tile_size.x,
+                    row * tile_size.y,
+                    (row + 1) * tile_size.y,
+                )
+            })
+            .collect();
+        let tile_size = tile_size.cast::<f32>().unwrap();
+        TilemapShaderPass {
+            uniform: Uniform::new(TilemapUniform::new(ortho, tile_size)),
+            atlas: tileset,
+            tile_size,
+            sections,
+
+            layers: Vec::new(),
+            animations: HashMap::new(),
+            frames: HashMap::new(),
+            time: 0.0,
+            scratch: Vec::new(),
+            render_state: RenderState::default(),
+        }
+    }
+
+    /// Sets the orthographic projection used to draw this pass. If none is passed, this function
+    /// does nothing.
+    pub fn set_ortho(&mut self, ortho: Option<Matrix4<f32>>) {
+        if let Some(ortho) = ortho {
+            self.uniform.set(TilemapUniform::new(ortho, self.tile_size));
+        }
+    }
+
+    /// Sets the render state applied when drawing this pass.
+    pub fn set_render_state(&mut self, state: RenderState) {
+        self.render_state = state;
+    }
+
+    /// Adds an empty layer with the given size in tiles, returning its index. Layers are drawn in
+    /// the order they're added.
+    pub fn add_layer(&mut self, width: u32, height: u32, depth: f32) -> usize {
+        self.layers.push(TilemapLayer::new(width, height, depth));
+        self.layers.len() - 1
+    }
+
+    /// Gets the layer at the given index. Panics if the index is out of bounds.
+    pub fn layer(&self, index: usize) -> &TilemapLayer {
+        &self.layers[index]
+    }
+
+    /// Gets the layer at the given index for editing. Panics if the index is out of bounds.
+    pub fn layer_mut(&mut self, index: usize) -> &mut TilemapLayer {
+        &mut self.layers[index]
+    }
+
+    /// Gets the number of layers in the pass.
+    pub fn layer_count(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Animates a tile. Wherever the tile is placed, the animation's frames are drawn instead.
+    pub fn set_animation(&mut self, tile: u16, animation: TileAnimation) {
+        match animation.frame(self.time) {
+            Some(frame) => self.frames.insert(tile, frame),
+            None => self.frames.remove(&tile),
+        };
+        self.animations.insert(tile, animation);
+        for layer in self.layers.iter_mut() {
+            layer.mark_all_dirty();
+        }
+    }
+
+    /// Stops animating a tile.
+    pub fn remove_animation(&mut self, tile: u16) {
+        if self.animations.remove(&tile).is_some() {
+            self.frames.remove(&tile);
+            for layer in self.layers.iter_mut() {
+                layer.mark_animated_dirty();
+            }
+        }
+    }
+
+    /// Sets the time used to pick the frame of animated tiles, in seconds. Only chunks with
+    /// animated tiles are rebuilt, and only when a frame changes.
+    pub fn set_time(&mut self, time: f32) {
+        self.time = time;
+        let mut changed = false;
+        for (&tile, animation) in self.animations.iter() {
+            if let Some(frame) = animation.frame(time) {
+                changed |= self.frames.insert(tile, frame) != Some(frame);
+            }
+        }
+        if changed {
+            for layer in self.layers.iter_mut() {
+                layer.mark_animated_dirty();
+            }
+        }
+    }
+
+    /// Draws the pass to the screen. Only chunks overlapping the visible area are drawn, which is
+    /// measured in the same units as tile positions. This is usually
+    /// [`crate::math::Transform::visible_bounds`].
+    pub fn draw(&mut self, shader: &TilemapShader, visible: &AABB2D) {
+        set_render_state(&self.render_state);
+        let lookup = TileLookup {
+            tile_size: self.tile_size,
+            sections: &self.sections,
+            frames: &self.frames,
+        };
+        for layer in self.layers.iter_mut() {
+            layer.draw(shader, &self.uniform, &self.atlas, visible, &lookup, &mut self.scratch);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+precision highp float;
+
+layout(location = 0) in vec3 a_pos;
+layout(location = 1) in vec4 a_uv;
+
+out vec2 v_uv;
+
+layout(std140) uniform vertex {
+    mat4 ortho;
+    vec2 tile_size;
+};
+
+// UV Layout: xmin xmax ymin ymax
+// ymin and ymax are swapped below because OpenGL reads images from bottom row to top row, but
+// they're stored top to bottom on upload, so this corrects that.
+vec4 uv_lut[4] = vec4[4](
+    vec4(1.0, 0.0, 1.0, 0.0),  // left bottom
+    vec4(1.0, 0.0, 0.0, 1.0),  // left top
+    vec4(0.0, 1.0, 1.0, 0.0),  // right bottom
+    vec4(0.0, 1.0, 0.0, 1.0)); // right top
+
+vec2 size_lut[4] = vec2[4](
+    vec2(0.0, 1.0),  // left top
+    vec2(0.0, 0.0),  // right top
+    vec2(1.0, 1.0),  // left bottom
+    vec2(1.0, 0.0)); // right bottom
+
+void main() {
+    vec4 temp = a_uv * uv_lut[gl_VertexID];
+    v_uv = vec2(temp.x + temp.y, temp.z + temp.w);
+
+    vec3 size = vec3(tile_size * size_lut[gl_VertexID], 0.0);
+    gl_Position = ortho * vec4(a_pos + size, 1.0);
+}
\ No newline at end of file
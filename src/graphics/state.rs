@@ -2,20 +2,27 @@ use crate::color::RGBA8;
 use crate::ctx;
 use crate::graphics::Texture;
 use crate::graphics::{
-    BlendFactor, Capability, ClearMode, CullFace, DepthTest, DisplayMode, OpenGL, OpenGLWindow,
-    OpenGLWindowContract, PixelStoreAlignment, WindowSettings,
+    resource, Capability, ClearMode, CursorGrab, CursorIcon, DebugMode, DepthTest, DisplayMode,
+    FramebufferTarget, Monitor, OpenGL, OpenGLWindow, OpenGLWindowContract, PixelStoreAlignment, RenderState,
+    RenderTarget, ScalingMode, Viewport, Vsync, WindowSettings,
 };
 use crate::image::Image;
+use alloc::vec::Vec;
 use cgmath::*;
-use log::trace;
+use log::{debug, error, info, trace, warn};
 
 pub(crate) struct OpenGLState {
     gl: OpenGL,
     window: OpenGLWindow,
-    logical_size: Vector2<f32>,
-    physical_size: Vector2<f32>,
+    window_physical_size: Vector2<f32>,
+    window_logical_size: Vector2<f32>,
+    scaling: ScalingMode,
+    viewport: Viewport,
+    render_target: Option<(resource::Framebuffer, Vector2<u32>)>,
     default_texture: Option<Texture>,
     max_texture_size: i32,
+    max_texture_layers: i32,
+    render_state: RenderState,
 }
 
 impl OpenGLState {
@@ -23,34 +30,108 @@ impl OpenGLState {
         let (window, gl) = OpenGLWindow::new(desc, event_loop);
         let mut gl = OpenGL::new(gl);
         let max_texture_size = gl.get_max_texture_size();
+        let max_texture_layers = gl.get_max_texture_layers();
         gl.pixel_store(PixelStoreAlignment::UnpackAlignment, 1);
-        gl.enable(Capability::CullFace);
-        gl.enable(Capability::Blend);
-        gl.enable(Capability::DepthTest);
-        // gl.enable(Capability::DebugOutput); // DEBUG
-        // gl.debug_message_callback(|source: u32, error_type: u32, id: u32, severity: u32, message: &str| {
-        //     warn!(
-        //         "source: {}, error_type: {}, id: {}, severity: {}, message: {}",
-        //         source, error_type, id, severity, message
-        //     );
-        // }); // DEBUG
+        match desc.debug {
+            DebugMode::Disabled => {}
+            DebugMode::Messages | DebugMode::Checked => {
+                if gl.supports_debug() {
+                    gl.enable(Capability::DebugOutput);
+                    gl.enable(Capability::DebugOutputSynchronous);
+                    gl.debug_message_callback(log_debug_message);
+                    info!("Graphics debug output enabled.");
+                } else {
+                    warn!("Graphics debug output is unsupported by this context.");
+                }
+                if desc.debug == DebugMode::Checked {
+                    gl.set_check_errors(true);
+                    info!("Graphics error checking enabled.");
+                }
+            }
+        }
+        if desc.context.srgb {
+            gl.enable(Capability::FramebufferSrgb);
+        }
         gl.clear_color(RGBA8::BLACK);
         gl.depth_func(DepthTest::Less);
-        gl.blend_func(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha);
-        gl.cull_face(CullFace::Back);
         trace!("MAX_TEXTURE_SIZE: {}", max_texture_size);
+        trace!("MAX_ARRAY_TEXTURE_LAYERS: {}", max_texture_layers);
+
+        let render_state = RenderState::default();
+        Self::apply_render_state(&gl, None, &render_state);
+
+        let window_physical_size = window.physical_size();
+        let window_logical_size = window.logical_size();
+        let viewport = Viewport::new(desc.scaling, window_physical_size, window_logical_size);
+        Self::apply_viewport(&gl, &viewport);
 
         let state = OpenGLState {
             gl,
-            logical_size: window.logical_size(),
-            physical_size: window.physical_size(),
+            window_physical_size,
+            window_logical_size,
+            scaling: desc.scaling,
+            viewport,
+            render_target: None,
             window,
             default_texture: None,
             max_texture_size,
+            max_texture_layers,
+            render_state,
         };
         state
     }
 
+    /// Sets the render state, only issuing calls for values that changed.
+    pub(crate) fn set_render_state(&mut self, state: &RenderState) {
+        Self::apply_render_state(&self.gl, Some(&self.render_state), state);
+        self.render_state = *state;
+    }
+
+    /// Applies the render state. Only values that differ from the current state are applied. If
+    /// there is no current state, every value is applied.
+    fn apply_render_state(gl: &OpenGL, current: Option<&RenderState>, state: &RenderState) {
+        if current.map(|current| current.blend) != Some(state.blend) {
+            match state.blend {
+                Some(mode) => {
+                    gl.enable(Capability::Blend);
+                    let (src, dst) = mode.factors();
+                    gl.blend_func(src, dst);
+                }
+                None => gl.disable(Capability::Blend),
+            }
+        }
+        if current.map(|current| current.depth_test) != Some(state.depth_test) {
+            if state.depth_test {
+                gl.enable(Capability::DepthTest);
+            } else {
+                gl.disable(Capability::DepthTest);
+            }
+        }
+        if current.map(|current| current.depth_write) != Some(state.depth_write) {
+            gl.depth_mask(state.depth_write);
+        }
+        if current.map(|current| current.cull) != Some(state.cull) {
+            match state.cull {
+                Some(mode) => {
+                    gl.enable(Capability::CullFace);
+                    gl.cull_face(mode.face());
+                }
+                None => gl.disable(Capability::CullFace),
+            }
+        }
+        if current.map(|current| current.scissor) != Some(state.scissor) {
+            match state.scissor {
+                Some(rect) => {
+                    let min = rect.min.map(|x| x.floor());
+                    let size = (rect.max.map(|x| x.ceil()) - min).map(|x| x.max(0.0));
+                    gl.enable(Capability::ScissorTest);
+                    gl.scissor(min.x as i32, min.y as i32, size.x as i32, size.y as i32);
+                }
+                None => gl.disable(Capability::ScissorTest),
+            }
+        }
+    }
+
     #[inline(always)]
     pub(crate) fn gl(&mut self) -> &mut OpenGL {
         &mut self.gl
@@ -61,15 +142,101 @@ impl OpenGLState {
         &mut self.window
     }
 
-    /// Resizes the viewport.
+    /// Gets the viewport.
+    pub(crate) fn viewport(&self) -> &Viewport {
+        &self.viewport
+    }
+
+    /// Resizes the viewport to fit a window with the given size.
     pub(crate) fn resize_viewport(&mut self, physical: Vector2<f32>, logical: Vector2<f32>) {
-        if self.logical_size != logical || self.physical_size != physical {
+        if self.window_logical_size != logical || self.window_physical_size != physical {
             trace!("Window resized: Physical({:?}) Logical({:?})", physical, logical);
-            self.logical_size = logical;
-            self.physical_size = physical;
-            self.gl.viewport(0, 0, physical.x as i32, physical.y as i32);
+            self.window_logical_size = logical;
+            self.window_physical_size = physical;
+            self.update_viewport();
+        }
+    }
+
+    fn set_scaling(&mut self, scaling: ScalingMode) {
+        if self.scaling != scaling {
+            self.scaling = scaling;
+            self.update_viewport();
+        }
+    }
+
+    fn update_viewport(&mut self) {
+        let viewport = Viewport::new(self.scaling, self.window_physical_size, self.window_logical_size);
+        if self.viewport != viewport {
+            trace!("Viewport changed: {:?}", viewport);
+            self.viewport = viewport;
+            if self.render_target.is_none() {
+                Self::apply_viewport(&self.gl, &viewport);
+            }
+        }
+    }
+
+    /// Gets the framebuffer draws are directed to and its size, or None for the window.
+    pub(crate) fn render_target(&self) -> Option<(resource::Framebuffer, Vector2<u32>)> {
+        self.render_target
+    }
+
+    /// Directs draws to the framebuffer, covering its full size, or to the window's viewport if
+    /// None.
+    pub(crate) fn set_render_target(&mut self, target: Option<(resource::Framebuffer, Vector2<u32>)>) {
+        if self.render_target != target {
+            self.render_target = target;
+            self.gl
+                .bind_framebuffer(FramebufferTarget::Framebuffer, target.map(|(framebuffer, _)| framebuffer));
+            match target {
+                Some((_, size)) => self.gl.viewport(0, 0, size.x as i32, size.y as i32),
+                None => Self::apply_viewport(&self.gl, &self.viewport),
+            }
         }
     }
+
+    /// Binds the current render target again, after another framebuffer was bound directly.
+    pub(crate) fn restore_render_target(&mut self) {
+        let framebuffer = self.render_target.map(|(framebuffer, _)| framebuffer);
+        self.gl.bind_framebuffer(FramebufferTarget::Framebuffer, framebuffer);
+    }
+
+    fn apply_viewport(gl: &OpenGL, viewport: &Viewport) {
+        gl.viewport(
+            viewport.physical_pos.x as i32,
+            viewport.physical_pos.y as i32,
+            viewport.physical_size.x as i32,
+            viewport.physical_size.y as i32,
+        );
+    }
+}
+
+/// Routes a message from the driver's debug output into the log by its severity.
+fn log_debug_message(source: u32, message_type: u32, id: u32, severity: u32, message: &str) {
+    let source = match source {
+        glow::DEBUG_SOURCE_API => "API",
+        glow::DEBUG_SOURCE_WINDOW_SYSTEM => "Window System",
+        glow::DEBUG_SOURCE_SHADER_COMPILER => "Shader Compiler",
+        glow::DEBUG_SOURCE_THIRD_PARTY => "Third Party",
+        glow::DEBUG_SOURCE_APPLICATION => "Application",
+        _ => "Other",
+    };
+    let message_type = match message_type {
+        glow::DEBUG_TYPE_ERROR => "Error",
+        glow::DEBUG_TYPE_DEPRECATED_BEHAVIOR => "Deprecated Behavior",
+        glow::DEBUG_TYPE_UNDEFINED_BEHAVIOR => "Undefined Behavior",
+        glow::DEBUG_TYPE_PORTABILITY => "Portability",
+        glow::DEBUG_TYPE_PERFORMANCE => "Performance",
+        glow::DEBUG_TYPE_MARKER => "Marker",
+        glow::DEBUG_TYPE_PUSH_GROUP => "Push Group",
+        glow::DEBUG_TYPE_POP_GROUP => "Pop Group",
+        _ => "Other",
+    };
+    match severity {
+        glow::DEBUG_SEVERITY_HIGH => error!("GL [{}, {}, {}]: {}", source, message_type, id, message),
+        glow::DEBUG_SEVERITY_MEDIUM => warn!("GL [{}, {}, {}]: {}", source, message_type, id, message),
+        glow::DEBUG_SEVERITY_LOW => info!("GL [{}, {}, {}]: {}", source, message_type, id, message),
+        _ => debug!("GL [{}, {}, {}]: {}", source, message_type, id, message),
+    }
 }
 
 /// Returns a simple 1x1 white texture. This texture is reused globally.
@@ -90,6 +257,11 @@ pub fn max_texture_size() -> i32 {
     ctx().graphics().max_texture_size
 }
 
+/// Gets the max number of layers a texture array may have on the GPU.
+pub fn max_texture_layers() -> i32 {
+    ctx().graphics().max_texture_layers
+}
+
 /// Sets the title of the window.
 ///
 /// ## Platform-specific
@@ -104,6 +276,106 @@ pub fn set_window_display_mode(display_mode: DisplayMode) {
     ctx().graphics().window.set_display_mode(display_mode);
 }
 
+/// Gets the display mode of the window in effect. This may differ from the requested display mode
+/// if the platform rejected part of it, such as an unsupported video mode.
+pub fn window_display_mode() -> DisplayMode {
+    ctx().graphics().window.display_mode()
+}
+
+/// Sets the vsync mode of the window.
+///
+/// ## Platform-specific
+///
+/// - **Web:** This is a no-op, the browser always synchronizes presentation.
+pub fn set_vsync(vsync: Vsync) {
+    ctx().graphics().window.set_vsync(vsync);
+}
+
+/// Gets the vsync mode in effect. This may differ from the requested mode if the platform doesn't
+/// support it, such as adaptive vsync falling back to vsync.
+pub fn vsync() -> Vsync {
+    ctx().graphics().window.vsync()
+}
+
+/// Sets the icon of the window. If None, the platform default is used.
+///
+/// ## Platform-specific
+///
+/// - **Web:** This is a no-op.
+pub fn set_window_icon(icon: Option<&Image<RGBA8>>) {
+    ctx().graphics().window.set_icon(icon);
+}
+
+/// Sets the minimum logical size of the window. If None, there is no minimum.
+///
+/// ## Platform-specific
+///
+/// - **Web:** This is a no-op.
+pub fn set_window_min_size(size: Option<Vector2<f32>>) {
+    ctx().graphics().window.set_min_size(size);
+}
+
+/// Sets the maximum logical size of the window. If None, there is no maximum.
+///
+/// ## Platform-specific
+///
+/// - **Web:** This is a no-op.
+pub fn set_window_max_size(size: Option<Vector2<f32>>) {
+    ctx().graphics().window.set_max_size(size);
+}
+
+/// Sets if the window is kept above all other windows.
+///
+/// ## Platform-specific
+///
+/// - **Web:** This is a no-op.
+pub fn set_window_always_on_top(always_on_top: bool) {
+    ctx().graphics().window.set_always_on_top(always_on_top);
+}
+
+/// Gets the logical position of the window's top left corner on the desktop, if the platform
+/// reports it.
+///
+/// ## Platform-specific
+///
+/// - **Web:** This is the position of the canvas on the page.
+pub fn window_position() -> Option<Vector2<f32>> {
+    ctx().graphics().window.position()
+}
+
+/// Sets the logical position of the window's top left corner on the desktop.
+///
+/// ## Platform-specific
+///
+/// - **Web:** This sets the position of the canvas on the page.
+pub fn set_window_position(position: Vector2<f32>) {
+    ctx().graphics().window.set_position(position);
+}
+
+/// Gets the monitors connected to the system.
+///
+/// ## Platform-specific
+///
+/// - **Web:** This is always empty.
+pub fn monitors() -> Vec<Monitor> {
+    ctx().graphics().window.monitors()
+}
+
+/// Sets if the cursor is visible while over the window.
+pub fn set_cursor_visible(visible: bool) {
+    ctx().graphics().window.set_cursor_visible(visible);
+}
+
+/// Sets the icon of the cursor while over the window.
+pub fn set_cursor_icon(icon: CursorIcon) {
+    ctx().graphics().window.set_cursor_icon(icon);
+}
+
+/// Sets how the cursor is grabbed by the window. See [`CursorGrab`] for platform differences.
+pub fn set_cursor_grab(grab: CursorGrab) {
+    ctx().graphics().window.set_cursor_grab(grab);
+}
+
 /// Gets the logical size of the window. This may differ from the viewport's logical size.
 pub fn window_logical_size() -> Vector2<f32> {
     ctx().graphics().window.logical_size()
@@ -114,21 +386,68 @@ pub fn window_physical_size() -> Vector2<f32> {
     ctx().graphics().window.physical_size()
 }
 
-/// Gets the logical size of the viewport. This may differ from the window's logical size.
+/// Gets the logical size of the viewport. This may differ from the window's logical size, and is
+/// the virtual resolution when the [`ScalingMode`] has one. Pass this to
+/// [`crate::math::Transform::new`] and [`crate::math::Transform::set_size`].
 pub fn viewport_logical_size() -> Vector2<f32> {
-    ctx().graphics().logical_size
+    ctx().graphics().viewport.logical_size
 }
 
 /// Gets the physical size of the viewport. This may differ from the window's physical size.
 pub fn viewport_physical_size() -> Vector2<f32> {
-    ctx().graphics().physical_size
+    ctx().graphics().viewport.physical_size
 }
 
-/// Clears the screen buffers according to the clear mode.
+/// Gets the physical position of the viewport's bottom left corner, relative to the bottom left of
+/// the window. This is non-zero when the [`ScalingMode`] adds bars around the viewport.
+pub fn viewport_physical_pos() -> Vector2<f32> {
+    ctx().graphics().viewport.physical_pos
+}
+
+/// Sets how the viewport is fit to the window. This may change the viewport's logical size, which
+/// should be read back with [`viewport_logical_size`].
+pub fn set_scaling_mode(scaling: ScalingMode) {
+    ctx().graphics().set_scaling(scaling);
+}
+
+/// Gets how the viewport is fit to the window.
+pub fn scaling_mode() -> ScalingMode {
+    ctx().graphics().scaling
+}
+
+/// Directs subsequent draws and clears to the render target. If None, draws go to the window's
+/// viewport. While a render target is set, draws cover the full render target, regardless of the
+/// [`ScalingMode`].
+pub fn set_render_target(target: Option<&RenderTarget>) {
+    ctx().graphics().set_render_target(target.map(|target| (target.framebuffer(), target.size())));
+}
+
+/// Sets the render state used by subsequent draws. Only the values that differ from the current
+/// render state are changed on the device, so this is cheap to call before every draw.
+pub fn set_render_state(state: &RenderState) {
+    ctx().graphics().set_render_state(state);
+}
+
+/// Gets the render state used by draws.
+pub fn render_state() -> RenderState {
+    ctx().graphics().render_state
+}
+
+/// Clears the screen buffers according to the clear mode. Of the render state, only the scissor
+/// limits what's cleared. Depth is cleared even while depth writes are off.
 pub fn clear(clear_mode: ClearMode) {
-    let gl = ctx().graphics().gl();
+    let graphics = ctx().graphics();
+    // Clearing depth obeys the depth mask, so it's enabled for the clear and then restored.
+    let unmask_depth = clear_mode.mode & glow::DEPTH_BUFFER_BIT != 0 && !graphics.render_state.depth_write;
+    let gl = graphics.gl();
     if let Some(clear_color) = clear_mode.color {
         gl.clear_color(clear_color);
     }
+    if unmask_depth {
+        gl.depth_mask(true);
+    }
     gl.clear(clear_mode.mode);
+    if unmask_depth {
+        gl.depth_mask(false);
+    }
 }
@@ -1,17 +1,29 @@
-use crate::color::ColorDescriptor;
+use crate::color::{ColorComponentType, ColorDescriptor, ColorLayoutFormat};
 use crate::ctx;
 use crate::graphics::{
-    max_texture_size, resource, TextureBindingTarget, TextureLoadTarget, TextureMagFilterValue,
-    TextureMinFilterValue, TextureParameterTarget, TextureSection, TextureWrapValue,
+    max_texture_layers, max_texture_size, resource, CompressedFormat, TextureBindingTarget, TextureError,
+    TextureLoadTarget, TextureMagFilterValue, TextureMinFilterValue, TextureParameterTarget, TextureSection,
+    TextureWrapValue,
 };
 use crate::image::Image;
 use alloc::rc::Rc;
 
-/// Represents a GPU resource for a texture.
+/// How a texture is sampled between texels.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TextureFilter {
+    /// The nearest texel is used. This is the default, and keeps pixel art crisp.
+    Nearest,
+    /// Neighboring texels are blended. Useful for smooth scaling and full-screen effects.
+    Linear,
+}
+
+/// Represents a GPU resource for a texture. A texture is either a single 2D image, or an array of
+/// equally sized 2D images called layers.
 pub struct Texture {
     id: resource::Texture,
     width: u32,
     height: u32,
+    layers: Option<u32>,
     rc: Rc<()>,
 }
 
@@ -21,6 +33,7 @@ impl Clone for Texture {
             id: self.id,
             width: self.width,
             height: self.height,
+            layers: self.layers,
             rc: self.rc.clone(),
         }
     }
@@ -35,44 +48,151 @@ impl Texture {
 
     /// Uploads an image to the GPU, creating a texture.
     pub fn from_image<T: ColorDescriptor>(image: &Image<T>) -> Texture {
-        let max_size = max_texture_size() as u32;
-        if image.width() > max_size || image.height() > max_size {
-            panic!(
-                "The max width or height texture may have on this device is {}. \
-                 The given image has a (width, height) of ({}, {})",
-                max_size,
-                image.width(),
-                image.height()
-            );
-        }
+        Self::create(image.width(), image.height(), Some(image.as_slice()))
+    }
+
+    /// Creates a texture with undefined contents in the format of the given color type. This is
+    /// useful for render targets, including depth textures with [`crate::color::Depth16`] or
+    /// [`crate::color::Depth32F`].
+    pub fn empty<T: ColorDescriptor>(width: u32, height: u32) -> Texture {
+        Self::create::<T>(width, height, None)
+    }
+
+    fn create<T: ColorDescriptor>(width: u32, height: u32, pixels: Option<&[T]>) -> Texture {
+        check_size(width, height);
+        check_format::<T>();
         let gl = ctx().graphics().gl();
         let id = gl.create_texture();
         let texture = Texture {
             id,
-            width: image.width(),
-            height: image.height(),
+            width,
+            height,
+            layers: None,
             rc: Rc::new(()),
         };
         gl.bind_texture(TextureBindingTarget::Texture2D, Some(id));
         gl.tex_image_2d(
             TextureLoadTarget::Texture2D,
             0,
-            image.width() as i32,
-            image.height() as i32,
+            width as i32,
+            height as i32,
             0,
-            T::layout().gpu_format(),
+            T::layout().gpu_format(T::component_type()),
             T::layout().cpu_format(),
             T::component_type().pixel_type(),
-            image.as_slice(),
+            pixels,
         );
-        gl.tex_parameter_wrap_s(TextureParameterTarget::Texture2D, TextureWrapValue::ClampToEdge);
-        gl.tex_parameter_wrap_t(TextureParameterTarget::Texture2D, TextureWrapValue::ClampToEdge);
-        gl.tex_parameter_min_filter(TextureParameterTarget::Texture2D, TextureMinFilterValue::Nearest);
-        gl.tex_parameter_mag_filter(TextureParameterTarget::Texture2D, TextureMagFilterValue::Nearest);
+        configure(TextureParameterTarget::Texture2D);
         gl.bind_texture(TextureBindingTarget::Texture2D, None);
         texture
     }
 
+    /// Uploads block compressed data to the GPU, creating a texture. The data must contain exactly
+    /// the blocks covering the given width and height. Returns an error if the format isn't
+    /// supported by the context, see [`CompressedFormat::is_supported`].
+    pub fn from_compressed(
+        format: CompressedFormat,
+        width: u32,
+        height: u32,
+        data: &[u8],
+    ) -> Result<Texture, TextureError> {
+        if !format.is_supported() {
+            return Err(TextureError::UnsupportedFormat(format));
+        }
+        let expected = format.data_len(width, height);
+        if data.len() != expected {
+            return Err(TextureError::InvalidDataLength {
+                expected,
+                actual: data.len(),
+            });
+        }
+        check_size(width, height);
+        let gl = ctx().graphics().gl();
+        let id = gl.create_texture();
+        let texture = Texture {
+            id,
+            width,
+            height,
+            layers: None,
+            rc: Rc::new(()),
+        };
+        gl.bind_texture(TextureBindingTarget::Texture2D, Some(id));
+        gl.compressed_tex_image_2d(
+            TextureLoadTarget::Texture2D,
+            0,
+            format.internal_format(),
+            width as i32,
+            height as i32,
+            data,
+        );
+        configure(TextureParameterTarget::Texture2D);
+        gl.bind_texture(TextureBindingTarget::Texture2D, None);
+        Ok(texture)
+    }
+
+    /// Uploads images to the GPU, creating a texture array with one layer per image. All images
+    /// must have the same width and height. Texture arrays are sampled with a `sampler2DArray` in
+    /// shaders, letting a single draw read from many layers.
+    pub fn from_images<T: ColorDescriptor>(images: &[Image<T>]) -> Texture {
+        assert!(!images.is_empty(), "A texture array requires at least one image.");
+        let width = images[0].width();
+        let height = images[0].height();
+        assert!(
+            images.iter().all(|image| image.width() == width && image.height() == height),
+            "All images in a texture array must have the same width and height."
+        );
+        check_size(width, height);
+        check_format::<T>();
+        let max_layers = max_texture_layers() as usize;
+        if images.len() > max_layers {
+            panic!(
+                "The max layers a texture array may have on this device is {}. {} images were given.",
+                max_layers,
+                images.len()
+            );
+        }
+        let gl = ctx().graphics().gl();
+        let id = gl.create_texture();
+        let texture = Texture {
+            id,
+            width,
+            height,
+            layers: Some(images.len() as u32),
+            rc: Rc::new(()),
+        };
+        gl.bind_texture(TextureBindingTarget::Texture2DArray, Some(id));
+        gl.tex_image_3d::<T>(
+            TextureLoadTarget::Texture2DArray,
+            0,
+            width as i32,
+            height as i32,
+            images.len() as i32,
+            0,
+            T::layout().gpu_format(T::component_type()),
+            T::layout().cpu_format(),
+            T::component_type().pixel_type(),
+            None,
+        );
+        for (layer, image) in images.iter().enumerate() {
+            gl.tex_sub_image_3d(
+                TextureLoadTarget::Texture2DArray,
+                0,
+                0,
+                0,
+                layer as i32,
+                width as i32,
+                height as i32,
+                1,
+                T::layout().cpu_format(),
+                T::component_type().pixel_type(),
+                image.as_slice(),
+            );
+        }
+        configure(TextureParameterTarget::Texture2DArray);
+        gl.bind_texture(TextureBindingTarget::Texture2DArray, None);
+        texture
+    }
+
     /// The width of the texture.
     pub fn width(&self) -> u32 {
         self.width
@@ -83,6 +203,11 @@ impl Texture {
         self.height
     }
 
+    /// The number of layers in the texture if it's a texture array, otherwise None.
+    pub fn layers(&self) -> Option<u32> {
+        self.layers
+    }
+
     /// Coordinates relative to the top left corner of the texture. (0, 0) is the top left of the
     /// texture, and (width, height) is the bottom right of the texture.
     pub fn subsection(&self, left: u32, right: u32, top: u32, bottom: u32) -> TextureSection {
@@ -90,13 +215,18 @@ impl Texture {
     }
 
     /// Sets a subsection of the texture to the given image. (0, 0) is the top left of the texture,
-    /// and (width, height) is the bottom right of the texture.
+    /// and (width, height) is the bottom right of the texture. For texture arrays, this sets the
+    /// first layer.
     /// # Arguments
     ///
     /// * `offset_x` - The top left texel x coordinate to offset the image by.
     /// * `offset_y` - The top left texel y coordinate to offset the image by.
     /// * `image` - The image to overwrite the texture with.
     pub fn set<Z: ColorDescriptor>(&self, offset_x: u32, offset_y: u32, image: &Image<Z>) {
+        if self.layers.is_some() {
+            self.set_layer(0, offset_x, offset_y, image);
+            return;
+        }
         assert!(image.width() + offset_x <= self.width && image.height() + offset_y <= self.height);
         let gl = ctx().graphics().gl();
         gl.bind_texture(TextureBindingTarget::Texture2D, Some(self.id));
@@ -114,10 +244,106 @@ impl Texture {
         gl.bind_texture(TextureBindingTarget::Texture2D, None);
     }
 
+    /// Sets a subsection of a layer of the texture array to the given image. (0, 0) is the top left
+    /// of the layer, and (width, height) is the bottom right of the layer.
+    /// # Arguments
+    ///
+    /// * `layer` - The layer to overwrite.
+    /// * `offset_x` - The top left texel x coordinate to offset the image by.
+    /// * `offset_y` - The top left texel y coordinate to offset the image by.
+    /// * `image` - The image to overwrite the layer with.
+    pub fn set_layer<Z: ColorDescriptor>(&self, layer: u32, offset_x: u32, offset_y: u32, image: &Image<Z>) {
+        let layers = self.layers.expect("The texture is not a texture array.");
+        assert!(layer < layers);
+        assert!(image.width() + offset_x <= self.width && image.height() + offset_y <= self.height);
+        let gl = ctx().graphics().gl();
+        gl.bind_texture(TextureBindingTarget::Texture2DArray, Some(self.id));
+        gl.tex_sub_image_3d(
+            TextureLoadTarget::Texture2DArray,
+            0,
+            offset_x as i32,
+            offset_y as i32,
+            layer as i32,
+            image.width() as i32,
+            image.height() as i32,
+            1,
+            Z::layout().cpu_format(),
+            Z::component_type().pixel_type(),
+            image.as_slice(),
+        );
+        gl.bind_texture(TextureBindingTarget::Texture2DArray, None);
+    }
+
+    /// Sets how the texture is sampled when it's scaled. The default is
+    /// [`TextureFilter::Nearest`]. This applies to every clone of the texture.
+    pub fn set_filter(&self, filter: TextureFilter) {
+        let (min, mag) = match filter {
+            TextureFilter::Nearest => (TextureMinFilterValue::Nearest, TextureMagFilterValue::Nearest),
+            TextureFilter::Linear => (TextureMinFilterValue::Linear, TextureMagFilterValue::Linear),
+        };
+        let (binding, target) = match self.layers {
+            Some(_) => (TextureBindingTarget::Texture2DArray, TextureParameterTarget::Texture2DArray),
+            None => (TextureBindingTarget::Texture2D, TextureParameterTarget::Texture2D),
+        };
+        let gl = ctx().graphics().gl();
+        gl.bind_texture(binding, Some(self.id));
+        gl.tex_parameter_min_filter(target, min);
+        gl.tex_parameter_mag_filter(target, mag);
+        gl.bind_texture(binding, None);
+    }
+
+    pub(crate) fn id(&self) -> resource::Texture {
+        self.id
+    }
+
     pub(crate) fn bind(&self, unit: u32) {
         let gl = ctx().graphics().gl();
         gl.active_texture(unit);
-        gl.bind_texture(TextureBindingTarget::Texture2D, Some(self.id));
+        let target = match self.layers {
+            Some(_) => TextureBindingTarget::Texture2DArray,
+            None => TextureBindingTarget::Texture2D,
+        };
+        gl.bind_texture(target, Some(self.id));
+    }
+}
+
+/// Applies the default sampling parameters to the bound texture.
+fn configure(target: TextureParameterTarget) {
+    let gl = ctx().graphics().gl();
+    gl.tex_parameter_wrap_s(target, TextureWrapValue::ClampToEdge);
+    gl.tex_parameter_wrap_t(target, TextureWrapValue::ClampToEdge);
+    gl.tex_parameter_min_filter(target, TextureMinFilterValue::Nearest);
+    gl.tex_parameter_mag_filter(target, TextureMagFilterValue::Nearest);
+}
+
+/// Panics if the context can't store textures of the color type. 16 bit normalized formats are
+/// only part of desktop OpenGL, and require an extension on OpenGL ES and WebGL.
+fn check_format<T: ColorDescriptor>() {
+    if T::component_type() != ColorComponentType::U16 || T::layout() == ColorLayoutFormat::Depth {
+        return;
+    }
+    let gl = ctx().graphics().gl();
+    let (_, _, embedded) = gl.version();
+    if (embedded || cfg!(target_arch = "wasm32"))
+        && !gl.supports_extension("GL_EXT_texture_norm16")
+        && !gl.supports_extension("EXT_texture_norm16")
+    {
+        panic!(
+            "16 bit normalized textures require the EXT_texture_norm16 extension, which this device \
+             doesn't support. The given color layout is {:?}.",
+            T::layout()
+        );
+    }
+}
+
+fn check_size(width: u32, height: u32) {
+    let max_size = max_texture_size() as u32;
+    if width > max_size || height > max_size {
+        panic!(
+            "The max width or height texture may have on this device is {}. \
+             The given image has a (width, height) of ({}, {})",
+            max_size, width, height
+        );
     }
 }
 
@@ -19,12 +19,25 @@ impl TextureSection {
     /// Coordinates relative to the top left corner of the texture. (0, 0) is the top left of the
     /// texture, and (width, height) is the bottom right of the texture.
     pub fn from_texture(texture: &Texture, left: u32, right: u32, top: u32, bottom: u32) -> TextureSection {
+        TextureSection::from_texture_size(texture.width(), texture.height(), left, right, top, bottom)
+    }
+
+    /// Coordinates relative to the top left corner of a texture with the given size. This is useful
+    /// when the texture isn't available yet, such as when loading sprite sheet metadata.
+    pub fn from_texture_size(
+        width: u32,
+        height: u32,
+        left: u32,
+        right: u32,
+        top: u32,
+        bottom: u32,
+    ) -> TextureSection {
         let left = left as f32;
         let right = right as f32;
         let top = top as f32;
         let bottom = bottom as f32;
-        let h_size = MAX_FLOAT / (texture.width() as f32);
-        let v_size = MAX_FLOAT / (texture.height() as f32);
+        let h_size = MAX_FLOAT / (width as f32);
+        let v_size = MAX_FLOAT / (height as f32);
         let h_nudge = h_size * 0.25;
         let v_nudge = v_size * 0.25;
         TextureSection(Vector4::new(
@@ -17,6 +17,9 @@ use log::trace;
 /// }
 ///
 /// impl VertexDescriptor for Demo {
+///     // Each element in the buffer is consumed once per instance. Use VertexStepRate::PerVertex
+///     // for meshes, where each element in the buffer is consumed once per vertex.
+///     const STEP_RATE: VertexStepRate = VertexStepRate::PerInstance;
 ///     const ATTRIBUTES: &'static [VertexAttribute] = &[
 ///         // This value represents the three f32s in pos's Vector3<f32>. When invoked in the
 ///         // shader, the values will be read as f32s.
@@ -28,9 +31,32 @@ use log::trace;
 /// }
 /// ```
 pub trait VertexDescriptor {
+    /// How often the shader advances to the next element in a buffer of this type. Defaults to
+    /// once per instance.
+    const STEP_RATE: VertexStepRate = VertexStepRate::PerInstance;
     const ATTRIBUTES: &'static [VertexAttribute];
 }
 
+/// Describes how often the shader advances to the next element in a vertex buffer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VertexStepRate {
+    /// The next element is read for every vertex. This is used for meshes, where each element is
+    /// a vertex of the mesh.
+    PerVertex,
+    /// The next element is read for every instance. This is used for instanced drawing, where each
+    /// element describes an instance and the shader generates the vertices, such as with sprites.
+    PerInstance,
+}
+
+impl VertexStepRate {
+    fn divisor(&self) -> u32 {
+        match self {
+            VertexStepRate::PerVertex => 0,
+            VertexStepRate::PerInstance => 1,
+        }
+    }
+}
+
 /// Describes an individual vertex attribute. These usually correspond to fields in a struct.
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct VertexAttribute {
@@ -161,7 +187,7 @@ pub(crate) fn configure_vertex<T: VertexDescriptor + Copy>(attributes: &[VertexA
     let mut size = 0;
     for attribute in attributes {
         gl.enable_vertex_attrib_array(index);
-        gl.vertex_attrib_divisor(index, 1);
+        gl.vertex_attrib_divisor(index, T::STEP_RATE.divisor());
         if attribute.output.integer {
             gl.vertex_attrib_pointer_i32(index, attribute.count, attribute.input.format, stride, size);
         } else {
@@ -177,5 +203,11 @@ pub(crate) fn configure_vertex<T: VertexDescriptor + Copy>(attributes: &[VertexA
         size += attribute.count * attribute.input.size;
         index += 1;
     }
-    trace!("Configured vertex {}: Size {}, Stride: {}", core::any::type_name::<T>(), size, stride);
+    trace!(
+        "Configured vertex {}: Size {}, Stride: {}, Step: {:?}",
+        core::any::type_name::<T>(),
+        size,
+        stride,
+        T::STEP_RATE
+    );
 }
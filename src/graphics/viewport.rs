@@ -0,0 +1,191 @@
+use cgmath::*;
+
+/// How the viewport is fit to the window. Modes with a virtual resolution keep the same amount of
+/// the world on screen regardless of the window's size.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ScalingMode {
+    /// The viewport fills the window, and its logical size is the window's logical size. Larger
+    /// windows show more of the world.
+    Window,
+    /// The viewport fills the window, and its logical size is always the virtual resolution. The
+    /// image is stretched if the window's aspect ratio differs.
+    Stretch {
+        width: f32,
+        height: f32,
+    },
+    /// The viewport is the largest area with the virtual resolution's aspect ratio that fits in the
+    /// window, centered with bars on the remaining sides.
+    Letterbox {
+        width: f32,
+        height: f32,
+    },
+    /// Like Letterbox, but each virtual pixel is always a whole number of physical pixels, keeping
+    /// pixel art crisp. The scale is at least 1, so windows smaller than the virtual resolution are
+    /// cropped.
+    PixelPerfect {
+        width: u32,
+        height: u32,
+    },
+    /// The viewport fills the window. The virtual resolution is scaled to fit, and the logical size
+    /// expands along the axis with extra room, so the virtual resolution is the minimum visible area.
+    Expand {
+        width: f32,
+        height: f32,
+    },
+}
+
+/// The area of the window drawn to, and the logical size it represents.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct Viewport {
+    /// The bottom left of the viewport in physical pixels, relative to the bottom left of the window.
+    pub physical_pos: Vector2<f32>,
+    /// The size of the viewport in physical pixels.
+    pub physical_size: Vector2<f32>,
+    /// The logical size of the viewport. This is the virtual resolution for modes that have one.
+    pub logical_size: Vector2<f32>,
+}
+
+impl Viewport {
+    /// Fits the viewport to a window with the given physical and logical size.
+    pub fn new(mode: ScalingMode, physical: Vector2<f32>, logical: Vector2<f32>) -> Viewport {
+        let full = |logical_size| Viewport {
+            physical_pos: Vector2::zero(),
+            physical_size: physical,
+            logical_size,
+        };
+        match mode {
+            ScalingMode::Window => full(logical),
+            ScalingMode::Stretch {
+                width,
+                height,
+            } => full(Vector2::new(width, height)),
+            ScalingMode::Letterbox {
+                width,
+                height,
+            } => {
+                let virtual_size = Vector2::new(width, height);
+                let scale = fit_scale(physical, virtual_size);
+                Viewport::centered(physical, virtual_size, (virtual_size * scale).map(|x| x.round()))
+            }
+            ScalingMode::PixelPerfect {
+                width,
+                height,
+            } => {
+                let virtual_size = Vector2::new(width as f32, height as f32);
+                let scale = fit_scale(physical, virtual_size).floor().max(1.0);
+                Viewport::centered(physical, virtual_size, virtual_size * scale)
+            }
+            ScalingMode::Expand {
+                width,
+                height,
+            } => {
+                let scale = fit_scale(physical, Vector2::new(width, height));
+                if scale > 0.0 {
+                    full(physical / scale)
+                } else {
+                    full(Vector2::new(width, height))
+                }
+            }
+        }
+    }
+
+    fn centered(physical: Vector2<f32>, logical_size: Vector2<f32>, size: Vector2<f32>) -> Viewport {
+        Viewport {
+            physical_pos: ((physical - size) * 0.5).map(|x| x.floor()),
+            physical_size: size,
+            logical_size,
+        }
+    }
+
+    /// Converts a physical position in the window, with (0, 0) at the bottom left, into a logical
+    /// position in the viewport, with (0, 0) at the bottom left of the viewport.
+    pub fn logical_pos(&self, physical: Vector2<f32>) -> Vector2<f32> {
+        if self.physical_size.x <= 0.0 || self.physical_size.y <= 0.0 {
+            return Vector2::zero();
+        }
+        (physical - self.physical_pos)
+            .div_element_wise(self.physical_size)
+            .mul_element_wise(self.logical_size)
+    }
+
+    /// Converts a physical position in the window into normalized viewport space, ranging from
+    /// (-1, -1) at the bottom left of the viewport to (1, 1) at the top right.
+    pub fn normalized_pos(&self, physical: Vector2<f32>) -> Vector2<f32> {
+        if self.logical_size.x <= 0.0 || self.logical_size.y <= 0.0 {
+            return Vector2::zero();
+        }
+        self.logical_pos(physical).div_element_wise(self.logical_size) * 2.0 - Vector2::new(1.0, 1.0)
+    }
+}
+
+/// The largest uniform scale that fits the size within the window.
+fn fit_scale(physical: Vector2<f32>, size: Vector2<f32>) -> f32 {
+    if size.x <= 0.0 || size.y <= 0.0 {
+        return 0.0;
+    }
+    (physical.x / size.x).min(physical.y / size.y)
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHYSICAL: Vector2<f32> = Vector2::new(1000.0, 500.0);
+    const LOGICAL: Vector2<f32> = Vector2::new(500.0, 250.0);
+
+    #[test]
+    fn letterbox_centers() {
+        let mode = ScalingMode::Letterbox {
+            width: 320.0,
+            height: 240.0,
+        };
+        let viewport = Viewport::new(mode, PHYSICAL, LOGICAL);
+        assert_eq!(viewport.physical_size, Vector2::new(667.0, 500.0));
+        assert_eq!(viewport.physical_pos, Vector2::new(166.0, 0.0));
+        assert_eq!(viewport.logical_size, Vector2::new(320.0, 240.0));
+        assert_eq!(viewport.normalized_pos(Vector2::new(166.0, 0.0)), Vector2::new(-1.0, -1.0));
+    }
+
+    #[test]
+    fn pixel_perfect_uses_whole_scales() {
+        let mode = ScalingMode::PixelPerfect {
+            width: 320,
+            height: 180,
+        };
+        let viewport = Viewport::new(mode, PHYSICAL, LOGICAL);
+        assert_eq!(viewport.physical_size, Vector2::new(640.0, 360.0));
+        assert_eq!(viewport.physical_pos, Vector2::new(180.0, 70.0));
+        assert_eq!(viewport.logical_pos(Vector2::new(182.0, 72.0)), Vector2::new(1.0, 1.0));
+        let small = Viewport::new(mode, Vector2::new(200.0, 100.0), Vector2::new(200.0, 100.0));
+        assert_eq!(small.physical_size, Vector2::new(320.0, 180.0));
+    }
+
+    #[test]
+    fn expand_grows_one_axis() {
+        let mode = ScalingMode::Expand {
+            width: 200.0,
+            height: 200.0,
+        };
+        let viewport = Viewport::new(mode, PHYSICAL, LOGICAL);
+        assert_eq!(viewport.physical_size, PHYSICAL);
+        assert_eq!(viewport.logical_size, Vector2::new(400.0, 200.0));
+    }
+
+    #[test]
+    fn window_and_stretch_fill() {
+        let window = Viewport::new(ScalingMode::Window, PHYSICAL, LOGICAL);
+        assert_eq!(window.logical_size, LOGICAL);
+        assert_eq!(window.logical_pos(Vector2::new(500.0, 250.0)), Vector2::new(250.0, 125.0));
+        let mode = ScalingMode::Stretch {
+            width: 100.0,
+            height: 100.0,
+        };
+        let stretch = Viewport::new(mode, PHYSICAL, LOGICAL);
+        assert_eq!(stretch.physical_size, PHYSICAL);
+        assert_eq!(stretch.logical_pos(Vector2::new(500.0, 250.0)), Vector2::new(50.0, 50.0));
+    }
+}
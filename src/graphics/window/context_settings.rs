@@ -0,0 +1,72 @@
+/// Configuration settings for the graphics context and its default framebuffer. If the platform
+/// can't create the requested framebuffer, multisampling and sRGB are dropped, and then the default
+/// depth and stencil buffers are used, with a warning logged for each attempt.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ContextSettings {
+    /// The number of samples per pixel for multisample anti-aliasing. Zero disables multisampling,
+    /// except on web. Counts that aren't a power of two are rounded down to the nearest power of
+    /// two.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** The sample count is chosen by the browser. Any non-zero value enables
+    ///   anti-aliasing, and zero keeps the browser's default, which is usually enabled.
+    pub multisampling: u16,
+    /// If the framebuffer should be sRGB capable. When enabled, colors written to the framebuffer
+    /// are treated as linear and encoded to sRGB.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Unsupported for the default framebuffer, and ignored.
+    pub srgb: bool,
+    /// The number of bits in the depth buffer. Zero requests no depth buffer.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** The bit depth is chosen by the browser. Any non-zero value requests a depth buffer.
+    pub depth_bits: u8,
+    /// The number of bits in the stencil buffer. Zero requests no stencil buffer.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** The bit depth is chosen by the browser. Any non-zero value requests a stencil
+    ///   buffer.
+    pub stencil_bits: u8,
+    /// The preferred OpenGL version as (major, minor), or None for the latest available. The
+    /// engine's shaders require at least 3.3.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Ignored, WebGL 2 is always used.
+    pub version: Option<(u8, u8)>,
+    /// The preferred OpenGL profile.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Ignored.
+    pub profile: ContextProfile,
+}
+
+impl Default for ContextSettings {
+    fn default() -> ContextSettings {
+        ContextSettings {
+            multisampling: 0,
+            srgb: false,
+            depth_bits: 24,
+            stencil_bits: 8,
+            version: None,
+            profile: ContextProfile::Any,
+        }
+    }
+}
+
+/// Enumeration for OpenGL profile options.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ContextProfile {
+    /// The platform will pick the profile.
+    Any,
+    /// The core profile, without deprecated functionality.
+    Core,
+    /// The compatibility profile, including deprecated functionality.
+    Compatibility,
+}
@@ -1,3 +1,5 @@
+use crate::graphics::VideoMode;
+
 /// Enumeration for window display options.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum DisplayMode {
@@ -13,14 +15,56 @@ pub enum DisplayMode {
     /// For "fake" fullscreen that takes the size of the desktop.
     WindowedFullscreen,
     /// For "real" fullscreen with a videomode change.
-    Fullscreen,
+    Fullscreen {
+        /// The index of the monitor to use, as listed by [`crate::graphics::monitors`]. If None, or
+        /// if the monitor is unavailable, the monitor the window is on is used.
+        ///
+        /// ## Platform-specific
+        ///
+        /// - **Web:** Ignored.
+        monitor: Option<usize>,
+        /// The video mode to switch the monitor to, from the monitor's
+        /// [`crate::graphics::Monitor::video_modes`]. If None, the desktop's video mode is kept.
+        /// If the monitor rejects the video mode, the desktop's video mode is kept instead, and
+        /// [`crate::graphics::window_display_mode`] will report None here.
+        ///
+        /// ## Platform-specific
+        ///
+        /// - **Web:** Ignored.
+        video_mode: Option<VideoMode>,
+    },
 }
 
-/// Enumeration for all possible vsync settings.
+/// Enumeration for all possible vsync settings. To cap the frame rate without vsync, see
+/// [`crate::wait_periodic`].
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Vsync {
     /// Vsync will be disabled.
     Disabled,
     /// Vsync will be enabled.
     Enabled,
+    /// Vsync will be enabled, but frames that miss the vertical blank are presented immediately,
+    /// tearing instead of stalling until the next one. If unsupported, this falls back to Enabled.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Unsupported.
+    Adaptive,
+}
+
+/// Enumeration for graphics debugging options.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DebugMode {
+    /// Debugging will be disabled.
+    Disabled,
+    /// A debug context will be requested, and messages reported by the driver will be routed into
+    /// the log by their severity.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Debug contexts are unavailable, so no messages are reported.
+    Messages,
+    /// Everything in Messages, and errors will be checked for after every graphics call, logging
+    /// the name of the call that produced the error. This has a significant performance cost.
+    Checked,
 }
@@ -8,14 +8,24 @@ mod wasm;
 #[cfg(target_arch = "wasm32")]
 pub use self::wasm::OpenGLWindow;
 
+mod context_settings;
 mod display_mode;
+mod monitor;
+#[cfg(not(target_arch = "wasm32"))]
+mod swap_interval;
 mod window_settings;
 
-pub use display_mode::{DisplayMode, Vsync};
+pub use context_settings::{ContextProfile, ContextSettings};
+pub use display_mode::{DebugMode, DisplayMode, Vsync};
+pub use monitor::{CursorGrab, Monitor, VideoMode};
 pub use window_settings::WindowSettings;
 
+use crate::color::RGBA8;
+use crate::image::Image;
+use alloc::vec::Vec;
 use cgmath::Vector2;
 use winit::event_loop::EventLoop;
+use winit::window::CursorIcon;
 
 pub(crate) trait OpenGLWindowContract: Sized {
     fn new(desc: &WindowSettings, event_loop: &EventLoop<()>) -> (Self, glow::Context);
@@ -39,6 +49,80 @@ pub(crate) trait OpenGLWindowContract: Sized {
     /// Sets the display mode of the window.
     fn set_display_mode(&self, display_mode: DisplayMode);
 
+    /// Sets the vsync mode of the window.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** This is a no-op, the browser always synchronizes presentation.
+    fn set_vsync(&self, vsync: Vsync);
+
+    /// Gets the vsync mode in effect. This may differ from the requested mode if the platform
+    /// doesn't support it.
+    fn vsync(&self) -> Vsync;
+
+    /// Gets the display mode in effect. This may differ from the requested display mode if the
+    /// platform rejected part of it.
+    fn display_mode(&self) -> DisplayMode;
+
+    /// Sets the icon of the window. If None, the platform default is used.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** This is a no-op.
+    fn set_icon(&self, icon: Option<&Image<RGBA8>>);
+
+    /// Sets the minimum logical size of the window. If None, there is no minimum.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** This is a no-op.
+    fn set_min_size(&self, size: Option<Vector2<f32>>);
+
+    /// Sets the maximum logical size of the window. If None, there is no maximum.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** This is a no-op.
+    fn set_max_size(&self, size: Option<Vector2<f32>>);
+
+    /// Sets if the window is kept above all other windows.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** This is a no-op.
+    fn set_always_on_top(&self, always_on_top: bool);
+
+    /// Gets the logical position of the window's top left corner on the desktop, if the platform
+    /// reports it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** This is the position of the canvas on the page.
+    fn position(&self) -> Option<Vector2<f32>>;
+
+    /// Sets the logical position of the window's top left corner on the desktop.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** This sets the position of the canvas on the page.
+    fn set_position(&self, position: Vector2<f32>);
+
+    /// Gets the monitors connected to the system.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** This is always empty.
+    fn monitors(&self) -> Vec<Monitor>;
+
+    /// Sets if the cursor is visible while over the window.
+    fn set_cursor_visible(&self, visible: bool);
+
+    /// Sets the icon of the cursor while over the window.
+    fn set_cursor_icon(&self, icon: CursorIcon);
+
+    /// Sets how the cursor is grabbed by the window.
+    fn set_cursor_grab(&self, grab: CursorGrab);
+
     /// Swaps the buffers in case of double or triple buffering. You should call this function every
     /// time you have finished rendering, or the image may not be displayed on the screen.
     ///
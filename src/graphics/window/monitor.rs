@@ -0,0 +1,56 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use cgmath::Vector2;
+
+/// Information about a monitor connected to the system.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Monitor {
+    /// The index of the monitor, used to select it in [`crate::graphics::DisplayMode::Fullscreen`].
+    pub index: usize,
+    /// A human readable name for the monitor, if one is available.
+    pub name: Option<String>,
+    /// The physical size of the monitor in pixels.
+    pub physical_size: Vector2<f32>,
+    /// The physical position of the monitor's top left corner on the desktop.
+    pub physical_position: Vector2<f32>,
+    /// The scale factor of the monitor. This is related to DPI scaling.
+    pub scale_factor: f32,
+    /// The video modes supported by the monitor, for use with
+    /// [`crate::graphics::DisplayMode::Fullscreen`].
+    pub video_modes: Vec<VideoMode>,
+}
+
+/// A resolution, refresh rate, and bit depth supported by a monitor in exclusive fullscreen.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct VideoMode {
+    /// The width of the mode in physical pixels.
+    pub width: u32,
+    /// The height of the mode in physical pixels.
+    pub height: u32,
+    /// The refresh rate of the mode in hertz.
+    pub refresh_rate: u16,
+    /// The number of bits per pixel of the mode.
+    pub bit_depth: u16,
+}
+
+/// Enumeration for cursor grab options.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CursorGrab {
+    /// The cursor can freely leave the window.
+    None,
+    /// The cursor is confined to the window.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** The cursor is locked in place, as with Locked.
+    /// - **Web:** Unsupported.
+    Confined,
+    /// The cursor is locked in place, which is useful for mouse-look controls.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / Linux:** The cursor is confined to the window, as with Confined.
+    /// - **Web:** This uses the pointer lock API, which browsers only allow in response to user
+    ///   input such as a click.
+    Locked,
+}
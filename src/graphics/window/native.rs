@@ -1,18 +1,31 @@
-use crate::graphics::{DisplayMode, OpenGLWindowContract, Vsync, WindowSettings};
+use super::swap_interval::set_swap_interval;
+use crate::color::RGBA8;
+use crate::graphics::{
+    ContextProfile, ContextSettings, CursorGrab, DebugMode, DisplayMode, Monitor, OpenGLWindowContract,
+    VideoMode, Vsync, WindowSettings,
+};
+use crate::image::Image;
+use alloc::{vec, vec::Vec};
 use cgmath::*;
-use glutin::ContextBuilder;
-use log::info;
-use winit::dpi::LogicalSize;
+use core::cell::Cell;
+use glutin::platform::ContextTraitExt;
+use glutin::{Api, ContextBuilder, GlProfile, GlRequest};
+use log::{info, warn};
+use winit::dpi::{LogicalPosition, LogicalSize};
 use winit::event_loop::EventLoop;
-use winit::window::{Fullscreen, Window, WindowBuilder};
+use winit::monitor::{MonitorHandle, VideoMode as WinitVideoMode};
+use winit::window::{CursorIcon, Fullscreen, Icon, Window, WindowBuilder};
 
 pub struct OpenGLWindow {
     inner: glutin::ContextWrapper<glutin::PossiblyCurrent, Window>,
+    display_mode: Cell<DisplayMode>,
+    vsync: Cell<Vsync>,
 }
 
 impl OpenGLWindowContract for OpenGLWindow {
     fn new(desc: &WindowSettings, event_loop: &EventLoop<()>) -> (OpenGLWindow, glow::Context) {
         let mut window_builder = WindowBuilder::new().with_title(&desc.title);
+        let mut display_mode = desc.display_mode;
         match desc.display_mode {
             DisplayMode::Windowed {
                 width,
@@ -22,32 +35,91 @@ impl OpenGLWindowContract for OpenGLWindow {
                 window_builder =
                     window_builder.with_resizable(resizable).with_inner_size(LogicalSize::new(width, height))
             }
-            DisplayMode::WindowedFullscreen | DisplayMode::Fullscreen => {
+            DisplayMode::WindowedFullscreen => {
                 let fullscreen = Fullscreen::Borderless(event_loop.primary_monitor());
                 window_builder = window_builder.with_fullscreen(Some(fullscreen));
             }
+            DisplayMode::Fullscreen {
+                monitor,
+                video_mode,
+            } => {
+                let handle = select_monitor(event_loop.available_monitors(), monitor)
+                    .or_else(|| event_loop.primary_monitor());
+                let (fullscreen, resolved) = resolve_fullscreen(handle, monitor, video_mode);
+                window_builder = window_builder.with_fullscreen(Some(fullscreen));
+                display_mode = resolved;
+            }
         }
         let mut context_builder = ContextBuilder::new();
         match desc.vsync {
             Vsync::Disabled => {
                 context_builder = context_builder.with_vsync(false);
             }
-            Vsync::Enabled => {
+            Vsync::Enabled | Vsync::Adaptive => {
                 context_builder = context_builder.with_vsync(true);
             }
         }
-        let window_context = context_builder.build_windowed(window_builder, &event_loop).unwrap();
+        if desc.debug != DebugMode::Disabled {
+            context_builder = context_builder.with_gl_debug_flag(true);
+        }
+        let context = &desc.context;
+        if let Some(version) = context.version {
+            context_builder = context_builder.with_gl(GlRequest::Specific(Api::OpenGl, version));
+        }
+        match context.profile {
+            ContextProfile::Any => {}
+            ContextProfile::Core => {
+                context_builder = context_builder.with_gl_profile(GlProfile::Core);
+            }
+            ContextProfile::Compatibility => {
+                context_builder = context_builder.with_gl_profile(GlProfile::Compatibility);
+            }
+        }
+        // Framebuffer settings the platform doesn't support fail to create a context, so fall back
+        // to settings that are more widely supported.
+        let defaults = ContextSettings::default();
+        let mut attempts = vec![
+            (sample_count(context.multisampling), context.srgb, context.depth_bits, context.stencil_bits),
+            (0, false, context.depth_bits, context.stencil_bits),
+            (0, false, defaults.depth_bits, defaults.stencil_bits),
+        ];
+        attempts.dedup();
+        let mut window_context = None;
+        for (index, &(samples, srgb, depth_bits, stencil_bits)) in attempts.iter().enumerate() {
+            let builder = context_builder
+                .clone()
+                .with_multisampling(samples)
+                .with_srgb(srgb)
+                .with_depth_buffer(depth_bits)
+                .with_stencil_buffer(stencil_bits);
+            match builder.build_windowed(window_builder.clone(), event_loop) {
+                Ok(built) => {
+                    window_context = Some(built);
+                    break;
+                }
+                Err(error) if index + 1 < attempts.len() => warn!(
+                    "Failed to create a context with {} samples, sRGB {}, {} depth bits and {} stencil bits, retrying with fewer features: {}",
+                    samples, srgb, depth_bits, stencil_bits, error
+                ),
+                Err(error) => panic!("Failed to create a context: {}", error),
+            }
+        }
+        let window_context = window_context.unwrap();
         let window_context = unsafe { window_context.make_current() }.unwrap();
         let gl = unsafe {
             glow::Context::from_loader_function(|s| window_context.get_proc_address(s) as *const _)
         };
+        let window = OpenGLWindow {
+            inner: window_context,
+            display_mode: Cell::new(display_mode),
+            vsync: Cell::new(desc.vsync),
+        };
+        window.verify_fullscreen();
+        if desc.vsync == Vsync::Adaptive {
+            window.set_vsync(Vsync::Adaptive);
+        }
         info!("Created window.");
-        (
-            OpenGLWindow {
-                inner: window_context,
-            },
-            gl,
-        )
+        (window, gl)
     }
 
     fn scale_factor(&self) -> f32 {
@@ -85,10 +157,221 @@ impl OpenGLWindowContract for OpenGLWindow {
                 self.inner.window().set_resizable(resizable);
                 self.inner.window().set_fullscreen(None);
             }
-            DisplayMode::WindowedFullscreen | DisplayMode::Fullscreen => {
+            DisplayMode::WindowedFullscreen => {
                 let fullscreen = Fullscreen::Borderless(self.inner.window().current_monitor());
                 self.inner.window().set_fullscreen(Some(fullscreen));
             }
+            DisplayMode::Fullscreen {
+                monitor,
+                video_mode,
+            } => {
+                let window = self.inner.window();
+                let handle =
+                    select_monitor(window.available_monitors(), monitor).or_else(|| window.current_monitor());
+                let (fullscreen, resolved) = resolve_fullscreen(handle, monitor, video_mode);
+                window.set_fullscreen(Some(fullscreen));
+                self.display_mode.set(resolved);
+                self.verify_fullscreen();
+                return;
+            }
+        }
+        self.display_mode.set(display_mode);
+    }
+
+    fn display_mode(&self) -> DisplayMode {
+        self.display_mode.get()
+    }
+
+    fn set_vsync(&self, vsync: Vsync) {
+        let mut vsync = vsync;
+        if vsync == Vsync::Adaptive && !self.swap_interval(-1) {
+            warn!("Adaptive vsync is unsupported, falling back to vsync.");
+            vsync = Vsync::Enabled;
+        }
+        let applied = match vsync {
+            Vsync::Disabled => self.swap_interval(0),
+            Vsync::Enabled => self.swap_interval(1),
+            Vsync::Adaptive => true,
+        };
+        if applied {
+            self.vsync.set(vsync);
+        } else {
+            warn!("Failed to set vsync to {:?}, keeping {:?}.", vsync, self.vsync.get());
+        }
+    }
+
+    fn vsync(&self) -> Vsync {
+        self.vsync.get()
+    }
+
+    fn set_icon(&self, icon: Option<&Image<RGBA8>>) {
+        let icon = match icon {
+            Some(image) => {
+                let mut rgba = Vec::with_capacity(image.as_slice().len() * 4);
+                for color in image.as_slice() {
+                    rgba.extend_from_slice(&[color.r, color.g, color.b, color.a]);
+                }
+                match Icon::from_rgba(rgba, image.width(), image.height()) {
+                    Ok(icon) => Some(icon),
+                    Err(error) => {
+                        warn!("Failed to set window icon: {}", error);
+                        return;
+                    }
+                }
+            }
+            None => None,
+        };
+        self.inner.window().set_window_icon(icon);
+    }
+
+    fn set_min_size(&self, size: Option<Vector2<f32>>) {
+        self.inner.window().set_min_inner_size(size.map(|size| LogicalSize::new(size.x, size.y)));
+    }
+
+    fn set_max_size(&self, size: Option<Vector2<f32>>) {
+        self.inner.window().set_max_inner_size(size.map(|size| LogicalSize::new(size.x, size.y)));
+    }
+
+    fn set_always_on_top(&self, always_on_top: bool) {
+        self.inner.window().set_always_on_top(always_on_top);
+    }
+
+    fn position(&self) -> Option<Vector2<f32>> {
+        let window = self.inner.window();
+        let position = window.outer_position().ok()?.to_logical::<f32>(window.scale_factor());
+        Some(Vector2::new(position.x, position.y))
+    }
+
+    fn set_position(&self, position: Vector2<f32>) {
+        self.inner.window().set_outer_position(LogicalPosition::new(position.x, position.y));
+    }
+
+    fn monitors(&self) -> Vec<Monitor> {
+        self.inner
+            .window()
+            .available_monitors()
+            .enumerate()
+            .map(|(index, monitor)| {
+                let size = monitor.size();
+                let position = monitor.position();
+                Monitor {
+                    index,
+                    name: monitor.name(),
+                    physical_size: Vector2::new(size.width as f32, size.height as f32),
+                    physical_position: Vector2::new(position.x as f32, position.y as f32),
+                    scale_factor: monitor.scale_factor() as f32,
+                    video_modes: monitor.video_modes().map(|mode| video_mode(&mode)).collect(),
+                }
+            })
+            .collect()
+    }
+
+    fn set_cursor_visible(&self, visible: bool) {
+        self.inner.window().set_cursor_visible(visible);
+    }
+
+    fn set_cursor_icon(&self, icon: CursorIcon) {
+        self.inner.window().set_cursor_icon(icon);
+    }
+
+    fn set_cursor_grab(&self, grab: CursorGrab) {
+        let grab = match grab {
+            CursorGrab::None => false,
+            CursorGrab::Confined | CursorGrab::Locked => true,
+        };
+        if let Err(error) = self.inner.window().set_cursor_grab(grab) {
+            warn!("Failed to set cursor grab: {}", error);
+        }
+    }
+}
+
+impl OpenGLWindow {
+    fn swap_interval(&self, interval: i32) -> bool {
+        unsafe {
+            let egl_display = self.inner.context().get_egl_display();
+            set_swap_interval(|symbol| self.inner.get_proc_address(symbol), egl_display, interval)
+        }
+    }
+
+    /// Falls back to borderless fullscreen if the monitor didn't switch to a requested exclusive
+    /// fullscreen video mode. The window reports the requested mode either way, so the monitor's
+    /// resolution is checked instead.
+    fn verify_fullscreen(&self) {
+        if let DisplayMode::Fullscreen {
+            monitor,
+            video_mode: Some(requested),
+        } = self.display_mode.get()
+        {
+            let window = self.inner.window();
+            let handle =
+                select_monitor(window.available_monitors(), monitor).or_else(|| window.current_monitor());
+            if let Some(handle) = &handle {
+                let size = handle.size();
+                if size.width == requested.width && size.height == requested.height {
+                    return;
+                }
+            }
+            warn!("Video mode {:?} was rejected, falling back to the desktop video mode.", requested);
+            window.set_fullscreen(Some(Fullscreen::Borderless(handle)));
+            self.display_mode.set(DisplayMode::Fullscreen {
+                monitor,
+                video_mode: None,
+            });
         }
     }
 }
+
+/// Resolves the fullscreen mode for a monitor. If the requested video mode isn't supported by the
+/// monitor, this falls back to borderless fullscreen. Returns the display mode that will be in
+/// effect.
+fn resolve_fullscreen(
+    handle: Option<MonitorHandle>,
+    monitor: Option<usize>,
+    video_mode: Option<VideoMode>,
+) -> (Fullscreen, DisplayMode) {
+    if let (Some(handle), Some(requested)) = (&handle, video_mode) {
+        if let Some(mode) = handle.video_modes().find(|mode| self::video_mode(mode) == requested) {
+            let display_mode = DisplayMode::Fullscreen {
+                monitor,
+                video_mode: Some(requested),
+            };
+            return (Fullscreen::Exclusive(mode), display_mode);
+        }
+        warn!("Video mode {:?} is unsupported, falling back to the desktop video mode.", requested);
+    }
+    let display_mode = DisplayMode::Fullscreen {
+        monitor,
+        video_mode: None,
+    };
+    (Fullscreen::Borderless(handle), display_mode)
+}
+
+fn video_mode(mode: &WinitVideoMode) -> VideoMode {
+    let size = mode.size();
+    VideoMode {
+        width: size.width,
+        height: size.height,
+        refresh_rate: mode.refresh_rate(),
+        bit_depth: mode.bit_depth(),
+    }
+}
+
+/// Selects the monitor at the given index, if there is one.
+fn select_monitor(
+    mut monitors: impl Iterator<Item = MonitorHandle>,
+    index: Option<usize>,
+) -> Option<MonitorHandle> {
+    monitors.nth(index?)
+}
+
+/// Rounds the sample count down to a power of two, since other counts are rejected.
+fn sample_count(samples: u16) -> u16 {
+    if samples == 0 {
+        return 0;
+    }
+    let rounded = 1 << (15 - samples.leading_zeros());
+    if rounded != samples {
+        warn!("Multisampling must be a power of two, using {} samples instead of {}.", rounded, samples);
+    }
+    rounded
+}
@@ -0,0 +1,150 @@
+use core::ffi::{c_char, c_ulong, c_void, CStr};
+use core::mem::transmute;
+
+/// Sets the swap interval of the current context. A negative interval requests adaptive vsync,
+/// where frames that miss the vertical blank are swapped immediately. Returns false if the interval
+/// couldn't be set.
+///
+/// # Safety
+///
+/// The context the symbols are loaded from must be current on this thread.
+pub(crate) unsafe fn set_swap_interval<F>(
+    get_proc_address: F,
+    egl_display: Option<*const c_void>,
+    interval: i32,
+) -> bool
+where
+    F: Fn(&str) -> *const c_void,
+{
+    match egl_display {
+        Some(display) => egl_swap_interval(get_proc_address, display, interval),
+        None => platform_swap_interval(get_proc_address, interval),
+    }
+}
+
+unsafe fn egl_swap_interval<F>(get_proc_address: F, display: *const c_void, interval: i32) -> bool
+where
+    F: Fn(&str) -> *const c_void,
+{
+    // EGL clamps negative intervals rather than rejecting them.
+    if interval < 0 {
+        return false;
+    }
+    let swap_interval = get_proc_address("eglSwapInterval");
+    if swap_interval.is_null() {
+        return false;
+    }
+    let swap_interval: unsafe extern "system" fn(*const c_void, i32) -> u32 = transmute(swap_interval);
+    swap_interval(display, interval) != 0
+}
+
+#[cfg(target_os = "windows")]
+unsafe fn platform_swap_interval<F>(get_proc_address: F, interval: i32) -> bool
+where
+    F: Fn(&str) -> *const c_void,
+{
+    let swap_interval = get_proc_address("wglSwapIntervalEXT");
+    if swap_interval.is_null() {
+        return false;
+    }
+    let swap_interval: unsafe extern "system" fn(i32) -> i32 = transmute(swap_interval);
+    swap_interval(interval) != 0
+}
+
+#[cfg(target_os = "macos")]
+unsafe fn platform_swap_interval<F>(get_proc_address: F, interval: i32) -> bool
+where
+    F: Fn(&str) -> *const c_void,
+{
+    const CGL_CP_SWAP_INTERVAL: i32 = 222;
+    // CGL has no adaptive swap interval.
+    if interval < 0 {
+        return false;
+    }
+    let get_current_context = get_proc_address("CGLGetCurrentContext");
+    let set_parameter = get_proc_address("CGLSetParameter");
+    if get_current_context.is_null() || set_parameter.is_null() {
+        return false;
+    }
+    let get_current_context: unsafe extern "C" fn() -> *mut c_void = transmute(get_current_context);
+    let set_parameter: unsafe extern "C" fn(*mut c_void, i32, *const i32) -> i32 = transmute(set_parameter);
+    let context = get_current_context();
+    if context.is_null() {
+        return false;
+    }
+    set_parameter(context, CGL_CP_SWAP_INTERVAL, &interval) == 0
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+unsafe fn platform_swap_interval<F>(get_proc_address: F, interval: i32) -> bool
+where
+    F: Fn(&str) -> *const c_void,
+{
+    const GLX_EXTENSIONS: i32 = 3;
+    let get_current_display = get_proc_address("glXGetCurrentDisplay");
+    let get_current_drawable = get_proc_address("glXGetCurrentDrawable");
+    let get_client_string = get_proc_address("glXGetClientString");
+    let swap_interval_ext = get_proc_address("glXSwapIntervalEXT");
+    if !get_current_display.is_null() && !get_current_drawable.is_null() && !swap_interval_ext.is_null() {
+        let get_current_display: unsafe extern "C" fn() -> *mut c_void = transmute(get_current_display);
+        let get_current_drawable: unsafe extern "C" fn() -> c_ulong = transmute(get_current_drawable);
+        let swap_interval_ext: unsafe extern "C" fn(*mut c_void, c_ulong, i32) = transmute(swap_interval_ext);
+        let display = get_current_display();
+        let drawable = get_current_drawable();
+        if !display.is_null() && drawable != 0 {
+            // A negative interval without the tear extension raises an X error, which is fatal by
+            // default, so the extension is checked first.
+            if interval < 0 {
+                if get_client_string.is_null() {
+                    return false;
+                }
+                let get_client_string: unsafe extern "C" fn(*mut c_void, i32) -> *const c_char =
+                    transmute(get_client_string);
+                let extensions = get_client_string(display, GLX_EXTENSIONS);
+                if extensions.is_null() {
+                    return false;
+                }
+                let extensions = CStr::from_ptr(extensions).to_bytes();
+                let supported = extensions
+                    .split(|&byte| byte == b' ')
+                    .any(|extension| extension == b"GLX_EXT_swap_control_tear");
+                if !supported {
+                    return false;
+                }
+            }
+            swap_interval_ext(display, drawable, interval);
+            return true;
+        }
+    }
+    if interval < 0 {
+        return false;
+    }
+    let swap_interval_mesa = get_proc_address("glXSwapIntervalMESA");
+    if swap_interval_mesa.is_null() {
+        return false;
+    }
+    let swap_interval_mesa: unsafe extern "C" fn(u32) -> i32 = transmute(swap_interval_mesa);
+    swap_interval_mesa(interval as u32) == 0
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+unsafe fn platform_swap_interval<F>(_get_proc_address: F, _interval: i32) -> bool
+where
+    F: Fn(&str) -> *const c_void,
+{
+    false
+}
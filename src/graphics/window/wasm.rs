@@ -1,14 +1,19 @@
-use crate::graphics::{DisplayMode, OpenGLWindowContract, WindowSettings};
+use crate::color::RGBA8;
+use crate::graphics::{CursorGrab, DisplayMode, Monitor, OpenGLWindowContract, Vsync, WindowSettings};
+use crate::image::Image;
+use alloc::vec::Vec;
 use cgmath::*;
-use log::info;
+use core::cell::Cell;
+use log::{info, warn};
 use wasm_bindgen::JsCast;
-use winit::dpi::LogicalSize;
+use winit::dpi::{LogicalPosition, LogicalSize};
 use winit::event_loop::EventLoop;
 use winit::platform::web::WindowExtWebSys;
-use winit::window::{Fullscreen, Window, WindowBuilder};
+use winit::window::{CursorIcon, Fullscreen, Window, WindowBuilder};
 
 pub struct OpenGLWindow {
     inner: Window,
+    display_mode: Cell<DisplayMode>,
 }
 
 impl OpenGLWindowContract for OpenGLWindow {
@@ -20,19 +25,33 @@ impl OpenGLWindowContract for OpenGLWindow {
                 height,
                 ..
             } => builder.with_inner_size(LogicalSize::new(width, height)),
-            DisplayMode::WindowedFullscreen | DisplayMode::Fullscreen => {
-                builder.with_fullscreen(Some(Fullscreen::Borderless(None)))
-            }
+            DisplayMode::WindowedFullscreen
+            | DisplayMode::Fullscreen {
+                ..
+            } => builder.with_fullscreen(Some(Fullscreen::Borderless(None))),
         };
         let winit_window = builder.build(event_loop).expect("Window build");
 
         let canvas = winit_window.canvas();
+        let context = &desc.context;
+        if context.srgb {
+            warn!("sRGB framebuffers are unsupported on web, ignoring.");
+        }
+        let mut attributes = web_sys::WebGlContextAttributes::new();
+        attributes.depth(context.depth_bits > 0).stencil(context.stencil_bits > 0);
+        // Without multisampling, the browser's default anti-aliasing is kept.
+        if context.multisampling > 0 {
+            attributes.antialias(true);
+        }
         let webgl2_context = canvas
-            .get_context("webgl2") // Result<Option<Object>, JsValue>
+            .get_context_with_context_options("webgl2", &attributes) // Result<Option<Object>, JsValue>
             .expect("Get webgl2 context A") // Option<Object>
             .expect("Get webgl2 context B") // Object
             .dyn_into::<web_sys::WebGl2RenderingContext>() // Result<WebGl2RenderingContext, Object>
             .expect("Get webgl2 context C"); // WebGl2RenderingContext
+
+        // WebGL extensions must be enabled before use, and glow doesn't enable this one.
+        let _ = webgl2_context.get_extension("EXT_texture_norm16");
         let gl = glow::Context::from_webgl2_context(webgl2_context);
 
         let window = web_sys::window().unwrap();
@@ -42,6 +61,7 @@ impl OpenGLWindowContract for OpenGLWindow {
 
         let window = OpenGLWindow {
             inner: winit_window,
+            display_mode: Cell::new(desc.display_mode),
         };
         window.set_title(&desc.title);
         window.set_display_mode(desc.display_mode);
@@ -90,9 +110,76 @@ impl OpenGLWindowContract for OpenGLWindow {
                 }
                 self.inner.set_inner_size(LogicalSize::new(width, height));
             }
-            DisplayMode::WindowedFullscreen | DisplayMode::Fullscreen => {
+            DisplayMode::WindowedFullscreen
+            | DisplayMode::Fullscreen {
+                ..
+            } => {
                 self.inner.set_fullscreen(Some(Fullscreen::Borderless(None)));
             }
         }
+        self.display_mode.set(display_mode);
+    }
+
+    fn set_vsync(&self, _vsync: Vsync) {
+        // Intentionally a no-op, the browser always synchronizes presentation.
+    }
+
+    fn vsync(&self) -> Vsync {
+        Vsync::Enabled
+    }
+
+    fn display_mode(&self) -> DisplayMode {
+        self.display_mode.get()
+    }
+
+    fn set_icon(&self, _icon: Option<&Image<RGBA8>>) {
+        // Intentionally a no-op, the page controls its icon.
+    }
+
+    fn set_min_size(&self, _size: Option<Vector2<f32>>) {
+        // Intentionally a no-op, the canvas can't be resized by the user.
+    }
+
+    fn set_max_size(&self, _size: Option<Vector2<f32>>) {
+        // Intentionally a no-op, the canvas can't be resized by the user.
+    }
+
+    fn set_always_on_top(&self, _always_on_top: bool) {
+        // Intentionally a no-op, there is no window ordering.
+    }
+
+    fn position(&self) -> Option<Vector2<f32>> {
+        let position = self.inner.outer_position().ok()?.to_logical::<f32>(self.inner.scale_factor());
+        Some(Vector2::new(position.x, position.y))
+    }
+
+    fn set_position(&self, position: Vector2<f32>) {
+        self.inner.set_outer_position(LogicalPosition::new(position.x, position.y));
+    }
+
+    fn monitors(&self) -> Vec<Monitor> {
+        Vec::new()
+    }
+
+    fn set_cursor_visible(&self, visible: bool) {
+        self.inner.set_cursor_visible(visible);
+    }
+
+    fn set_cursor_icon(&self, icon: CursorIcon) {
+        self.inner.set_cursor_icon(icon);
+    }
+
+    fn set_cursor_grab(&self, grab: CursorGrab) {
+        match grab {
+            CursorGrab::None => {
+                web_sys::window() // Option<Window>
+                    .unwrap() // Window
+                    .document() // Option<Document>
+                    .unwrap() // Document
+                    .exit_pointer_lock();
+            }
+            CursorGrab::Confined => warn!("Confining the cursor is unsupported on web, ignoring."),
+            CursorGrab::Locked => self.inner.canvas().request_pointer_lock(),
+        }
     }
 }
@@ -1,4 +1,4 @@
-use crate::graphics::{DisplayMode, Vsync};
+use crate::graphics::{ContextSettings, DebugMode, DisplayMode, ScalingMode, Vsync};
 use alloc::string::String;
 
 /// Configuration settings for the window.
@@ -8,8 +8,14 @@ pub struct WindowSettings {
     pub title: String,
     /// The display mode of the window.
     pub display_mode: DisplayMode,
+    /// How the viewport is fit to the window.
+    pub scaling: ScalingMode,
     /// Vsync mode for the window.
     pub vsync: Vsync,
+    /// Debugging mode for the graphics context.
+    pub debug: DebugMode,
+    /// Settings for the graphics context and its framebuffer.
+    pub context: ContextSettings,
 }
 
 impl Default for WindowSettings {
@@ -21,7 +27,10 @@ impl Default for WindowSettings {
                 height: 500,
                 resizable: true,
             },
+            scaling: ScalingMode::Window,
             vsync: Vsync::Disabled,
+            debug: DebugMode::Disabled,
+            context: ContextSettings::default(),
         }
     }
 }
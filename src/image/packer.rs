@@ -162,7 +162,8 @@ impl Packer {
         };
 
         assert!(skyline.right() <= self.border.right());
-        assert!(skyline.y <= self.border.bottom());
+        // A rect touching the bottom of the border leaves a skyline just past it, which has no room.
+        assert!(skyline.y <= self.border.bottom() + 1);
 
         self.skylines.insert(index, skyline);
 
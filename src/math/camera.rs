@@ -0,0 +1,276 @@
+use crate::math::{sin_rad, Transform, AABB2D};
+use cgmath::*;
+
+/// A 2D camera built on [`Transform`]. The camera is positioned by the world point at the center
+/// of the screen, and can follow a target, stay within bounds, and shake.
+///
+/// Call [`Camera::update`] and [`Camera::follow`] from [`crate::event::Event::Update`], then pass
+/// [`Camera::generate`] to the `set_ortho` function of each pass drawn through the camera.
+pub struct Camera {
+    transform: Transform,
+    position: Vector2<f32>,
+    zoom: f32,
+    rotation: f32,
+    deadzone: Vector2<f32>,
+    smoothing: f32,
+    bounds: Option<AABB2D>,
+    shake_intensity: f32,
+    shake_duration: f32,
+    shake_remaining: f32,
+    shake_time: f32,
+}
+
+impl Camera {
+    /// Creates a new camera centered on the origin, for a viewport with the given logical size.
+    pub fn new(logical_size: Vector2<f32>) -> Camera {
+        Camera {
+            transform: Transform::new(logical_size),
+            position: Vector2::zero(),
+            zoom: 1.0,
+            rotation: 0.0,
+            deadzone: Vector2::zero(),
+            smoothing: 0.0,
+            bounds: None,
+            shake_intensity: 0.0,
+            shake_duration: 0.0,
+            shake_remaining: 0.0,
+            shake_time: 0.0,
+        }
+    }
+
+    /// Logical size of the viewport. This is usually set when the window is resized.
+    pub fn set_size(&mut self, logical_size: Vector2<f32>) {
+        self.transform.set_size(logical_size);
+        self.apply();
+    }
+
+    /// The world position at the center of the screen.
+    pub fn position(&self) -> Vector2<f32> {
+        self.position
+    }
+
+    /// Moves the camera so the world position is at the center of the screen, clamped to the
+    /// camera's bounds.
+    pub fn set_position(&mut self, position: Vector2<f32>) {
+        self.position = position;
+        self.apply();
+    }
+
+    /// The zoom level. This is 1.0 by default, meaning 1 world unit takes up 1 pixel on screen.
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Sets the zoom level. Larger values zoom in.
+    pub fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = zoom;
+        self.apply();
+    }
+
+    /// The rotation of the camera, measured in turns.
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    /// Sets the rotation of the camera, measured in turns from [0, 1). The world rotates around
+    /// the center of the screen.
+    pub fn set_rotation(&mut self, rotation: f32) {
+        self.rotation = rotation;
+        self.apply();
+    }
+
+    /// Sets the size of the area around the center of the screen the follow target can move
+    /// within without moving the camera, in world units. The default is zero, which keeps the
+    /// target centered.
+    pub fn set_deadzone(&mut self, size: Vector2<f32>) {
+        self.deadzone = size;
+    }
+
+    /// Sets how long the camera takes to catch up to the follow target, in seconds. This is the time
+    /// to close about 63% of the remaining distance. The default is zero, which moves instantly.
+    pub fn set_smoothing(&mut self, smoothing: f32) {
+        self.smoothing = smoothing;
+    }
+
+    /// Sets the area of the world the camera stays within, in world units. If the visible area is
+    /// larger than the bounds on an axis, the camera is centered on the bounds on that axis. Screen
+    /// shake may briefly move past the bounds.
+    pub fn set_bounds(&mut self, bounds: Option<AABB2D>) {
+        self.bounds = bounds;
+        self.apply();
+    }
+
+    /// Moves the camera towards the target, keeping it within the deadzone. This is usually called
+    /// once per update with the update's delta in seconds.
+    pub fn follow(&mut self, target: Vector2<f32>, delta: f32) {
+        let half = self.deadzone * 0.5;
+        let offset = target - self.position;
+        let mut desired = self.position;
+        if offset.x > half.x {
+            desired.x = target.x - half.x;
+        } else if offset.x < -half.x {
+            desired.x = target.x + half.x;
+        }
+        if offset.y > half.y {
+            desired.y = target.y - half.y;
+        } else if offset.y < -half.y {
+            desired.y = target.y + half.y;
+        }
+        if self.smoothing > 0.0 {
+            let t = 1.0 - (-delta / self.smoothing).exp();
+            self.position += (desired - self.position) * t;
+        } else {
+            self.position = desired;
+        }
+        self.apply();
+    }
+
+    /// Shakes the camera. The shake starts at the intensity, measured as a distance in world
+    /// units, and fades out over the duration in seconds. A stronger shake replaces a weaker one.
+    pub fn shake(&mut self, intensity: f32, duration: f32) {
+        if duration > 0.0 && intensity >= self.current_shake() {
+            self.shake_intensity = intensity;
+            self.shake_duration = duration;
+            self.shake_remaining = duration;
+        }
+    }
+
+    /// Advances screen shake by the delta in seconds.
+    pub fn update(&mut self, delta: f32) {
+        if self.shake_remaining > 0.0 {
+            self.shake_remaining = (self.shake_remaining - delta).max(0.0);
+            self.shake_time += delta;
+            self.apply();
+        }
+    }
+
+    /// Creates a new matrix for the camera. A new matrix is only created if it has changed. See
+    /// [`Transform::generate`].
+    pub fn generate(&mut self) -> Option<Matrix4<f32>> {
+        self.transform.generate()
+    }
+
+    /// Gets the matrix for the camera. See [`Transform::matrix`].
+    pub fn matrix(&mut self) -> Matrix4<f32> {
+        self.transform.matrix()
+    }
+
+    /// The transform the camera controls.
+    pub fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    /// Converts a position in normalized screen space into world space. See
+    /// [`Transform::screen_to_world`].
+    pub fn screen_to_world(&self, normalized: Vector2<f32>) -> Vector2<f32> {
+        self.transform.screen_to_world(normalized)
+    }
+
+    /// Converts a position in world space into normalized screen space. See
+    /// [`Transform::world_to_screen`].
+    pub fn world_to_screen(&self, world: Vector2<f32>) -> Vector2<f32> {
+        self.transform.world_to_screen(world)
+    }
+
+    /// Gets the area of the world visible through the camera. See [`Transform::visible_bounds`].
+    pub fn visible_bounds(&self) -> AABB2D {
+        self.transform.visible_bounds()
+    }
+
+    fn current_shake(&self) -> f32 {
+        if self.shake_duration <= 0.0 {
+            return 0.0;
+        }
+        let fade = self.shake_remaining / self.shake_duration;
+        self.shake_intensity * fade * fade
+    }
+
+    /// Clamps the position to the bounds, and updates the transform to match the camera.
+    fn apply(&mut self) {
+        {
+            let params = self.transform.set();
+            params.scale = self.zoom;
+            params.rotation = self.rotation;
+        }
+        if let Some(bounds) = self.bounds {
+            let visible = self.visible_extent();
+            self.position.x = clamp_axis(self.position.x, bounds.min.x, bounds.max.x, visible.x);
+            self.position.y = clamp_axis(self.position.y, bounds.min.y, bounds.max.y, visible.y);
+        }
+        let shake = self.current_shake();
+        let offset =
+            Vector2::new(sin_rad(self.shake_time * 47.0), sin_rad(self.shake_time * 61.0 + 1.3)) * shake;
+        let rotation = Matrix2::from_angle(Rad(core::f32::consts::PI * 2.0 * self.rotation));
+        self.transform.set().translation = -(rotation * (self.position + offset));
+    }
+
+    /// The size of the visible area in world units, which doesn't depend on the position.
+    fn visible_extent(&self) -> Vector2<f32> {
+        let bounds = self.transform.visible_bounds();
+        bounds.max - bounds.min
+    }
+}
+
+fn clamp_axis(position: f32, min: f32, max: f32, visible: f32) -> f32 {
+    let half = visible * 0.5;
+    if max - min <= visible {
+        (min + max) * 0.5
+    } else {
+        position.max(min + half).min(max - half)
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn near(a: Vector2<f32>, b: Vector2<f32>) -> bool {
+        (a - b).magnitude() < 0.01
+    }
+
+    #[test]
+    fn screen_world_round_trip() {
+        let mut camera = Camera::new(Vector2::new(800.0, 600.0));
+        camera.set_position(Vector2::new(100.0, -50.0));
+        camera.set_zoom(2.0);
+        assert!(near(camera.screen_to_world(Vector2::zero()), Vector2::new(100.0, -50.0)));
+        assert!(near(camera.screen_to_world(Vector2::new(1.0, 1.0)), Vector2::new(300.0, 100.0)));
+        camera.set_rotation(0.125);
+        let world = Vector2::new(120.0, 30.0);
+        assert!(near(camera.screen_to_world(camera.world_to_screen(world)), world));
+    }
+
+    #[test]
+    fn follow_respects_deadzone() {
+        let mut camera = Camera::new(Vector2::new(800.0, 600.0));
+        camera.set_deadzone(Vector2::new(100.0, 100.0));
+        camera.follow(Vector2::new(40.0, -40.0), 0.016);
+        assert_eq!(camera.position(), Vector2::zero());
+        camera.follow(Vector2::new(80.0, -40.0), 0.016);
+        assert_eq!(camera.position(), Vector2::new(30.0, 0.0));
+    }
+
+    #[test]
+    fn bounds_clamp_position() {
+        let mut camera = Camera::new(Vector2::new(800.0, 600.0));
+        camera.set_bounds(Some(AABB2D::new(0.0, 0.0, 2000.0, 400.0)));
+        camera.set_position(Vector2::new(-100.0, 1000.0));
+        assert!(near(camera.position(), Vector2::new(400.0, 200.0)));
+        assert!(near(camera.visible_bounds().min, Vector2::new(0.0, -100.0)));
+    }
+
+    #[test]
+    fn shake_fades_out() {
+        let mut camera = Camera::new(Vector2::new(800.0, 600.0));
+        camera.shake(10.0, 0.5);
+        camera.update(0.1);
+        assert!(camera.current_shake() > 0.0);
+        camera.update(0.5);
+        assert_eq!(camera.current_shake(), 0.0);
+        assert!(near(camera.screen_to_world(Vector2::zero()), Vector2::zero()));
+    }
+}
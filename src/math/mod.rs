@@ -1,9 +1,11 @@
 mod aabb;
+mod camera;
 mod interpolation;
 mod transform;
 mod trigonometry;
 
 pub use self::aabb::*;
+pub use self::camera::*;
 pub use self::interpolation::*;
 pub use self::transform::*;
 pub use self::trigonometry::*;
@@ -1,3 +1,4 @@
+use crate::math::AABB2D;
 use cgmath::*;
 
 pub const IDENTITY_MATRIX: Matrix4<f32> = Matrix4::new(
@@ -79,12 +80,7 @@ impl Transform {
     /// if it has changed.
     pub fn generate(&mut self) -> Option<Matrix4<f32>> {
         if self.transform_dirty {
-            let mut translation = self.params.translation;
-            translation.x = (translation.x * self.params.scale).floor() / self.params.scale;
-            translation.y = (translation.y * self.params.scale).floor() / self.params.scale;
-            self.transform = Matrix4::from_scale(self.params.scale)
-                * Matrix4::from_translation(translation.extend(0.0))
-                * Matrix4::from_angle_z(Rad(core::f32::consts::PI * 2.0 * self.params.rotation));
+            self.transform = self.build_transform();
             self.transform_dirty = false;
         }
 
@@ -108,4 +104,49 @@ impl Transform {
         self.generate();
         self.ortho_transform
     }
+
+    /// Converts a position in normalized screen space into world space. Normalized screen space
+    /// ranges from (-1, -1) at the bottom left of the viewport to (1, 1) at the top right, matching
+    /// the `normalized_pos` of [`crate::event::Event::CursorMoved`].
+    pub fn screen_to_world(&self, normalized: Vector2<f32>) -> Vector2<f32> {
+        match (ortho_from_bounds(&self.logical_size) * self.build_transform()).invert() {
+            Some(inverse) => (inverse * normalized.extend(0.0).extend(1.0)).truncate().truncate(),
+            None => Vector2::zero(),
+        }
+    }
+
+    /// Converts a position in world space into normalized screen space. See
+    /// [`Transform::screen_to_world`].
+    pub fn world_to_screen(&self, world: Vector2<f32>) -> Vector2<f32> {
+        let matrix = ortho_from_bounds(&self.logical_size) * self.build_transform();
+        (matrix * world.extend(0.0).extend(1.0)).truncate().truncate()
+    }
+
+    /// Gets the area of the world visible through the transform, in world units. If the transform
+    /// is rotated, this is the smallest axis aligned box containing the visible area. This is
+    /// useful for culling objects that are off screen.
+    pub fn visible_bounds(&self) -> AABB2D {
+        let mut min = Vector2::new(f32::INFINITY, f32::INFINITY);
+        let mut max = Vector2::new(f32::NEG_INFINITY, f32::NEG_INFINITY);
+        for corner in [
+            Vector2::new(-1.0, -1.0),
+            Vector2::new(1.0, -1.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(-1.0, 1.0),
+        ] {
+            let world = self.screen_to_world(corner);
+            min = Vector2::new(min.x.min(world.x), min.y.min(world.y));
+            max = Vector2::new(max.x.max(world.x), max.y.max(world.y));
+        }
+        AABB2D::from_min_max(min, max)
+    }
+
+    fn build_transform(&self) -> Matrix4<f32> {
+        let mut translation = self.params.translation;
+        translation.x = (translation.x * self.params.scale).floor() / self.params.scale;
+        translation.y = (translation.y * self.params.scale).floor() / self.params.scale;
+        Matrix4::from_scale(self.params.scale)
+            * Matrix4::from_translation(translation.extend(0.0))
+            * Matrix4::from_angle_z(Rad(core::f32::consts::PI * 2.0 * self.params.rotation))
+    }
 }